@@ -39,15 +39,29 @@ extern crate alloc;
 
 use alloc::borrow::Cow;
 use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
 
 // =============================================================================
 // Attribute Value Types
 // =============================================================================
 
 /// A trait for types that can be converted to an attribute value string.
+///
+/// Values known at compile time, such as every enum in this crate, return
+/// [`Cow::Borrowed`] so converting them never allocates; only values built
+/// at runtime (`String`, numbers, [`RelTokens`] with several tokens) return
+/// [`Cow::Owned`]. Implementations for your own types should follow the
+/// same rule.
 pub trait AttributeValue {
     /// Convert to the attribute value string.
     fn to_attr_value(&self) -> Cow<'static, str>;
+
+    /// The value as a boolean, if it is one. Only `bool` returns `Some`, so
+    /// a string such as `"false"` is never mistaken for a boolean.
+    fn as_bool(&self) -> Option<bool> {
+        None
+    }
 }
 
 impl AttributeValue for &'static str {
@@ -90,6 +104,10 @@ impl AttributeValue for bool {
             Cow::Borrowed("false")
         }
     }
+
+    fn as_bool(&self) -> Option<bool> {
+        Some(*self)
+    }
 }
 
 // =============================================================================
@@ -470,17 +488,28 @@ impl AttributeValue for Target {
 /// - `Alternate`: Alternate representation of the document
 /// - `Author`: Link to the document's author
 /// - `Bookmark`: Permalink for the nearest ancestor section
+/// - `Canonical`: Preferred URL for the current document
+/// - `DnsPrefetch`: Resolve the target origin's DNS ahead of time
 /// - `External`: Link to a different website
 /// - `Help`: Link to context-sensitive help
+/// - `Icon`: Icon representing the document
 /// - `License`: Link to copyright/license information
+/// - `Manifest`: Web application manifest
+/// - `ModulePreload`: Fetch and prepare a module script early
 /// - `Next`: Next document in a sequence
 /// - `Nofollow`: Do not follow this link for SEO purposes
 /// - `Noopener`: Prevents window.opener access (security)
 /// - `Noreferrer`: Don't send referer header (privacy)
+/// - `Preconnect`: Open a connection to the target origin early
+/// - `Prefetch`: Fetch a resource likely needed by a future navigation
+/// - `Preload`: Fetch a resource needed by the current document early
 /// - `Prev`: Previous document in a sequence
 /// - `Search`: Link to a search tool
+/// - `Stylesheet`: Imports a stylesheet
 /// - `Tag`: Tag/keyword for the current document
 ///
+/// To combine several values in one `rel` attribute, use [`RelTokens`].
+///
 /// # Example
 /// ```rust
 /// use ironhtml_attributes::{AttributeValue, Rel};
@@ -507,12 +536,22 @@ pub enum Rel {
     Author,
     /// Provides a permalink to the nearest ancestor section.
     Bookmark,
+    /// Gives the preferred URL for the current document.
+    Canonical,
+    /// Asks the browser to resolve the target origin's DNS ahead of time.
+    DnsPrefetch,
     /// Indicates the link references a resource on a different site.
     External,
     /// Link to context-sensitive help information.
     Help,
+    /// Imports an icon representing the document, such as a favicon.
+    Icon,
     /// Link to copyright, license, or legal information for the document.
     License,
+    /// Imports a web application manifest.
+    Manifest,
+    /// Fetches, parses, and compiles a module script ahead of time.
+    ModulePreload,
     /// Indicates the next document in a sequence (pagination, slideshows).
     Next,
     /// Instructs search engines not to follow this link for ranking purposes.
@@ -522,10 +561,18 @@ pub enum Rel {
     Noopener,
     /// Prevents the browser from sending the Referer header. Enhances privacy.
     Noreferrer,
+    /// Opens a connection to the target origin ahead of time.
+    Preconnect,
+    /// Fetches a resource that a future navigation is likely to need.
+    Prefetch,
+    /// Fetches a resource the current document needs, ahead of discovery.
+    Preload,
     /// Indicates the previous document in a sequence (pagination, slideshows).
     Prev,
     /// Link to a search tool or interface for the current document.
     Search,
+    /// Imports a stylesheet.
+    Stylesheet,
     /// Indicates the link represents a tag or keyword for the current document.
     Tag,
 }
@@ -536,20 +583,564 @@ impl AttributeValue for Rel {
             Self::Alternate => "alternate",
             Self::Author => "author",
             Self::Bookmark => "bookmark",
+            Self::Canonical => "canonical",
+            Self::DnsPrefetch => "dns-prefetch",
             Self::External => "external",
             Self::Help => "help",
+            Self::Icon => "icon",
             Self::License => "license",
+            Self::Manifest => "manifest",
+            Self::ModulePreload => "modulepreload",
             Self::Next => "next",
             Self::Nofollow => "nofollow",
             Self::Noopener => "noopener",
             Self::Noreferrer => "noreferrer",
+            Self::Preconnect => "preconnect",
+            Self::Prefetch => "prefetch",
+            Self::Preload => "preload",
             Self::Prev => "prev",
             Self::Search => "search",
+            Self::Stylesheet => "stylesheet",
             Self::Tag => "tag",
         })
     }
 }
 
+/// A set of [`Rel`] values for a single `rel` attribute.
+///
+/// # Purpose
+/// The `rel` attribute is an unordered set of space-separated tokens, so a
+/// link can be both `noopener` and `noreferrer`, or a stylesheet that is
+/// also preloaded. `RelTokens` collects them, keeps insertion order for
+/// stable output, and drops duplicates.
+///
+/// # Usage Context
+/// - Used with: `<a>`, `<area>`, `<link>`, `<form>` elements
+/// - An empty set renders as an empty value
+///
+/// # Example
+/// ```rust
+/// use ironhtml_attributes::{AttributeValue, Rel, RelTokens};
+/// let rel = RelTokens::new()
+///     .with(Rel::Noopener)
+///     .with(Rel::Noreferrer)
+///     .with(Rel::Noopener);
+/// assert_eq!(rel.to_attr_value(), "noopener noreferrer");
+/// ```
+///
+/// ```html
+/// <a href="https://example.com" target="_blank" rel="noopener noreferrer nofollow">Link</a>
+/// ```
+///
+/// # WHATWG Specification
+/// - [The `rel` attribute](https://html.spec.whatwg.org/multipage/links.html#attr-hyperlink-rel)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RelTokens {
+    tokens: Vec<Rel>,
+}
+
+impl RelTokens {
+    /// Create an empty token set.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { tokens: Vec::new() }
+    }
+
+    /// Add a token, ignoring it if already present.
+    #[must_use]
+    pub fn with(mut self, rel: Rel) -> Self {
+        self.insert(rel);
+        self
+    }
+
+    /// Add a token in place. Returns `false` if it was already present.
+    pub fn insert(&mut self, rel: Rel) -> bool {
+        if self.tokens.contains(&rel) {
+            return false;
+        }
+        self.tokens.push(rel);
+        true
+    }
+
+    /// Whether the set contains `rel`.
+    #[must_use]
+    pub fn contains(&self, rel: Rel) -> bool {
+        self.tokens.contains(&rel)
+    }
+
+    /// Whether the set has no tokens.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// The tokens, in insertion order.
+    #[must_use]
+    pub fn as_slice(&self) -> &[Rel] {
+        &self.tokens
+    }
+}
+
+impl From<Rel> for RelTokens {
+    fn from(rel: Rel) -> Self {
+        Self {
+            tokens: alloc::vec![rel],
+        }
+    }
+}
+
+impl FromIterator<Rel> for RelTokens {
+    fn from_iter<I: IntoIterator<Item = Rel>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for rel in iter {
+            set.insert(rel);
+        }
+        set
+    }
+}
+
+impl AttributeValue for RelTokens {
+    fn to_attr_value(&self) -> Cow<'static, str> {
+        match self.tokens.as_slice() {
+            [] => Cow::Borrowed(""),
+            [rel] => rel.to_attr_value(),
+            tokens => {
+                let mut value = String::new();
+                for (i, rel) in tokens.iter().enumerate() {
+                    if i > 0 {
+                        value.push(' ');
+                    }
+                    value.push_str(&rel.to_attr_value());
+                }
+                Cow::Owned(value)
+            }
+        }
+    }
+}
+
+/// A width or pixel density descriptor for one [`SrcSet`] candidate.
+///
+/// # Valid Values
+/// - `Width(w)`: The image file is `w` pixels wide (`640w`); the browser
+///   picks a candidate using the `sizes` attribute
+/// - `Density(d)`: The image suits displays with `d` device pixels per CSS
+///   pixel (`2x`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Descriptor {
+    /// Intrinsic width in pixels, written `640w`.
+    Width(u32),
+    /// Pixel density, written `2x`.
+    Density(f64),
+}
+
+impl Descriptor {
+    /// Whether two descriptors are the same kind, both widths or both
+    /// densities.
+    const fn same_kind(self, other: Self) -> bool {
+        matches!(
+            (self, other),
+            (Self::Width(_), Self::Width(_)) | (Self::Density(_), Self::Density(_))
+        )
+    }
+}
+
+impl core::fmt::Display for Descriptor {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Width(width) => write!(f, "{width}w"),
+            Self::Density(density) => write!(f, "{density}x"),
+        }
+    }
+}
+
+/// A `srcset` list of image candidates for responsive images.
+///
+/// # Purpose
+/// Lists alternative files for one image so the browser can download the
+/// one that best fits the viewport and screen density. Building the list
+/// from typed candidates avoids malformed strings: a missing descriptor
+/// unit, a comma inside a URL, or a mix of width and density descriptors,
+/// which the spec forbids within one set.
+///
+/// # Usage Context
+/// - Used with: `<img>` and `<source>` (inside `<picture>`) elements
+/// - Width descriptors need a `sizes` attribute on the same element
+///
+/// # Example
+/// ```rust
+/// use ironhtml_attributes::{AttributeValue, Descriptor, SrcSet};
+/// let srcset = SrcSet::new()
+///     .candidate("cat-640.jpg", Descriptor::Width(640))
+///     .candidate("cat-1280.jpg", Descriptor::Width(1280));
+/// assert_eq!(srcset.to_attr_value(), "cat-640.jpg 640w, cat-1280.jpg 1280w");
+///
+/// let retina = SrcSet::new()
+///     .candidate("logo.png", Descriptor::Density(1.0))
+///     .candidate("logo@1.5x.png", Descriptor::Density(1.5));
+/// assert_eq!(retina.to_attr_value(), "logo.png 1x, logo@1.5x.png 1.5x");
+/// ```
+///
+/// ```html
+/// <img src="cat-640.jpg" alt="Cat" srcset="cat-640.jpg 640w, cat-1280.jpg 1280w"
+///      sizes="(max-width: 600px) 100vw, 640px">
+/// ```
+///
+/// # WHATWG Specification
+/// - [The `srcset` attribute](https://html.spec.whatwg.org/multipage/images.html#srcset-attributes)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SrcSet {
+    candidates: Vec<(String, Descriptor)>,
+}
+
+impl SrcSet {
+    /// Create an empty set.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            candidates: Vec::new(),
+        }
+    }
+
+    /// Add an image candidate.
+    ///
+    /// Use [`try_candidate`](Self::try_candidate) for URLs or descriptors
+    /// built at runtime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`try_candidate`](Self::try_candidate) would return an
+    /// error.
+    #[must_use]
+    pub fn candidate(self, url: impl Into<String>, descriptor: Descriptor) -> Self {
+        self.try_candidate(url, descriptor)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Add an image candidate, or return why it is invalid.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `url` is empty, contains whitespace, or starts or
+    /// ends with a comma; if a width is zero or a density is not a positive
+    /// finite number; if `descriptor` is a width and the set holds
+    /// densities, or the other way round; or if another candidate already
+    /// has the same descriptor.
+    ///
+    /// # Example
+    /// ```rust
+    /// use ironhtml_attributes::{Descriptor, SrcSet, SrcSetError};
+    /// let srcset = SrcSet::new().candidate("a.jpg", Descriptor::Width(640));
+    /// assert_eq!(
+    ///     srcset.try_candidate("b.jpg", Descriptor::Density(2.0)),
+    ///     Err(SrcSetError::MixedDescriptors)
+    /// );
+    /// ```
+    pub fn try_candidate(
+        mut self,
+        url: impl Into<String>,
+        descriptor: Descriptor,
+    ) -> Result<Self, SrcSetError> {
+        let url = url.into();
+        if url.is_empty()
+            || url.chars().any(char::is_whitespace)
+            || url.starts_with(',')
+            || url.ends_with(',')
+        {
+            return Err(SrcSetError::InvalidUrl(url));
+        }
+        let valid = match descriptor {
+            Descriptor::Width(width) => width > 0,
+            Descriptor::Density(density) => density.is_finite() && density > 0.0,
+        };
+        if !valid {
+            return Err(SrcSetError::InvalidDescriptor(descriptor));
+        }
+        if let Some((_, first)) = self.candidates.first() {
+            if !first.same_kind(descriptor) {
+                return Err(SrcSetError::MixedDescriptors);
+            }
+        }
+        if self.candidates.iter().any(|(_, d)| *d == descriptor) {
+            return Err(SrcSetError::DuplicateDescriptor(descriptor));
+        }
+        self.candidates.push((url, descriptor));
+        Ok(self)
+    }
+
+    /// Whether the set has no candidates.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.candidates.is_empty()
+    }
+}
+
+/// Why [`SrcSet::try_candidate`] rejected a candidate.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SrcSetError {
+    /// The URL is empty, contains whitespace, or starts or ends with a
+    /// comma.
+    InvalidUrl(String),
+    /// A width is zero or a density is not a positive finite number.
+    InvalidDescriptor(Descriptor),
+    /// The set already holds descriptors of the other kind.
+    MixedDescriptors,
+    /// Another candidate already has this descriptor.
+    DuplicateDescriptor(Descriptor),
+}
+
+impl core::fmt::Display for SrcSetError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidUrl(url) => write!(f, "invalid srcset URL {url:?}"),
+            Self::InvalidDescriptor(descriptor) => {
+                write!(f, "invalid srcset descriptor {descriptor}")
+            }
+            Self::MixedDescriptors => {
+                f.write_str("srcset cannot mix width and density descriptors")
+            }
+            Self::DuplicateDescriptor(descriptor) => {
+                write!(f, "duplicate srcset descriptor {descriptor}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for SrcSetError {}
+
+impl AttributeValue for SrcSet {
+    fn to_attr_value(&self) -> Cow<'static, str> {
+        let mut value = String::new();
+        for (i, (url, descriptor)) in self.candidates.iter().enumerate() {
+            if i > 0 {
+                value.push_str(", ");
+            }
+            let _ = write!(value, "{url} {descriptor}");
+        }
+        Cow::Owned(value)
+    }
+}
+
+/// An inline `style` attribute built from CSS declarations.
+///
+/// # Purpose
+/// Builds the declaration list of a `style` attribute one property at a
+/// time, so values taken from user input cannot end their declaration and
+/// smuggle in extra ones. A `;`, `{`, `}`, `\`, `"`, or `'` in a value is
+/// backslash-escaped, which CSS reads as that literal character rather
+/// than as syntax, so a value can neither end its declaration nor open a
+/// string that swallows the ones after it. Line breaks become hex escapes
+/// and the `*` of a `/*` is escaped, so no comment can start either.
+/// Values are therefore literal text: quoted strings such as
+/// `url("a.png")` and CSS escapes such as `\2014` are kept as-is rather
+/// than interpreted.
+///
+/// Named `InlineStyle` rather than `Style` to stay clear of the `<style>`
+/// element type.
+///
+/// # Usage Context
+/// - Used with: All HTML elements (global attribute)
+/// - Setting a property again replaces its earlier value in place
+///
+/// # Example
+/// ```rust
+/// use ironhtml_attributes::{AttributeValue, InlineStyle};
+/// let style = InlineStyle::new()
+///     .prop("color", "red")
+///     .prop("margin", "0 auto");
+/// assert_eq!(style.to_attr_value(), "color: red; margin: 0 auto");
+///
+/// let hostile = InlineStyle::new().color("red; position: fixed");
+/// assert_eq!(hostile.to_attr_value(), r"color: red\; position: fixed");
+/// ```
+///
+/// ```html
+/// <div style="color: red; margin: 0 auto">Centered</div>
+/// ```
+///
+/// # WHATWG Specification
+/// - [The `style` attribute](https://html.spec.whatwg.org/multipage/dom.html#the-style-attribute)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InlineStyle {
+    declarations: Vec<(String, String)>,
+}
+
+impl InlineStyle {
+    /// Create an empty style.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            declarations: Vec::new(),
+        }
+    }
+
+    /// Set a CSS property, replacing any earlier value for it.
+    ///
+    /// `name` is a property such as `margin-top`, a vendor-prefixed one
+    /// such as `-webkit-line-clamp`, or a custom property such as
+    /// `--accent`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` is not a valid property name.
+    #[must_use]
+    pub fn prop(mut self, name: &str, value: impl Into<String>) -> Self {
+        assert!(is_css_property(name), "invalid CSS property name {name:?}");
+        let value = value.into();
+        if let Some((_, existing)) = self.declarations.iter_mut().find(|(n, _)| n == name) {
+            *existing = value;
+        } else {
+            self.declarations.push((name.into(), value));
+        }
+        self
+    }
+
+    /// Set `color`.
+    #[must_use]
+    pub fn color(self, value: impl Into<String>) -> Self {
+        self.prop("color", value)
+    }
+
+    /// Set `background`.
+    #[must_use]
+    pub fn background(self, value: impl Into<String>) -> Self {
+        self.prop("background", value)
+    }
+
+    /// Set `display`.
+    #[must_use]
+    pub fn display(self, value: impl Into<String>) -> Self {
+        self.prop("display", value)
+    }
+
+    /// Set `width`.
+    #[must_use]
+    pub fn width(self, value: impl Into<String>) -> Self {
+        self.prop("width", value)
+    }
+
+    /// Set `height`.
+    #[must_use]
+    pub fn height(self, value: impl Into<String>) -> Self {
+        self.prop("height", value)
+    }
+
+    /// Set `margin`.
+    #[must_use]
+    pub fn margin(self, value: impl Into<String>) -> Self {
+        self.prop("margin", value)
+    }
+
+    /// Set `padding`.
+    #[must_use]
+    pub fn padding(self, value: impl Into<String>) -> Self {
+        self.prop("padding", value)
+    }
+
+    /// Whether no property is set.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.declarations.is_empty()
+    }
+}
+
+/// Whether `name` is a CSS property name: `--` followed by a custom name,
+/// or an optional `-` vendor prefix, a letter, then letters, digits, and
+/// `-`.
+fn is_css_property(name: &str) -> bool {
+    if let Some(custom) = name.strip_prefix("--") {
+        return !custom.is_empty()
+            && custom
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    }
+    let name = name.strip_prefix('-').unwrap_or(name);
+    name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+impl AttributeValue for InlineStyle {
+    fn to_attr_value(&self) -> Cow<'static, str> {
+        let mut value = String::new();
+        for (i, (name, declared)) in self.declarations.iter().enumerate() {
+            if i > 0 {
+                value.push_str("; ");
+            }
+            value.push_str(name);
+            value.push_str(": ");
+            escape_css_value(&mut value, declared);
+        }
+        Cow::Owned(value)
+    }
+}
+
+/// Append `declared` to `value` as literal CSS: characters that could end
+/// the declaration, open a string, or start a comment are escaped.
+fn escape_css_value(value: &mut String, declared: &str) {
+    let mut prev = None;
+    for c in declared.chars() {
+        match c {
+            ';' | '{' | '}' | '\\' | '"' | '\'' => {
+                value.push('\\');
+                value.push(c);
+            }
+            '*' if prev == Some('/') => value.push_str("\\*"),
+            '\n' => value.push_str("\\a "),
+            '\r' => value.push_str("\\d "),
+            '\x0C' => value.push_str("\\c "),
+            _ => value.push(c),
+        }
+        prev = Some(c);
+    }
+}
+
+/// The `download` attribute value for hyperlinks.
+///
+/// # Purpose
+/// Tells the browser to download the linked resource instead of navigating
+/// to it, optionally suggesting a filename for the saved file.
+///
+/// # Usage Context
+/// - Used with: `<a>`, `<area>` elements
+/// - Same-origin only: browsers ignore it for cross-origin URLs
+///
+/// # Valid Values
+/// - `Default`: Bare attribute; the browser picks the filename
+/// - `Filename(name)`: Suggests `name` as the filename
+///
+/// # Example
+/// ```rust
+/// use ironhtml_attributes::{AttributeValue, Download};
+/// let download = Download::Filename("report.pdf".into());
+/// assert_eq!(download.to_attr_value(), "report.pdf");
+/// assert_eq!(Download::Default.to_attr_value(), "");
+/// ```
+///
+/// ```html
+/// <a href="/files/report" download>Download</a>
+/// <a href="/files/report" download="report.pdf">Download PDF</a>
+/// ```
+///
+/// # WHATWG Specification
+/// - [The `download` attribute](https://html.spec.whatwg.org/multipage/links.html#attr-hyperlink-download)
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Download {
+    /// Download using a filename chosen by the browser. Renders as a bare
+    /// `download` attribute.
+    #[default]
+    Default,
+    /// Download and suggest this filename.
+    Filename(String),
+}
+
+impl AttributeValue for Download {
+    fn to_attr_value(&self) -> Cow<'static, str> {
+        match self {
+            Self::Default => Cow::Borrowed(""),
+            Self::Filename(name) => Cow::Owned(name.clone()),
+        }
+    }
+}
+
 /// The `loading` attribute values for lazy-loading resources.
 ///
 /// # Purpose
@@ -1521,6 +2112,940 @@ impl AttributeValue for Sandbox {
     }
 }
 
+/// The `http-equiv` attribute values for `<meta>` pragma directives.
+///
+/// # Purpose
+/// Makes a `<meta>` element act like the HTTP response header of the same
+/// name. The directive's value goes in the `content` attribute.
+///
+/// # Usage Context
+/// - Used with: `<meta>` elements inside `<head>`
+/// - Requires: a `content` attribute carrying the directive's value
+///
+/// # Valid Values
+/// - `ContentSecurityPolicy`: Declares a Content Security Policy
+/// - `Refresh`: Reloads or redirects after a delay
+/// - `XUaCompatible`: Legacy Internet Explorer compatibility mode
+/// - `ContentType`: Legacy character encoding declaration
+///
+/// # Example
+/// ```rust
+/// use ironhtml_attributes::{AttributeValue, HttpEquiv};
+/// let equiv = HttpEquiv::ContentSecurityPolicy;
+/// assert_eq!(equiv.to_attr_value(), "content-security-policy");
+/// ```
+///
+/// ```html
+/// <meta http-equiv="content-security-policy" content="default-src 'self'">
+/// <meta http-equiv="refresh" content="5; url=https://example.com/">
+/// ```
+///
+/// # WHATWG Specification
+/// - [Pragma directives](https://html.spec.whatwg.org/multipage/semantics.html#pragma-directives)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HttpEquiv {
+    /// Declares a Content Security Policy for the document. The `content`
+    /// attribute holds the policy, e.g. `default-src 'self'`.
+    ContentSecurityPolicy,
+    /// Reloads the page, or redirects to a URL, after a number of seconds.
+    /// The `content` attribute holds e.g. `5; url=/next`.
+    Refresh,
+    /// Selects a document mode in legacy Internet Explorer. The only
+    /// conforming `content` value is `IE=edge`.
+    XUaCompatible,
+    /// Legacy form of the `charset` attribute. The `content` attribute
+    /// must be `text/html; charset=utf-8`.
+    ContentType,
+}
+
+impl AttributeValue for HttpEquiv {
+    fn to_attr_value(&self) -> Cow<'static, str> {
+        Cow::Borrowed(match self {
+            Self::ContentSecurityPolicy => "content-security-policy",
+            Self::Refresh => "refresh",
+            Self::XUaCompatible => "x-ua-compatible",
+            Self::ContentType => "content-type",
+        })
+    }
+}
+
+/// Common `name` attribute values for `<meta>` elements.
+///
+/// # Purpose
+/// Names the piece of document metadata a `<meta>` element provides. The
+/// metadata value goes in the `content` attribute.
+///
+/// # Usage Context
+/// - Used with: `<meta>` elements inside `<head>`
+/// - Requires: a `content` attribute carrying the metadata value
+///
+/// # Valid Values
+/// - `Viewport`: Viewport sizing hints for mobile browsers
+/// - `Description`: Short summary of the page
+/// - `Author`: Name of the page's author
+/// - `Robots`: Crawling and indexing instructions for search engines
+/// - `ThemeColor`: Color used by the browser to decorate the page
+///
+/// # Example
+/// ```rust
+/// use ironhtml_attributes::{AttributeValue, MetaName};
+/// let name = MetaName::ThemeColor;
+/// assert_eq!(name.to_attr_value(), "theme-color");
+/// ```
+///
+/// ```html
+/// <meta name="viewport" content="width=device-width, initial-scale=1">
+/// <meta name="description" content="Type-safe HTML for Rust">
+/// <meta name="theme-color" content="#0d6efd">
+/// ```
+///
+/// # WHATWG Specification
+/// - [Standard metadata names](https://html.spec.whatwg.org/multipage/semantics.html#standard-metadata-names)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetaName {
+    /// Viewport sizing hints, e.g. `width=device-width, initial-scale=1`.
+    Viewport,
+    /// A short, free-form summary of the page, often shown by search engines.
+    Description,
+    /// The name of the page's author.
+    Author,
+    /// Crawling and indexing instructions, e.g. `noindex, nofollow`.
+    Robots,
+    /// A CSS color the browser may use to decorate the surrounding UI.
+    ThemeColor,
+}
+
+impl AttributeValue for MetaName {
+    fn to_attr_value(&self) -> Cow<'static, str> {
+        Cow::Borrowed(match self {
+            Self::Viewport => "viewport",
+            Self::Description => "description",
+            Self::Author => "author",
+            Self::Robots => "robots",
+            Self::ThemeColor => "theme-color",
+        })
+    }
+}
+
+/// The `type` attribute values for `<script>` elements.
+///
+/// # Purpose
+/// Selects how the browser treats a `<script>` element: as a classic script,
+/// as a module, or as a data block that is never executed.
+///
+/// # Usage Context
+/// - Used with: `<script>` elements
+/// - Default: a classic JavaScript script when the attribute is omitted
+///
+/// # Valid Values
+/// - `JavaScript`: Classic script (the default, so the attribute is omitted)
+/// - `Module`: JavaScript module script
+/// - `ImportMap`: Import map for module specifiers (JSON)
+/// - `JsonLd`: JSON-LD structured data block
+/// - `SpeculationRules`: Prefetch and prerender rules (JSON)
+///
+/// # Example
+/// ```rust
+/// use ironhtml_attributes::{AttributeValue, ScriptType};
+/// let script_type = ScriptType::JsonLd;
+/// assert_eq!(script_type.to_attr_value(), "application/ld+json");
+/// ```
+///
+/// ```html
+/// <script type="module" src="app.js"></script>
+/// <script type="application/ld+json">{"@type": "Organization"}</script>
+/// ```
+///
+/// # WHATWG Specification
+/// - [The `type` attribute](https://html.spec.whatwg.org/multipage/scripting.html#attr-script-type)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScriptType {
+    /// A classic script. This is the default, so setters omit the attribute
+    /// rather than write `text/javascript`.
+    #[default]
+    JavaScript,
+    /// A JavaScript module, which can use `import` and is deferred by default.
+    Module,
+    /// A JSON import map that maps module specifiers to URLs.
+    ImportMap,
+    /// A JSON-LD data block, typically schema.org structured data.
+    JsonLd,
+    /// JSON rules telling the browser which pages to prefetch or prerender.
+    SpeculationRules,
+}
+
+impl AttributeValue for ScriptType {
+    fn to_attr_value(&self) -> Cow<'static, str> {
+        Cow::Borrowed(match self {
+            Self::JavaScript => "text/javascript",
+            Self::Module => "module",
+            Self::ImportMap => "importmap",
+            Self::JsonLd => "application/ld+json",
+            Self::SpeculationRules => "speculationrules",
+        })
+    }
+}
+
+/// The `aria-live` attribute values for live regions.
+///
+/// # Purpose
+/// Tells assistive technologies how to announce changes to a region that
+/// is updated after the page loads, such as a status message or a log.
+///
+/// # Usage Context
+/// - Used with: any element, typically `<div>` or `<output>` status areas
+/// - Related: `aria-atomic`, `aria-busy`, and the `status`/`alert`/`log` roles
+///
+/// # Valid Values
+/// - `Off`: Changes are not announced (the default)
+/// - `Polite`: Changes are announced when the user is idle
+/// - `Assertive`: Changes are announced immediately
+///
+/// # Example
+/// ```rust
+/// use ironhtml_attributes::{AttributeValue, AriaLive};
+/// let live = AriaLive::Polite;
+/// assert_eq!(live.to_attr_value(), "polite");
+/// ```
+///
+/// ```html
+/// <div aria-live="polite">3 items in your cart</div>
+/// <div aria-live="assertive">Connection lost</div>
+/// ```
+///
+/// # WAI-ARIA Specification
+/// - [`aria-live`](https://www.w3.org/TR/wai-aria-1.2/#aria-live)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AriaLive {
+    /// Updates are not announced unless the user is focused on the region.
+    Off,
+    /// Updates are announced at the next graceful opportunity, without
+    /// interrupting the user. Suits most status messages.
+    Polite,
+    /// Updates are announced immediately, interrupting the user. Reserve
+    /// for urgent information.
+    Assertive,
+}
+
+impl AttributeValue for AriaLive {
+    fn to_attr_value(&self) -> Cow<'static, str> {
+        Cow::Borrowed(match self {
+            Self::Off => "off",
+            Self::Polite => "polite",
+            Self::Assertive => "assertive",
+        })
+    }
+}
+
+/// Common values for the ARIA `role` attribute.
+///
+/// # Purpose
+/// Gives an element a semantic role for assistive technologies when its
+/// native HTML semantics are missing or insufficient.
+///
+/// # Usage Context
+/// - Used with: any element
+/// - Prefer native elements (`<nav>`, `<button>`, ...) where one exists
+/// - The `Alert`, `Log`, `Status`, and `Timer` roles are implicit live
+///   regions
+///
+/// # Example
+/// ```rust
+/// use ironhtml_attributes::{AttributeValue, Role};
+/// let role = Role::Status;
+/// assert_eq!(role.to_attr_value(), "status");
+/// ```
+///
+/// ```html
+/// <div role="status" aria-live="polite">Saved</div>
+/// <div role="alert">Payment failed</div>
+/// ```
+///
+/// # WAI-ARIA Specification
+/// - [Definition of roles](https://www.w3.org/TR/wai-aria-1.2/#role_definitions)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    /// Important, usually time-sensitive information. An assertive live
+    /// region.
+    Alert,
+    /// A dialog that conveys an alert and requires a response.
+    AlertDialog,
+    /// Site-oriented content at the start of the page, such as a header.
+    Banner,
+    /// A clickable element that triggers an action.
+    Button,
+    /// Supporting content related to the main content.
+    Complementary,
+    /// Information about the page, such as a footer.
+    ContentInfo,
+    /// A window separate from the rest of the page.
+    Dialog,
+    /// A region where new information is appended in order, such as a chat
+    /// history. A polite live region.
+    Log,
+    /// The main content of the page.
+    Main,
+    /// A region of non-essential, frequently changing information.
+    Marquee,
+    /// A collection of navigation links.
+    Navigation,
+    /// Removes the element's implicit semantics.
+    Presentation,
+    /// Displays the progress of a long-running task.
+    ProgressBar,
+    /// A significant section of the page that deserves a label.
+    Region,
+    /// A section containing search functionality.
+    Search,
+    /// Advisory information that is not urgent enough for an alert. A
+    /// polite live region.
+    Status,
+    /// A tab in a tab list.
+    Tab,
+    /// A list of tabs.
+    TabList,
+    /// The content panel of a tab.
+    TabPanel,
+    /// A counter of elapsed or remaining time.
+    Timer,
+}
+
+impl AttributeValue for Role {
+    fn to_attr_value(&self) -> Cow<'static, str> {
+        Cow::Borrowed(match self {
+            Self::Alert => "alert",
+            Self::AlertDialog => "alertdialog",
+            Self::Banner => "banner",
+            Self::Button => "button",
+            Self::Complementary => "complementary",
+            Self::ContentInfo => "contentinfo",
+            Self::Dialog => "dialog",
+            Self::Log => "log",
+            Self::Main => "main",
+            Self::Marquee => "marquee",
+            Self::Navigation => "navigation",
+            Self::Presentation => "presentation",
+            Self::ProgressBar => "progressbar",
+            Self::Region => "region",
+            Self::Search => "search",
+            Self::Status => "status",
+            Self::Tab => "tab",
+            Self::TabList => "tablist",
+            Self::TabPanel => "tabpanel",
+            Self::Timer => "timer",
+        })
+    }
+}
+
+/// The `shape` attribute of `<area>`, together with its coordinates.
+///
+/// # Purpose
+/// Defines the clickable region of an image map. Each shape carries the
+/// coordinates it needs, so `shape` and `coords` cannot disagree.
+///
+/// # Usage Context
+/// - Used with: `<area>` elements only
+/// - Coordinates are CSS pixels from the image's top-left corner
+/// - `to_attr_value` gives the `shape` keyword; [`Shape::coords`] gives
+///   the `coords` value
+///
+/// # Valid Values
+/// - `Rect`: Rectangle from the top-left `[x1, y1]` to the bottom-right
+///   `[x2, y2]` corner
+/// - `Circle`: Circle centred on `(x, y)` with radius `r`
+/// - `Poly`: Polygon through the listed points
+/// - `Default`: The whole image; takes no coordinates
+///
+/// # Example
+/// ```rust
+/// use ironhtml_attributes::{AttributeValue, Shape};
+/// let shape = Shape::Circle(50, 50, 20);
+/// assert_eq!(shape.to_attr_value(), "circle");
+/// assert_eq!(shape.coords().as_deref(), Some("50,50,20"));
+/// ```
+///
+/// ```html
+/// <area shape="rect" coords="0,0,100,50" href="/top" alt="Top">
+/// <area shape="poly" coords="0,0,10,0,5,10" href="/tri" alt="Triangle">
+/// ```
+///
+/// # WHATWG Specification
+/// - [The `area` element](https://html.spec.whatwg.org/multipage/image-maps.html#the-area-element)
+/// - [The `shape` attribute](https://html.spec.whatwg.org/multipage/image-maps.html#attr-area-shape)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape<'a> {
+    /// A rectangle, `[x1, y1, x2, y2]`.
+    Rect([u32; 4]),
+    /// A circle, `(x, y, radius)`.
+    Circle(u32, u32, u32),
+    /// A polygon through the given `(x, y)` points.
+    Poly(&'a [(u32, u32)]),
+    /// The whole image.
+    Default,
+}
+
+impl Shape<'_> {
+    /// The comma-separated `coords` value, or `None` for
+    /// [`Shape::Default`], which has no coordinates.
+    #[must_use]
+    pub fn coords(&self) -> Option<String> {
+        use core::fmt::Write;
+
+        let mut out = String::new();
+        let mut push = |n: u32| {
+            if !out.is_empty() {
+                out.push(',');
+            }
+            let _ = write!(out, "{n}");
+        };
+        match *self {
+            Self::Rect(corners) => corners.into_iter().for_each(&mut push),
+            Self::Circle(x, y, r) => [x, y, r].into_iter().for_each(&mut push),
+            Self::Poly(points) => {
+                for &(x, y) in points {
+                    push(x);
+                    push(y);
+                }
+            }
+            Self::Default => return None,
+        }
+        Some(out)
+    }
+}
+
+impl AttributeValue for Shape<'_> {
+    fn to_attr_value(&self) -> Cow<'static, str> {
+        Cow::Borrowed(match self {
+            Self::Rect(_) => "rect",
+            Self::Circle(..) => "circle",
+            Self::Poly(_) => "poly",
+            Self::Default => "default",
+        })
+    }
+}
+
+/// The `capture` attribute values for file inputs.
+///
+/// # Purpose
+/// Asks the device to capture a new photo or video with its camera instead
+/// of picking an existing file.
+///
+/// # Usage Context
+/// - Used with: `<input type="file">` elements
+/// - Usually combined with an `accept` of `image/*` or `video/*`
+/// - Ignored by devices without a camera
+///
+/// # Valid Values
+/// - `User`: The user-facing (front) camera
+/// - `Environment`: The outward-facing (rear) camera
+///
+/// # Example
+/// ```rust
+/// use ironhtml_attributes::{AttributeValue, Capture};
+/// let capture = Capture::Environment;
+/// assert_eq!(capture.to_attr_value(), "environment");
+/// ```
+///
+/// ```html
+/// <input type="file" accept="image/*" capture="user">
+/// ```
+///
+/// # Specification
+/// - [HTML Media Capture](https://www.w3.org/TR/html-media-capture/#the-capture-attribute)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Capture {
+    /// The camera facing the user, such as for a selfie.
+    User,
+    /// The camera facing away from the user, such as for a document scan.
+    Environment,
+}
+
+impl AttributeValue for Capture {
+    fn to_attr_value(&self) -> Cow<'static, str> {
+        Cow::Borrowed(match self {
+            Self::User => "user",
+            Self::Environment => "environment",
+        })
+    }
+}
+
+/// Event handler attributes available on every HTML element.
+///
+/// # Purpose
+/// Names the inline event handler attribute, such as `onclick`, that runs
+/// a snippet of JavaScript when the event fires. The value is the
+/// attribute's *name*; the handler code is the attribute value.
+///
+/// # Usage Context
+/// - Used with: any element (the `GlobalEventHandlers` set), plus pointer
+///   events from Pointer Events
+/// - Handlers fire only for events that reach the element, so `onsubmit`
+///   matters on `<form>` and `onplay` on media elements
+/// - Window-level handlers on `<body>` are in [`WindowEventHandler`]
+///
+/// # Example
+/// ```rust
+/// use ironhtml_attributes::EventHandler;
+/// assert_eq!(EventHandler::Click.attr_name(), "onclick");
+/// assert_eq!(EventHandler::Submit.attr_name(), "onsubmit");
+/// ```
+///
+/// ```html
+/// <button onclick="toggleMenu()">Menu</button>
+/// <form onsubmit="return validate(this)">...</form>
+/// ```
+///
+/// # WHATWG Specification
+/// - [Event handlers on elements](https://html.spec.whatwg.org/multipage/webappapis.html#event-handlers-on-elements,-document-objects,-and-window-objects)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventHandler {
+    /// `onabort`: Loading of a media resource was aborted.
+    Abort,
+    /// `onauxclick`: A non-primary pointer button was clicked.
+    AuxClick,
+    /// `onbeforeinput`: An editable element's value is about to change.
+    BeforeInput,
+    /// `onbeforematch`: A `hidden="until-found"` element is about to be revealed.
+    BeforeMatch,
+    /// `onbeforetoggle`: A popover or dialog is about to be shown or hidden.
+    BeforeToggle,
+    /// `onblur`: The element lost focus.
+    Blur,
+    /// `oncancel`: A dialog or file picker was dismissed.
+    Cancel,
+    /// `oncanplay`: Media can start playing.
+    CanPlay,
+    /// `oncanplaythrough`: Media can play to the end without buffering.
+    CanPlayThrough,
+    /// `onchange`: A form control's value was committed.
+    Change,
+    /// `onclick`: The element was clicked or activated.
+    Click,
+    /// `onclose`: A dialog was closed.
+    Close,
+    /// `oncontextlost`: A canvas context was lost.
+    ContextLost,
+    /// `oncontextmenu`: A context menu was requested.
+    ContextMenu,
+    /// `oncontextrestored`: A lost canvas context was restored.
+    ContextRestored,
+    /// `oncopy`: The user copied content.
+    Copy,
+    /// `oncuechange`: A text track's active cues changed.
+    CueChange,
+    /// `oncut`: The user cut content.
+    Cut,
+    /// `ondblclick`: The element was double-clicked.
+    DblClick,
+    /// `ondrag`: The element is being dragged.
+    Drag,
+    /// `ondragend`: A drag operation ended.
+    DragEnd,
+    /// `ondragenter`: A dragged item entered the element.
+    DragEnter,
+    /// `ondragleave`: A dragged item left the element.
+    DragLeave,
+    /// `ondragover`: A dragged item is over the element.
+    DragOver,
+    /// `ondragstart`: A drag operation started.
+    DragStart,
+    /// `ondrop`: A dragged item was dropped on the element.
+    Drop,
+    /// `ondurationchange`: The media's duration changed.
+    DurationChange,
+    /// `onemptied`: The media was reset to empty.
+    Emptied,
+    /// `onended`: Playback reached the end of the media.
+    Ended,
+    /// `onerror`: A resource failed to load.
+    Error,
+    /// `onfocus`: The element received focus.
+    Focus,
+    /// `onformdata`: A form's data set was constructed.
+    FormData,
+    /// `oninput`: A form control's value changed.
+    Input,
+    /// `oninvalid`: A form control failed validation.
+    Invalid,
+    /// `onkeydown`: A key was pressed.
+    KeyDown,
+    /// `onkeypress`: A character key was pressed. Deprecated; prefer `KeyDown`.
+    KeyPress,
+    /// `onkeyup`: A key was released.
+    KeyUp,
+    /// `onload`: The element, or on `<body>` the document, finished loading.
+    Load,
+    /// `onloadeddata`: The first frame of the media loaded.
+    LoadedData,
+    /// `onloadedmetadata`: The media's metadata loaded.
+    LoadedMetadata,
+    /// `onloadstart`: Loading of a media resource started.
+    LoadStart,
+    /// `onmousedown`: A mouse button was pressed on the element.
+    MouseDown,
+    /// `onmouseenter`: The pointer entered the element.
+    MouseEnter,
+    /// `onmouseleave`: The pointer left the element.
+    MouseLeave,
+    /// `onmousemove`: The pointer moved over the element.
+    MouseMove,
+    /// `onmouseout`: The pointer left the element or one of its children.
+    MouseOut,
+    /// `onmouseover`: The pointer entered the element or one of its children.
+    MouseOver,
+    /// `onmouseup`: A mouse button was released over the element.
+    MouseUp,
+    /// `onpaste`: The user pasted content.
+    Paste,
+    /// `onpause`: Playback was paused.
+    Pause,
+    /// `onplay`: Playback was requested.
+    Play,
+    /// `onplaying`: Playback started.
+    Playing,
+    /// `onpointercancel`: A pointer stopped producing events.
+    PointerCancel,
+    /// `onpointerdown`: A pointer became active.
+    PointerDown,
+    /// `onpointerenter`: A pointer entered the element.
+    PointerEnter,
+    /// `onpointerleave`: A pointer left the element.
+    PointerLeave,
+    /// `onpointermove`: A pointer moved.
+    PointerMove,
+    /// `onpointerout`: A pointer left the element or one of its children.
+    PointerOut,
+    /// `onpointerover`: A pointer entered the element or one of its children.
+    PointerOver,
+    /// `onpointerup`: A pointer stopped being active.
+    PointerUp,
+    /// `onprogress`: A resource is downloading.
+    Progress,
+    /// `onratechange`: The playback rate changed.
+    RateChange,
+    /// `onreset`: A form was reset.
+    Reset,
+    /// `onresize`: The element, or on `<body>` the window, was resized.
+    Resize,
+    /// `onscroll`: The element was scrolled.
+    Scroll,
+    /// `onscrollend`: Scrolling finished.
+    ScrollEnd,
+    /// `onsecuritypolicyviolation`: A Content Security Policy was violated.
+    SecurityPolicyViolation,
+    /// `onseeked`: A seek operation finished.
+    Seeked,
+    /// `onseeking`: A seek operation started.
+    Seeking,
+    /// `onselect`: Text in a form control was selected.
+    Select,
+    /// `onslotchange`: The nodes assigned to a `<slot>` changed.
+    SlotChange,
+    /// `onstalled`: Media data is unexpectedly not arriving.
+    Stalled,
+    /// `onsubmit`: A form was submitted.
+    Submit,
+    /// `onsuspend`: Media loading was suspended.
+    Suspend,
+    /// `ontimeupdate`: The current playback position changed.
+    TimeUpdate,
+    /// `ontoggle`: A `<details>`, popover, or dialog was opened or closed.
+    Toggle,
+    /// `onvolumechange`: The volume changed.
+    VolumeChange,
+    /// `onwaiting`: Playback stopped to wait for data.
+    Waiting,
+    /// `onwheel`: A wheel was rotated over the element.
+    Wheel,
+}
+
+impl EventHandler {
+    /// The attribute name, such as `"onabort"`.
+    #[must_use]
+    pub const fn attr_name(self) -> &'static str {
+        match self {
+            Self::Abort => "onabort",
+            Self::AuxClick => "onauxclick",
+            Self::BeforeInput => "onbeforeinput",
+            Self::BeforeMatch => "onbeforematch",
+            Self::BeforeToggle => "onbeforetoggle",
+            Self::Blur => "onblur",
+            Self::Cancel => "oncancel",
+            Self::CanPlay => "oncanplay",
+            Self::CanPlayThrough => "oncanplaythrough",
+            Self::Change => "onchange",
+            Self::Click => "onclick",
+            Self::Close => "onclose",
+            Self::ContextLost => "oncontextlost",
+            Self::ContextMenu => "oncontextmenu",
+            Self::ContextRestored => "oncontextrestored",
+            Self::Copy => "oncopy",
+            Self::CueChange => "oncuechange",
+            Self::Cut => "oncut",
+            Self::DblClick => "ondblclick",
+            Self::Drag => "ondrag",
+            Self::DragEnd => "ondragend",
+            Self::DragEnter => "ondragenter",
+            Self::DragLeave => "ondragleave",
+            Self::DragOver => "ondragover",
+            Self::DragStart => "ondragstart",
+            Self::Drop => "ondrop",
+            Self::DurationChange => "ondurationchange",
+            Self::Emptied => "onemptied",
+            Self::Ended => "onended",
+            Self::Error => "onerror",
+            Self::Focus => "onfocus",
+            Self::FormData => "onformdata",
+            Self::Input => "oninput",
+            Self::Invalid => "oninvalid",
+            Self::KeyDown => "onkeydown",
+            Self::KeyPress => "onkeypress",
+            Self::KeyUp => "onkeyup",
+            Self::Load => "onload",
+            Self::LoadedData => "onloadeddata",
+            Self::LoadedMetadata => "onloadedmetadata",
+            Self::LoadStart => "onloadstart",
+            Self::MouseDown => "onmousedown",
+            Self::MouseEnter => "onmouseenter",
+            Self::MouseLeave => "onmouseleave",
+            Self::MouseMove => "onmousemove",
+            Self::MouseOut => "onmouseout",
+            Self::MouseOver => "onmouseover",
+            Self::MouseUp => "onmouseup",
+            Self::Paste => "onpaste",
+            Self::Pause => "onpause",
+            Self::Play => "onplay",
+            Self::Playing => "onplaying",
+            Self::PointerCancel => "onpointercancel",
+            Self::PointerDown => "onpointerdown",
+            Self::PointerEnter => "onpointerenter",
+            Self::PointerLeave => "onpointerleave",
+            Self::PointerMove => "onpointermove",
+            Self::PointerOut => "onpointerout",
+            Self::PointerOver => "onpointerover",
+            Self::PointerUp => "onpointerup",
+            Self::Progress => "onprogress",
+            Self::RateChange => "onratechange",
+            Self::Reset => "onreset",
+            Self::Resize => "onresize",
+            Self::Scroll => "onscroll",
+            Self::ScrollEnd => "onscrollend",
+            Self::SecurityPolicyViolation => "onsecuritypolicyviolation",
+            Self::Seeked => "onseeked",
+            Self::Seeking => "onseeking",
+            Self::Select => "onselect",
+            Self::SlotChange => "onslotchange",
+            Self::Stalled => "onstalled",
+            Self::Submit => "onsubmit",
+            Self::Suspend => "onsuspend",
+            Self::TimeUpdate => "ontimeupdate",
+            Self::Toggle => "ontoggle",
+            Self::VolumeChange => "onvolumechange",
+            Self::Waiting => "onwaiting",
+            Self::Wheel => "onwheel",
+        }
+    }
+}
+
+/// Window event handler attributes, set on `<body>`.
+///
+/// # Purpose
+/// These events fire on the window, and `<body>` exposes them as content
+/// attributes: `<body onhashchange="...">` handles the window's
+/// `hashchange` event.
+///
+/// # Usage Context
+/// - Used with: `<body>` elements only
+/// - Element-level handlers such as `onload` are in [`EventHandler`]
+///
+/// # Example
+/// ```rust
+/// use ironhtml_attributes::WindowEventHandler;
+/// assert_eq!(WindowEventHandler::HashChange.attr_name(), "onhashchange");
+/// ```
+///
+/// ```html
+/// <body onpageshow="restore()" onbeforeunload="return warnUnsaved()">
+/// ```
+///
+/// # WHATWG Specification
+/// - [`WindowEventHandlers`](https://html.spec.whatwg.org/multipage/webappapis.html#windoweventhandlers)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowEventHandler {
+    /// `onafterprint`: The page finished printing.
+    AfterPrint,
+    /// `onbeforeprint`: The page is about to print.
+    BeforePrint,
+    /// `onbeforeunload`: The page is about to be unloaded.
+    BeforeUnload,
+    /// `onhashchange`: The URL fragment changed.
+    HashChange,
+    /// `onlanguagechange`: The user's preferred languages changed.
+    LanguageChange,
+    /// `onmessage`: The window received a message.
+    Message,
+    /// `onmessageerror`: The window received a message that could not be deserialized.
+    MessageError,
+    /// `onoffline`: The browser went offline.
+    Offline,
+    /// `ononline`: The browser came back online.
+    Online,
+    /// `onpagehide`: The page is being hidden, such as when navigating away.
+    PageHide,
+    /// `onpagereveal`: The page is about to be rendered for the first time or after being restored.
+    PageReveal,
+    /// `onpageshow`: The page was shown, including from the back/forward cache.
+    PageShow,
+    /// `onpageswap`: The page is about to be replaced by a navigation.
+    PageSwap,
+    /// `onpopstate`: The session history entry changed.
+    PopState,
+    /// `onrejectionhandled`: A previously unhandled promise rejection was handled.
+    RejectionHandled,
+    /// `onstorage`: Web storage was changed by another page.
+    Storage,
+    /// `onunhandledrejection`: A promise rejection was not handled.
+    UnhandledRejection,
+    /// `onunload`: The page is being unloaded. Deprecated; prefer `PageHide`.
+    Unload,
+}
+
+impl WindowEventHandler {
+    /// The attribute name, such as `"onafterprint"`.
+    #[must_use]
+    pub const fn attr_name(self) -> &'static str {
+        match self {
+            Self::AfterPrint => "onafterprint",
+            Self::BeforePrint => "onbeforeprint",
+            Self::BeforeUnload => "onbeforeunload",
+            Self::HashChange => "onhashchange",
+            Self::LanguageChange => "onlanguagechange",
+            Self::Message => "onmessage",
+            Self::MessageError => "onmessageerror",
+            Self::Offline => "onoffline",
+            Self::Online => "ononline",
+            Self::PageHide => "onpagehide",
+            Self::PageReveal => "onpagereveal",
+            Self::PageShow => "onpageshow",
+            Self::PageSwap => "onpageswap",
+            Self::PopState => "onpopstate",
+            Self::RejectionHandled => "onrejectionhandled",
+            Self::Storage => "onstorage",
+            Self::UnhandledRejection => "onunhandledrejection",
+            Self::Unload => "onunload",
+        }
+    }
+}
+
+/// The attribute name of every [`EventHandler`] and [`WindowEventHandler`],
+/// such as `"onclick"` and `"onhashchange"`, taken from their `attr_name`.
+///
+/// # Example
+/// ```rust
+/// use ironhtml_attributes::EVENT_HANDLER_NAMES;
+/// assert!(EVENT_HANDLER_NAMES.contains(&"onclick"));
+/// assert!(EVENT_HANDLER_NAMES.contains(&"onpopstate"));
+/// ```
+pub const EVENT_HANDLER_NAMES: &[&str] = &[
+    EventHandler::Abort.attr_name(),
+    EventHandler::AuxClick.attr_name(),
+    EventHandler::BeforeInput.attr_name(),
+    EventHandler::BeforeMatch.attr_name(),
+    EventHandler::BeforeToggle.attr_name(),
+    EventHandler::Blur.attr_name(),
+    EventHandler::Cancel.attr_name(),
+    EventHandler::CanPlay.attr_name(),
+    EventHandler::CanPlayThrough.attr_name(),
+    EventHandler::Change.attr_name(),
+    EventHandler::Click.attr_name(),
+    EventHandler::Close.attr_name(),
+    EventHandler::ContextLost.attr_name(),
+    EventHandler::ContextMenu.attr_name(),
+    EventHandler::ContextRestored.attr_name(),
+    EventHandler::Copy.attr_name(),
+    EventHandler::CueChange.attr_name(),
+    EventHandler::Cut.attr_name(),
+    EventHandler::DblClick.attr_name(),
+    EventHandler::Drag.attr_name(),
+    EventHandler::DragEnd.attr_name(),
+    EventHandler::DragEnter.attr_name(),
+    EventHandler::DragLeave.attr_name(),
+    EventHandler::DragOver.attr_name(),
+    EventHandler::DragStart.attr_name(),
+    EventHandler::Drop.attr_name(),
+    EventHandler::DurationChange.attr_name(),
+    EventHandler::Emptied.attr_name(),
+    EventHandler::Ended.attr_name(),
+    EventHandler::Error.attr_name(),
+    EventHandler::Focus.attr_name(),
+    EventHandler::FormData.attr_name(),
+    EventHandler::Input.attr_name(),
+    EventHandler::Invalid.attr_name(),
+    EventHandler::KeyDown.attr_name(),
+    EventHandler::KeyPress.attr_name(),
+    EventHandler::KeyUp.attr_name(),
+    EventHandler::Load.attr_name(),
+    EventHandler::LoadedData.attr_name(),
+    EventHandler::LoadedMetadata.attr_name(),
+    EventHandler::LoadStart.attr_name(),
+    EventHandler::MouseDown.attr_name(),
+    EventHandler::MouseEnter.attr_name(),
+    EventHandler::MouseLeave.attr_name(),
+    EventHandler::MouseMove.attr_name(),
+    EventHandler::MouseOut.attr_name(),
+    EventHandler::MouseOver.attr_name(),
+    EventHandler::MouseUp.attr_name(),
+    EventHandler::Paste.attr_name(),
+    EventHandler::Pause.attr_name(),
+    EventHandler::Play.attr_name(),
+    EventHandler::Playing.attr_name(),
+    EventHandler::PointerCancel.attr_name(),
+    EventHandler::PointerDown.attr_name(),
+    EventHandler::PointerEnter.attr_name(),
+    EventHandler::PointerLeave.attr_name(),
+    EventHandler::PointerMove.attr_name(),
+    EventHandler::PointerOut.attr_name(),
+    EventHandler::PointerOver.attr_name(),
+    EventHandler::PointerUp.attr_name(),
+    EventHandler::Progress.attr_name(),
+    EventHandler::RateChange.attr_name(),
+    EventHandler::Reset.attr_name(),
+    EventHandler::Resize.attr_name(),
+    EventHandler::Scroll.attr_name(),
+    EventHandler::ScrollEnd.attr_name(),
+    EventHandler::SecurityPolicyViolation.attr_name(),
+    EventHandler::Seeked.attr_name(),
+    EventHandler::Seeking.attr_name(),
+    EventHandler::Select.attr_name(),
+    EventHandler::SlotChange.attr_name(),
+    EventHandler::Stalled.attr_name(),
+    EventHandler::Submit.attr_name(),
+    EventHandler::Suspend.attr_name(),
+    EventHandler::TimeUpdate.attr_name(),
+    EventHandler::Toggle.attr_name(),
+    EventHandler::VolumeChange.attr_name(),
+    EventHandler::Waiting.attr_name(),
+    EventHandler::Wheel.attr_name(),
+    WindowEventHandler::AfterPrint.attr_name(),
+    WindowEventHandler::BeforePrint.attr_name(),
+    WindowEventHandler::BeforeUnload.attr_name(),
+    WindowEventHandler::HashChange.attr_name(),
+    WindowEventHandler::LanguageChange.attr_name(),
+    WindowEventHandler::Message.attr_name(),
+    WindowEventHandler::MessageError.attr_name(),
+    WindowEventHandler::Offline.attr_name(),
+    WindowEventHandler::Online.attr_name(),
+    WindowEventHandler::PageHide.attr_name(),
+    WindowEventHandler::PageReveal.attr_name(),
+    WindowEventHandler::PageShow.attr_name(),
+    WindowEventHandler::PageSwap.attr_name(),
+    WindowEventHandler::PopState.attr_name(),
+    WindowEventHandler::RejectionHandled.attr_name(),
+    WindowEventHandler::Storage.attr_name(),
+    WindowEventHandler::UnhandledRejection.attr_name(),
+    WindowEventHandler::Unload.attr_name(),
+];
+
 // =============================================================================
 // Global Attributes
 // =============================================================================
@@ -1632,6 +3157,12 @@ pub mod global {
     ///
     /// Assigns the element to a named slot in a shadow DOM template.
     pub const SLOT: &str = "slot";
+
+    /// The `nonce` attribute.
+    ///
+    /// Cryptographic nonce that a Content Security Policy can use to allow
+    /// this inline `<script>` or `<style>`.
+    pub const NONCE: &str = "nonce";
 }
 
 // =============================================================================
@@ -1699,6 +3230,11 @@ pub mod anchor {
     ///
     /// Controls how much referrer information is sent when following the link.
     pub const REFERRERPOLICY: &str = "referrerpolicy";
+
+    /// The `ping` attribute.
+    ///
+    /// Space-separated URLs notified with a POST request when the link is followed.
+    pub const PING: &str = "ping";
 }
 
 /// Attribute names for image (`<img>`) elements.
@@ -1911,6 +3447,11 @@ pub mod input {
     /// Boolean allowing multiple values (for file or email inputs).
     pub const MULTIPLE: &str = "multiple";
 
+    /// The `capture` attribute.
+    ///
+    /// Which camera a file input should capture from: "user" or "environment".
+    pub const CAPTURE: &str = "capture";
+
     /// The `list` attribute.
     ///
     /// ID of a `<datalist>` element providing autocomplete suggestions.
@@ -2357,6 +3898,7 @@ pub mod table {
 /// - `rowspan`: Number of rows the cell spans
 /// - `headers`: Space-separated list of header cell IDs
 /// - `scope`: Scope of header cell (row, col, rowgroup, colgroup)
+/// - `abbr`: Short label for a header cell
 ///
 /// # Example
 /// ```html
@@ -2400,6 +3942,12 @@ pub mod tablecell {
     /// For `<th>` elements: specifies which cells the header applies to
     /// ("row", "col", "rowgroup", "colgroup").
     pub const SCOPE: &str = "scope";
+
+    /// The `abbr` attribute.
+    ///
+    /// For `<th>` elements: a short alternative label used when referring
+    /// to the header from other cells, e.g. by screen readers.
+    pub const ABBR: &str = "abbr";
 }
 
 /// Attribute names for media (`<audio>`, `<video>`) elements.
@@ -2540,6 +4088,58 @@ pub mod source {
     pub const MEDIA: &str = "media";
 }
 
+/// Attribute names for image map area (`<area>`) elements.
+///
+/// # Purpose
+/// The `<area>` element defines a clickable region inside a `<map>`.
+///
+/// # Common Attributes
+/// - `shape`: Kind of region (rect, circle, poly, default)
+/// - `coords`: Comma-separated coordinates of the region
+/// - `href`: Link target of the region
+/// - `alt`: Text alternative, required when `href` is set
+///
+/// # Example
+/// ```html
+/// <map name="planets">
+///   <area shape="circle" coords="90,58,3" href="/mercury" alt="Mercury">
+/// </map>
+/// ```
+///
+/// # WHATWG Specification
+/// - [The `area` element](https://html.spec.whatwg.org/multipage/image-maps.html#the-area-element)
+pub mod area {
+    /// The `shape` attribute.
+    ///
+    /// Kind of region: "rect", "circle", "poly", or "default".
+    pub const SHAPE: &str = "shape";
+
+    /// The `coords` attribute.
+    ///
+    /// Comma-separated coordinates of the region, interpreted by `shape`.
+    pub const COORDS: &str = "coords";
+
+    /// The `href` attribute.
+    ///
+    /// URL the region links to.
+    pub const HREF: &str = "href";
+
+    /// The `alt` attribute.
+    ///
+    /// Text alternative for the region, required when `href` is set.
+    pub const ALT: &str = "alt";
+
+    /// The `ping` attribute.
+    ///
+    /// Space-separated URLs notified with a POST request when the region is followed.
+    pub const PING: &str = "ping";
+
+    /// The `rel` attribute.
+    ///
+    /// Relationship between the current document and the linked resource.
+    pub const REL: &str = "rel";
+}
+
 /// Attribute names for track (`<track>`) elements.
 ///
 /// # Purpose
@@ -2916,6 +4516,106 @@ mod tests {
         assert_eq!(Dir::Auto.to_attr_value(), "auto");
     }
 
+    #[test]
+    fn test_srcset() {
+        let set = SrcSet::new()
+            .candidate("a.jpg", Descriptor::Density(1.0))
+            .candidate("a@2x.jpg", Descriptor::Density(2.0))
+            .candidate("a@2.5x.jpg", Descriptor::Density(2.5));
+        assert_eq!(
+            set.to_attr_value(),
+            "a.jpg 1x, a@2x.jpg 2x, a@2.5x.jpg 2.5x"
+        );
+        assert_eq!(SrcSet::new().to_attr_value(), "");
+    }
+
+    #[test]
+    #[should_panic(expected = "srcset cannot mix width and density descriptors")]
+    fn test_srcset_rejects_mixed_descriptors() {
+        let _ = SrcSet::new()
+            .candidate("a.jpg", Descriptor::Width(640))
+            .candidate("b.jpg", Descriptor::Density(2.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid srcset URL")]
+    fn test_srcset_rejects_url_with_space() {
+        let _ = SrcSet::new().candidate("my photo.jpg", Descriptor::Width(640));
+    }
+
+    #[test]
+    #[should_panic(expected = "duplicate srcset descriptor 640w")]
+    fn test_srcset_rejects_duplicate_descriptor() {
+        let _ = SrcSet::new()
+            .candidate("a.jpg", Descriptor::Width(640))
+            .candidate("b.jpg", Descriptor::Width(640));
+    }
+
+    #[test]
+    fn test_srcset_try_candidate() {
+        let srcset = SrcSet::new()
+            .try_candidate("a.jpg", Descriptor::Width(640))
+            .unwrap();
+        assert_eq!(
+            srcset
+                .clone()
+                .try_candidate("my photo.jpg", Descriptor::Width(320)),
+            Err(SrcSetError::InvalidUrl("my photo.jpg".into()))
+        );
+        assert_eq!(
+            srcset.clone().try_candidate("b.jpg", Descriptor::Width(0)),
+            Err(SrcSetError::InvalidDescriptor(Descriptor::Width(0)))
+        );
+        assert_eq!(
+            srcset.try_candidate("b.jpg", Descriptor::Width(640)),
+            Err(SrcSetError::DuplicateDescriptor(Descriptor::Width(640)))
+        );
+    }
+
+    #[test]
+    fn test_inline_style() {
+        let style = InlineStyle::new()
+            .display("flex")
+            .prop("--gap", "4px")
+            .prop("-webkit-line-clamp", "2")
+            .display("grid");
+        assert_eq!(
+            style.to_attr_value(),
+            "display: grid; --gap: 4px; -webkit-line-clamp: 2"
+        );
+        assert!(InlineStyle::new().is_empty());
+    }
+
+    #[test]
+    fn test_inline_style_escapes_declaration_breakout() {
+        let style = InlineStyle::new()
+            .background(r"url(x) } body { color: red \")
+            .width("1px");
+        assert_eq!(
+            style.to_attr_value(),
+            r"background: url(x) \} body \{ color: red \\; width: 1px"
+        );
+    }
+
+    #[test]
+    fn test_inline_style_escapes_strings_and_comments() {
+        let style = InlineStyle::new()
+            .color("\"")
+            .prop("font-family", "'a'\nb")
+            .background("/* x")
+            .width("1px");
+        assert_eq!(
+            style.to_attr_value(),
+            r#"color: \"; font-family: \'a\'\a b; background: /\* x; width: 1px"#
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid CSS property name")]
+    fn test_inline_style_rejects_invalid_property() {
+        let _ = InlineStyle::new().prop("color:red;x", "y");
+    }
+
     #[test]
     fn test_target_values() {
         assert_eq!(Target::Self_.to_attr_value(), "_self");
@@ -2946,6 +4646,144 @@ mod tests {
         assert_eq!(Method::Dialog.to_attr_value(), "dialog");
     }
 
+    #[test]
+    fn test_rel_tokens() {
+        let rel = RelTokens::new()
+            .with(Rel::Noopener)
+            .with(Rel::Noreferrer)
+            .with(Rel::Nofollow);
+        assert_eq!(rel.to_attr_value(), "noopener noreferrer nofollow");
+
+        let link: RelTokens = [Rel::Stylesheet, Rel::Preload].into_iter().collect();
+        assert_eq!(link.to_attr_value(), "stylesheet preload");
+
+        assert_eq!(RelTokens::from(Rel::Icon).to_attr_value(), "icon");
+        assert_eq!(RelTokens::new().to_attr_value(), "");
+        assert_eq!(Rel::DnsPrefetch.to_attr_value(), "dns-prefetch");
+    }
+
+    #[test]
+    fn test_rel_tokens_dedup() {
+        let mut rel: RelTokens = [Rel::Noopener, Rel::Noreferrer, Rel::Noopener]
+            .into_iter()
+            .collect();
+        assert!(!rel.insert(Rel::Noreferrer));
+        assert!(rel.insert(Rel::External));
+        assert_eq!(
+            rel.as_slice(),
+            &[Rel::Noopener, Rel::Noreferrer, Rel::External]
+        );
+        assert_eq!(rel.to_attr_value(), "noopener noreferrer external");
+    }
+
+    #[test]
+    fn test_download_values() {
+        assert_eq!(Download::Default.to_attr_value(), "");
+        assert_eq!(
+            Download::Filename("a b.pdf".into()).to_attr_value(),
+            "a b.pdf"
+        );
+    }
+
+    #[test]
+    fn test_enum_values_are_borrowed() {
+        let values = [
+            Dir::Auto.to_attr_value(),
+            ContentEditable::True.to_attr_value(),
+            Target::Blank.to_attr_value(),
+            Rel::Noopener.to_attr_value(),
+            Loading::Lazy.to_attr_value(),
+            Decoding::Async.to_attr_value(),
+            CrossOrigin::Anonymous.to_attr_value(),
+            ReferrerPolicy::NoReferrer.to_attr_value(),
+            InputType::Email.to_attr_value(),
+            ButtonType::Submit.to_attr_value(),
+            Autocomplete::Email.to_attr_value(),
+            Method::Post.to_attr_value(),
+            Enctype::Multipart.to_attr_value(),
+            Wrap::Hard.to_attr_value(),
+            Scope::Col.to_attr_value(),
+            Preload::Auto.to_attr_value(),
+            TrackKind::Subtitles.to_attr_value(),
+            Sandbox::AllowScripts.to_attr_value(),
+            HttpEquiv::Refresh.to_attr_value(),
+            MetaName::Robots.to_attr_value(),
+            RelTokens::from(Rel::Icon).to_attr_value(),
+            Download::Default.to_attr_value(),
+        ];
+        for value in values {
+            assert!(matches!(value, Cow::Borrowed(_)), "{value} allocated");
+        }
+    }
+
+    #[test]
+    fn test_meta_values() {
+        assert_eq!(
+            HttpEquiv::ContentSecurityPolicy.to_attr_value(),
+            "content-security-policy"
+        );
+        assert_eq!(HttpEquiv::Refresh.to_attr_value(), "refresh");
+        assert_eq!(MetaName::Viewport.to_attr_value(), "viewport");
+        assert_eq!(MetaName::ThemeColor.to_attr_value(), "theme-color");
+    }
+
+    #[test]
+    fn test_aria_values() {
+        assert_eq!(AriaLive::Off.to_attr_value(), "off");
+        assert_eq!(AriaLive::Polite.to_attr_value(), "polite");
+        assert_eq!(AriaLive::Assertive.to_attr_value(), "assertive");
+        assert_eq!(Role::Status.to_attr_value(), "status");
+        assert_eq!(Role::AlertDialog.to_attr_value(), "alertdialog");
+    }
+
+    #[test]
+    fn test_script_type_values() {
+        assert_eq!(ScriptType::default(), ScriptType::JavaScript);
+        assert_eq!(ScriptType::Module.to_attr_value(), "module");
+        assert_eq!(ScriptType::ImportMap.to_attr_value(), "importmap");
+        assert_eq!(ScriptType::JsonLd.to_attr_value(), "application/ld+json");
+        assert_eq!(
+            ScriptType::SpeculationRules.to_attr_value(),
+            "speculationrules"
+        );
+    }
+
+    #[test]
+    fn test_shape_coords() {
+        let rect = Shape::Rect([0, 0, 100, 50]);
+        assert_eq!(rect.to_attr_value(), "rect");
+        assert_eq!(rect.coords().as_deref(), Some("0,0,100,50"));
+
+        let circle = Shape::Circle(90, 58, 3);
+        assert_eq!(circle.to_attr_value(), "circle");
+        assert_eq!(circle.coords().as_deref(), Some("90,58,3"));
+
+        let poly = Shape::Poly(&[(0, 0), (10, 0), (5, 10)]);
+        assert_eq!(poly.to_attr_value(), "poly");
+        assert_eq!(poly.coords().as_deref(), Some("0,0,10,0,5,10"));
+        assert_eq!(Shape::Poly(&[]).coords().as_deref(), Some(""));
+
+        assert_eq!(Shape::Default.to_attr_value(), "default");
+        assert_eq!(Shape::Default.coords(), None);
+    }
+
+    #[test]
+    fn test_capture_values() {
+        assert_eq!(Capture::User.to_attr_value(), "user");
+        assert_eq!(Capture::Environment.to_attr_value(), "environment");
+    }
+
+    #[test]
+    fn test_event_handler_names() {
+        assert_eq!(EventHandler::Click.attr_name(), "onclick");
+        assert_eq!(EventHandler::DblClick.attr_name(), "ondblclick");
+        assert_eq!(
+            EventHandler::SecurityPolicyViolation.attr_name(),
+            "onsecuritypolicyviolation"
+        );
+        assert_eq!(WindowEventHandler::PopState.attr_name(), "onpopstate");
+    }
+
     #[test]
     fn test_numeric_attribute_values() {
         assert_eq!(42u32.to_attr_value(), "42");
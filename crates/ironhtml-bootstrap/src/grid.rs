@@ -119,6 +119,10 @@ where
 ///
 /// Generates: `<div class="col-{size}">...</div>`
 ///
+/// # Panics
+///
+/// Panics if `size` is not between 1 and 12.
+///
 /// ## Example
 ///
 /// ```rust
@@ -132,6 +136,7 @@ pub fn col<F>(size: u8, f: F) -> Element<Div>
 where
     F: FnOnce(Element<Div>) -> Element<Div>,
 {
+    assert_col_size(size);
     let class = alloc::format!("col-{size}");
     f(Element::<Div>::new().class(&class))
 }
@@ -140,6 +145,10 @@ where
 ///
 /// Generates: `<div class="col-{breakpoint}-{size}">...</div>`
 ///
+/// # Panics
+///
+/// Panics if `size` is not between 1 and 12.
+///
 /// ## Example
 ///
 /// ```rust
@@ -153,10 +162,19 @@ pub fn col_bp<F>(bp: Breakpoint, size: u8, f: F) -> Element<Div>
 where
     F: FnOnce(Element<Div>) -> Element<Div>,
 {
+    assert_col_size(size);
     let class = alloc::format!("col-{}-{size}", bp.as_str());
     f(Element::<Div>::new().class(&class))
 }
 
+/// Panic unless `size` is one of the grid's 12 column widths.
+fn assert_col_size(size: u8) {
+    assert!(
+        (1..=12).contains(&size),
+        "column size must be between 1 and 12, got {size}"
+    );
+}
+
 extern crate alloc;
 
 #[cfg(test)]
@@ -180,4 +198,24 @@ mod tests {
         assert!(html.contains(r#"class="col-4"#));
         assert!(html.contains(r#"class="col-8"#));
     }
+
+    #[test]
+    fn test_col_sizes() {
+        assert!(col(12, |c| c).render().contains(r#"class="col-12"#));
+        assert!(col_bp(Breakpoint::Lg, 1, |c| c)
+            .render()
+            .contains(r#"class="col-lg-1"#));
+    }
+
+    #[test]
+    #[should_panic(expected = "column size must be between 1 and 12")]
+    fn test_col_rejects_zero() {
+        let _ = col(0, |c| c);
+    }
+
+    #[test]
+    #[should_panic(expected = "column size must be between 1 and 12")]
+    fn test_col_bp_rejects_thirteen() {
+        let _ = col_bp(Breakpoint::Md, 13, |c| c);
+    }
 }
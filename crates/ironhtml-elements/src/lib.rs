@@ -30,6 +30,13 @@
 //! // accepts_phrasing::<Div>(); // ERROR - div is not phrasing content
 //! ```
 //!
+//! ## Features
+//!
+//! - `experimental-elements`: elements from proposals that are not yet part
+//!   of the HTML standard, currently [`<portal>`](Portal) and
+//!   [`<fencedframe>`](Fencedframe). They may change or be removed in a
+//!   minor release if the proposals do.
+//!
 //! ## Specification References
 //!
 //! - [Content categories](https://html.spec.whatwg.org/multipage/dom.html#content-models)
@@ -158,6 +165,11 @@ extern crate alloc;
 pub trait MetadataContent {}
 
 /// Flow content: most elements used in the body of documents and applications.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not flow content, so it cannot be nested here",
+    label = "not allowed in this parent",
+    note = "the parent only accepts flow content, such as `<div>`, `<p>`, `<ul>`, text, and inline elements"
+)]
 pub trait FlowContent {}
 
 /// Sectioning content: elements that define the scope of headings and footers.
@@ -167,6 +179,11 @@ pub trait SectioningContent: FlowContent {}
 pub trait HeadingContent: FlowContent {}
 
 /// Phrasing content: the text of the document and elements that mark up that text.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not phrasing content, so it cannot be nested here",
+    label = "block-level element inside a text-level parent",
+    note = "the parent only accepts phrasing content: text and inline elements such as `<span>`, `<a>`, `<em>`, and `<img>`"
+)]
 pub trait PhrasingContent: FlowContent {}
 
 /// Embedded content: elements that import another resource into the document.
@@ -207,6 +224,11 @@ pub trait ScriptSupporting {}
 /// // valid_child::<P, Div>();   // ERROR - p cannot contain div (block in inline)
 /// // valid_child::<Ul, Div>();  // ERROR - ul can only contain li
 /// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot contain `{Child}`",
+    label = "this nesting is not allowed by the HTML content model",
+    note = "see the element's content model in the HTML spec for what it may contain"
+)]
 pub trait CanContain<Child> {}
 
 // =============================================================================
@@ -220,8 +242,43 @@ pub trait HtmlElement {
 
     /// Whether this is a void element (self-closing, no children allowed).
     const VOID: bool = false;
+
+    /// Whether whitespace in this element's content is significant, so a
+    /// pretty-printer must not indent or break lines inside it: `<pre>`,
+    /// `<textarea>`, and the raw text elements.
+    const PRESERVE_WHITESPACE: bool = Self::RAW_TEXT;
+
+    /// Whether this is a raw text element (`<script>`, `<style>`), whose
+    /// text is not parsed as HTML, so it must be written without escaping:
+    /// `a < b` in JavaScript stays `a < b`, not `a &lt; b`.
+    const RAW_TEXT: bool = false;
 }
 
+/// Marker for elements that may have children, i.e. every element whose
+/// [`HtmlElement::VOID`] is `false`.
+///
+/// This lifts `VOID` to the type level so APIs can refuse void elements
+/// as parents at compile time even where no [`CanContain`] bound applies.
+///
+/// ```rust
+/// use ironhtml_elements::{Div, NonVoidElement};
+///
+/// fn takes_children<E: NonVoidElement>() {}
+/// takes_children::<Div>();
+/// // takes_children::<Img>(); // ERROR - img is a void element
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is a void element and cannot have children",
+    label = "void element"
+)]
+pub trait NonVoidElement: HtmlElement {}
+
+/// Listed elements: form controls that can be associated with a `<form>`
+/// through the `form` attribute, even when not nested inside it.
+///
+/// <https://html.spec.whatwg.org/multipage/forms.html#category-listed>
+pub trait ListedElement: HtmlElement {}
+
 // =============================================================================
 // Text Node (special pseudo-element for content model)
 // =============================================================================
@@ -590,6 +647,7 @@ impl MetadataContent for Meta {}
 pub struct Style;
 impl HtmlElement for Style {
     const TAG: &'static str = "style";
+    const RAW_TEXT: bool = true;
 }
 impl MetadataContent for Style {}
 
@@ -1753,6 +1811,7 @@ impl FlowContent for Hr {}
 pub struct Pre;
 impl HtmlElement for Pre {
     const TAG: &'static str = "pre";
+    const PRESERVE_WHITESPACE: bool = true;
 }
 impl FlowContent for Pre {}
 impl PalpableContent for Pre {}
@@ -4884,6 +4943,139 @@ impl HtmlElement for Param {
     const VOID: bool = true;
 }
 
+// =============================================================================
+// Experimental Elements
+// Proposals not yet in the HTML standard; require the `experimental-elements`
+// feature.
+// =============================================================================
+
+/// The `<portal>` element - embeds another page that can be activated to
+/// navigate to it seamlessly.
+///
+/// Requires the `experimental-elements` feature.
+///
+/// # Purpose
+///
+/// The `<portal>` element shows a preview of another page, like an
+/// `<iframe>`, which the embedding page can later activate: the portal's
+/// page then becomes the top-level page, with no new load. It was designed
+/// for instant, animated transitions between pages.
+///
+/// # Content Categories
+///
+/// - Flow Content
+/// - Phrasing Content
+/// - Embedded Content
+/// - Interactive Content
+/// - Palpable Content
+///
+/// # Permitted Content Model
+///
+/// - Nothing
+///
+/// # Key Attributes
+///
+/// - `src`: URL of the page to preview
+/// - `referrerpolicy`: Referrer policy for the request
+///
+/// # Example
+///
+/// ```html
+/// <portal src="https://example.com/article" referrerpolicy="origin"></portal>
+/// ```
+///
+/// # Browser Support
+///
+/// Portals were only ever available behind a flag in Chromium, and the
+/// proposal is no longer being developed. Browsers without support ignore
+/// the element, so provide a regular link to the same page.
+///
+/// # Specification
+///
+/// - [Portals (WICG draft)](https://wicg.github.io/portals/#the-portal-element)
+#[cfg(feature = "experimental-elements")]
+pub struct Portal;
+#[cfg(feature = "experimental-elements")]
+impl HtmlElement for Portal {
+    const TAG: &'static str = "portal";
+}
+#[cfg(feature = "experimental-elements")]
+impl FlowContent for Portal {}
+#[cfg(feature = "experimental-elements")]
+impl PhrasingContent for Portal {}
+#[cfg(feature = "experimental-elements")]
+impl EmbeddedContent for Portal {}
+#[cfg(feature = "experimental-elements")]
+impl InteractiveContent for Portal {}
+#[cfg(feature = "experimental-elements")]
+impl PalpableContent for Portal {}
+
+/// The `<fencedframe>` element - embeds content, such as an ad, that cannot
+/// communicate with the embedding page.
+///
+/// Requires the `experimental-elements` feature.
+///
+/// # Purpose
+///
+/// The `<fencedframe>` element is like an `<iframe>`, but the embedded
+/// content and the page around it cannot share information: no
+/// `postMessage`, no access to each other's DOM, and no shared storage
+/// keys. It is part of the Privacy Sandbox, where it shows content chosen
+/// by APIs such as Protected Audience without revealing the choice to the
+/// embedder.
+///
+/// # Content Categories
+///
+/// - Flow Content
+/// - Phrasing Content
+/// - Embedded Content
+/// - Interactive Content
+/// - Palpable Content
+///
+/// # Permitted Content Model
+///
+/// - Nothing
+///
+/// # Key Attributes
+///
+/// - `allow`: Permissions policy for the embedded content
+/// - `width`: Width in CSS pixels
+/// - `height`: Height in CSS pixels
+///
+/// The content is set from script through the element's `config` property,
+/// not through a `src` attribute.
+///
+/// # Example
+///
+/// ```html
+/// <fencedframe width="300" height="250" allow="attribution-reporting"></fencedframe>
+/// ```
+///
+/// # Browser Support
+///
+/// Shipped in Chromium-based browsers only; elsewhere the element is
+/// ignored.
+///
+/// # Specification
+///
+/// - [Fenced Frame (WICG draft)](https://wicg.github.io/fenced-frame/#the-fencedframe-element)
+#[cfg(feature = "experimental-elements")]
+pub struct Fencedframe;
+#[cfg(feature = "experimental-elements")]
+impl HtmlElement for Fencedframe {
+    const TAG: &'static str = "fencedframe";
+}
+#[cfg(feature = "experimental-elements")]
+impl FlowContent for Fencedframe {}
+#[cfg(feature = "experimental-elements")]
+impl PhrasingContent for Fencedframe {}
+#[cfg(feature = "experimental-elements")]
+impl EmbeddedContent for Fencedframe {}
+#[cfg(feature = "experimental-elements")]
+impl InteractiveContent for Fencedframe {}
+#[cfg(feature = "experimental-elements")]
+impl PalpableContent for Fencedframe {}
+
 // =============================================================================
 // SVG and MathML
 // =============================================================================
@@ -5174,6 +5366,7 @@ impl PalpableContent for Math {}
 pub struct Script;
 impl HtmlElement for Script {
     const TAG: &'static str = "script";
+    const RAW_TEXT: bool = true;
 }
 impl MetadataContent for Script {}
 impl FlowContent for Script {}
@@ -7575,6 +7768,7 @@ impl HtmlElement for Option_ {
 pub struct Textarea;
 impl HtmlElement for Textarea {
     const TAG: &'static str = "textarea";
+    const PRESERVE_WHITESPACE: bool = true;
 }
 impl FlowContent for Textarea {}
 impl PhrasingContent for Textarea {}
@@ -8487,7 +8681,77 @@ impl FlowContent for Dialog {}
 // Web Components
 // =============================================================================
 
-// Note: Custom elements are handled separately
+/// An autonomous custom element (a web component), such as `<my-widget>`
+/// or `<ion-button>`.
+///
+/// Unlike the other elements, its tag is not fixed by the type: each
+/// element carries its own name, which must pass
+/// [`is_custom_element_name`]. `TAG` is empty, standing for "any custom
+/// name".
+///
+/// # Content Categories
+///
+/// - Flow content
+/// - Phrasing content
+/// - Palpable content
+///
+/// # Permitted Content Model
+///
+/// - Anything: the browser does not know the element's content model, so
+///   any element or text is allowed
+///
+/// # WHATWG Specification
+///
+/// - [4.13 Custom elements](https://html.spec.whatwg.org/multipage/custom-elements.html)
+pub struct Custom;
+impl HtmlElement for Custom {
+    const TAG: &'static str = "";
+}
+impl FlowContent for Custom {}
+impl PhrasingContent for Custom {}
+impl PalpableContent for Custom {}
+
+/// The hyphenated names the spec reserves, which cannot be custom element
+/// names.
+pub const RESERVED_CUSTOM_ELEMENT_NAMES: [&str; 8] = [
+    "annotation-xml",
+    "color-profile",
+    "font-face",
+    "font-face-src",
+    "font-face-uri",
+    "font-face-format",
+    "font-face-name",
+    "missing-glyph",
+];
+
+/// Whether `name` is a valid custom element name.
+///
+/// That is a lowercase ASCII letter, then lowercase letters, digits, `-`,
+/// `.`, `_`, or non-ASCII characters, with at least one `-`, and not one of
+/// the [reserved names](RESERVED_CUSTOM_ELEMENT_NAMES).
+///
+/// <https://html.spec.whatwg.org/multipage/custom-elements.html#valid-custom-element-name>
+///
+/// ```rust
+/// use ironhtml_elements::is_custom_element_name;
+///
+/// assert!(is_custom_element_name("my-widget"));
+/// assert!(!is_custom_element_name("widget"));
+/// assert!(!is_custom_element_name("My-Widget"));
+/// assert!(!is_custom_element_name("font-face"));
+/// ```
+#[must_use]
+pub fn is_custom_element_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name.contains('-')
+        && name.chars().all(|c| {
+            c.is_ascii_lowercase()
+                || c.is_ascii_digit()
+                || matches!(c, '-' | '.' | '_')
+                || (!c.is_ascii() && !c.is_whitespace())
+        })
+        && !RESERVED_CUSTOM_ELEMENT_NAMES.contains(&name)
+}
 
 // =============================================================================
 // Deprecated/Obsolete Elements (included for completeness)
@@ -8509,6 +8773,147 @@ impl HtmlElement for Ins {
 impl FlowContent for Ins {}
 impl PhrasingContent for Ins {}
 
+// =============================================================================
+// Element Index
+// =============================================================================
+
+/// The tag name of every standard element this crate defines, taken from
+/// each type's [`HtmlElement::TAG`].
+///
+/// [`Custom`] is not listed, as it has no fixed tag, and neither are the
+/// [experimental elements](EXPERIMENTAL_TAG_NAMES).
+///
+/// ```rust
+/// use ironhtml_elements::TAG_NAMES;
+///
+/// assert!(TAG_NAMES.contains(&"blockquote"));
+/// assert!(!TAG_NAMES.contains(&"blink"));
+/// ```
+pub const TAG_NAMES: &[&str] = &[
+    Html::TAG,
+    Head::TAG,
+    Title::TAG,
+    Base::TAG,
+    Link::TAG,
+    Meta::TAG,
+    Style::TAG,
+    Body::TAG,
+    Article::TAG,
+    Section::TAG,
+    Nav::TAG,
+    Aside::TAG,
+    H1::TAG,
+    H2::TAG,
+    H3::TAG,
+    H4::TAG,
+    H5::TAG,
+    H6::TAG,
+    Hgroup::TAG,
+    Header::TAG,
+    Footer::TAG,
+    Address::TAG,
+    Main::TAG,
+    Div::TAG,
+    P::TAG,
+    Hr::TAG,
+    Pre::TAG,
+    Blockquote::TAG,
+    Ol::TAG,
+    Ul::TAG,
+    Menu::TAG,
+    Li::TAG,
+    Dl::TAG,
+    Dt::TAG,
+    Dd::TAG,
+    Figure::TAG,
+    Figcaption::TAG,
+    Search::TAG,
+    A::TAG,
+    Em::TAG,
+    Strong::TAG,
+    Small::TAG,
+    S::TAG,
+    Cite::TAG,
+    Q::TAG,
+    Dfn::TAG,
+    Abbr::TAG,
+    Ruby::TAG,
+    Rt::TAG,
+    Rp::TAG,
+    Data::TAG,
+    Time::TAG,
+    Code::TAG,
+    Var::TAG,
+    Samp::TAG,
+    Kbd::TAG,
+    Sub::TAG,
+    Sup::TAG,
+    I::TAG,
+    B::TAG,
+    U::TAG,
+    Mark::TAG,
+    Bdi::TAG,
+    Bdo::TAG,
+    Span::TAG,
+    Br::TAG,
+    Wbr::TAG,
+    Img::TAG,
+    Picture::TAG,
+    Source::TAG,
+    Audio::TAG,
+    Video::TAG,
+    Track::TAG,
+    Map::TAG,
+    Area::TAG,
+    Iframe::TAG,
+    Embed::TAG,
+    Object::TAG,
+    Param::TAG,
+    Svg::TAG,
+    Math::TAG,
+    Script::TAG,
+    Noscript::TAG,
+    Template::TAG,
+    Slot::TAG,
+    Canvas::TAG,
+    Table::TAG,
+    Caption::TAG,
+    Colgroup::TAG,
+    Col::TAG,
+    Thead::TAG,
+    Tbody::TAG,
+    Tfoot::TAG,
+    Tr::TAG,
+    Th::TAG,
+    Td::TAG,
+    Form::TAG,
+    Label::TAG,
+    Input::TAG,
+    Button::TAG,
+    Select::TAG,
+    Datalist::TAG,
+    Optgroup::TAG,
+    Option_::TAG,
+    Textarea::TAG,
+    Output::TAG,
+    Progress::TAG,
+    Meter::TAG,
+    Fieldset::TAG,
+    Legend::TAG,
+    Details::TAG,
+    Summary::TAG,
+    Dialog::TAG,
+    Del::TAG,
+    Ins::TAG,
+];
+
+/// The tag names of the elements behind the `experimental-elements`
+/// feature.
+///
+/// Listed whether or not the feature is enabled, so tools that only see
+/// names, such as the `html!` macro, can recognize them either way.
+pub const EXPERIMENTAL_TAG_NAMES: &[&str] = &["portal", "fencedframe"];
+
 // =============================================================================
 // Content Model Implementations
 // https://html.spec.whatwg.org/multipage/dom.html#content-models
@@ -8588,6 +8993,13 @@ impl CanContain<Script> for Head {}
 impl CanContain<Noscript> for Head {}
 impl CanContain<Template> for Head {}
 
+// -----------------------------------------------------------------------------
+// Custom elements (can contain anything)
+// -----------------------------------------------------------------------------
+
+impl<T: HtmlElement> CanContain<T> for Custom {}
+impl CanContain<Text> for Custom {}
+
 // -----------------------------------------------------------------------------
 // Flow content containers (can contain any flow content)
 // -----------------------------------------------------------------------------
@@ -8631,7 +9043,7 @@ impl<T: FlowContent> CanContain<T> for Figure {}
 // Figcaption can contain flow content
 impl<T: FlowContent> CanContain<T> for Figcaption {}
 
-// Details can contain flow content
+// Details can contain one summary followed by flow content
 impl<T: FlowContent> CanContain<T> for Details {}
 impl CanContain<Summary> for Details {}
 
@@ -8670,8 +9082,55 @@ impl<T: PhrasingContent> CanContain<T> for Pre {}
 // Span can contain phrasing content
 impl<T: PhrasingContent> CanContain<T> for Span {}
 
-// A can contain phrasing content (transparent, but simplified here)
-impl<T: PhrasingContent> CanContain<T> for A {}
+// A can contain phrasing content (transparent, but simplified here), except
+// interactive content: no links, buttons, or form controls inside a link.
+// https://html.spec.whatwg.org/multipage/text-level-semantics.html#the-a-element
+impl CanContain<Em> for A {}
+impl CanContain<Strong> for A {}
+impl CanContain<Small> for A {}
+impl CanContain<S> for A {}
+impl CanContain<Cite> for A {}
+impl CanContain<Q> for A {}
+impl CanContain<Dfn> for A {}
+impl CanContain<Abbr> for A {}
+impl CanContain<Ruby> for A {}
+impl CanContain<Data> for A {}
+impl CanContain<Time> for A {}
+impl CanContain<Code> for A {}
+impl CanContain<Var> for A {}
+impl CanContain<Samp> for A {}
+impl CanContain<Kbd> for A {}
+impl CanContain<Sub> for A {}
+impl CanContain<Sup> for A {}
+impl CanContain<I> for A {}
+impl CanContain<B> for A {}
+impl CanContain<U> for A {}
+impl CanContain<Mark> for A {}
+impl CanContain<Bdi> for A {}
+impl CanContain<Bdo> for A {}
+impl CanContain<Span> for A {}
+impl CanContain<Br> for A {}
+impl CanContain<Custom> for A {}
+impl CanContain<Wbr> for A {}
+impl CanContain<Img> for A {}
+impl CanContain<Picture> for A {}
+impl CanContain<Audio> for A {}
+impl CanContain<Map> for A {}
+impl CanContain<Area> for A {}
+impl CanContain<Object> for A {}
+impl CanContain<Svg> for A {}
+impl CanContain<Math> for A {}
+impl CanContain<Script> for A {}
+impl CanContain<Noscript> for A {}
+impl CanContain<Template> for A {}
+impl CanContain<Slot> for A {}
+impl CanContain<Canvas> for A {}
+impl CanContain<Datalist> for A {}
+impl CanContain<Output> for A {}
+impl CanContain<Progress> for A {}
+impl CanContain<Meter> for A {}
+impl CanContain<Del> for A {}
+impl CanContain<Ins> for A {}
 
 // Em, Strong, etc. can contain phrasing content
 impl<T: PhrasingContent> CanContain<T> for Em {}
@@ -8713,8 +9172,15 @@ impl<T: PhrasingContent> CanContain<T> for Output {}
 // Legend can contain phrasing content
 impl<T: PhrasingContent> CanContain<T> for Legend {}
 
-// Summary can contain phrasing content
+// Summary can contain phrasing content, optionally intermixed with heading content
 impl<T: PhrasingContent> CanContain<T> for Summary {}
+impl CanContain<H1> for Summary {}
+impl CanContain<H2> for Summary {}
+impl CanContain<H3> for Summary {}
+impl CanContain<H4> for Summary {}
+impl CanContain<H5> for Summary {}
+impl CanContain<H6> for Summary {}
+impl CanContain<Hgroup> for Summary {}
 
 // Button can contain phrasing content
 impl<T: PhrasingContent> CanContain<T> for Button {}
@@ -8879,6 +9345,131 @@ impl<T: PhrasingContent> CanContain<T> for Slot {}
 // Iframe content is loaded externally, but can have fallback
 impl<T: FlowContent> CanContain<T> for Iframe {}
 
+// =============================================================================
+// Non-void elements
+// https://html.spec.whatwg.org/multipage/syntax.html#void-elements
+// =============================================================================
+
+impl NonVoidElement for Html {}
+impl NonVoidElement for Head {}
+impl NonVoidElement for Title {}
+impl NonVoidElement for Style {}
+impl NonVoidElement for Body {}
+impl NonVoidElement for Article {}
+impl NonVoidElement for Section {}
+impl NonVoidElement for Nav {}
+impl NonVoidElement for Aside {}
+impl NonVoidElement for H1 {}
+impl NonVoidElement for H2 {}
+impl NonVoidElement for H3 {}
+impl NonVoidElement for H4 {}
+impl NonVoidElement for H5 {}
+impl NonVoidElement for H6 {}
+impl NonVoidElement for Hgroup {}
+impl NonVoidElement for Header {}
+impl NonVoidElement for Footer {}
+impl NonVoidElement for Address {}
+impl NonVoidElement for Main {}
+impl NonVoidElement for Div {}
+impl NonVoidElement for P {}
+impl NonVoidElement for Pre {}
+impl NonVoidElement for Blockquote {}
+impl NonVoidElement for Ol {}
+impl NonVoidElement for Ul {}
+impl NonVoidElement for Menu {}
+impl NonVoidElement for Li {}
+impl NonVoidElement for Dl {}
+impl NonVoidElement for Dt {}
+impl NonVoidElement for Dd {}
+impl NonVoidElement for Figure {}
+impl NonVoidElement for Figcaption {}
+impl NonVoidElement for Search {}
+impl NonVoidElement for A {}
+impl NonVoidElement for Em {}
+impl NonVoidElement for Strong {}
+impl NonVoidElement for Small {}
+impl NonVoidElement for S {}
+impl NonVoidElement for Cite {}
+impl NonVoidElement for Q {}
+impl NonVoidElement for Dfn {}
+impl NonVoidElement for Abbr {}
+impl NonVoidElement for Ruby {}
+impl NonVoidElement for Rt {}
+impl NonVoidElement for Rp {}
+impl NonVoidElement for Data {}
+impl NonVoidElement for Time {}
+impl NonVoidElement for Code {}
+impl NonVoidElement for Var {}
+impl NonVoidElement for Samp {}
+impl NonVoidElement for Kbd {}
+impl NonVoidElement for Sub {}
+impl NonVoidElement for Sup {}
+impl NonVoidElement for I {}
+impl NonVoidElement for B {}
+impl NonVoidElement for U {}
+impl NonVoidElement for Mark {}
+impl NonVoidElement for Bdi {}
+impl NonVoidElement for Bdo {}
+impl NonVoidElement for Span {}
+impl NonVoidElement for Picture {}
+impl NonVoidElement for Audio {}
+impl NonVoidElement for Video {}
+impl NonVoidElement for Map {}
+impl NonVoidElement for Iframe {}
+#[cfg(feature = "experimental-elements")]
+impl NonVoidElement for Portal {}
+#[cfg(feature = "experimental-elements")]
+impl NonVoidElement for Fencedframe {}
+impl NonVoidElement for Object {}
+impl NonVoidElement for Svg {}
+impl NonVoidElement for Math {}
+impl NonVoidElement for Script {}
+impl NonVoidElement for Noscript {}
+impl NonVoidElement for Template {}
+impl NonVoidElement for Slot {}
+impl NonVoidElement for Canvas {}
+impl NonVoidElement for Table {}
+impl NonVoidElement for Caption {}
+impl NonVoidElement for Colgroup {}
+impl NonVoidElement for Thead {}
+impl NonVoidElement for Tbody {}
+impl NonVoidElement for Tfoot {}
+impl NonVoidElement for Tr {}
+impl NonVoidElement for Th {}
+impl NonVoidElement for Td {}
+impl NonVoidElement for Form {}
+impl NonVoidElement for Label {}
+impl NonVoidElement for Button {}
+impl NonVoidElement for Select {}
+impl NonVoidElement for Datalist {}
+impl NonVoidElement for Optgroup {}
+impl NonVoidElement for Option_ {}
+impl NonVoidElement for Textarea {}
+impl NonVoidElement for Output {}
+impl NonVoidElement for Progress {}
+impl NonVoidElement for Meter {}
+impl NonVoidElement for Fieldset {}
+impl NonVoidElement for Legend {}
+impl NonVoidElement for Details {}
+impl NonVoidElement for Summary {}
+impl NonVoidElement for Dialog {}
+impl NonVoidElement for Del {}
+impl NonVoidElement for Ins {}
+impl NonVoidElement for Custom {}
+
+// =============================================================================
+// Listed form-associated elements
+// https://html.spec.whatwg.org/multipage/forms.html#category-listed
+// =============================================================================
+
+impl ListedElement for Button {}
+impl ListedElement for Fieldset {}
+impl ListedElement for Input {}
+impl ListedElement for Object {}
+impl ListedElement for Output {}
+impl ListedElement for Select {}
+impl ListedElement for Textarea {}
+
 // =============================================================================
 // Tests
 // =============================================================================
@@ -8896,6 +9487,25 @@ mod tests {
         assert_eq!(Table::TAG, "table");
     }
 
+    #[test]
+    fn test_tag_names_are_unique() {
+        let mut names: alloc::vec::Vec<&str> = TAG_NAMES
+            .iter()
+            .chain(EXPERIMENTAL_TAG_NAMES)
+            .copied()
+            .collect();
+        let count = names.len();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), count);
+    }
+
+    #[test]
+    #[cfg(feature = "experimental-elements")]
+    fn test_experimental_tag_names() {
+        assert_eq!(EXPERIMENTAL_TAG_NAMES, [Portal::TAG, Fencedframe::TAG]);
+    }
+
     #[test]
     #[allow(clippy::assertions_on_constants)]
     fn test_void_elements() {
@@ -8911,6 +9521,17 @@ mod tests {
         assert!(Link::VOID);
     }
 
+    #[test]
+    #[allow(clippy::assertions_on_constants)]
+    fn test_whitespace_and_raw_text_elements() {
+        assert!(Script::RAW_TEXT && Script::PRESERVE_WHITESPACE);
+        assert!(Style::RAW_TEXT && Style::PRESERVE_WHITESPACE);
+        assert!(!Pre::RAW_TEXT && Pre::PRESERVE_WHITESPACE);
+        assert!(!Textarea::RAW_TEXT && Textarea::PRESERVE_WHITESPACE);
+        assert!(!Div::RAW_TEXT && !Div::PRESERVE_WHITESPACE);
+        assert!(!Title::RAW_TEXT && !Title::PRESERVE_WHITESPACE);
+    }
+
     #[test]
     fn test_content_categories() {
         // Test that elements implement correct traits
@@ -8935,6 +9556,14 @@ mod tests {
         is_embedded::<Img>();
         is_embedded::<Video>();
         is_embedded::<Iframe>();
+        #[cfg(feature = "experimental-elements")]
+        {
+            is_phrasing::<Portal>();
+            is_embedded::<Portal>();
+            is_phrasing::<Fencedframe>();
+            is_embedded::<Fencedframe>();
+            assert_eq!(Fencedframe::TAG, "fencedframe");
+        }
         is_interactive::<A>();
         is_interactive::<Button>();
         is_interactive::<Input>();
@@ -8998,6 +9627,15 @@ mod tests {
         valid::<Optgroup, Option_>();
         valid::<Fieldset, Legend>();
 
+        // Interactive elements
+        valid::<Details, Summary>();
+        valid::<Details, P>();
+        valid::<Summary, Text>();
+        valid::<Summary, Strong>();
+        valid::<Summary, H2>();
+        valid::<Summary, Hgroup>();
+        valid::<Dialog, Form>();
+
         // Media
         valid::<Picture, Source>();
         valid::<Picture, Img>();
@@ -9011,4 +9649,47 @@ mod tests {
         valid::<H1, Text>();
         valid::<Button, Text>();
     }
+
+    #[test]
+    fn test_non_void_elements() {
+        fn non_void<E: NonVoidElement>() {
+            assert!(!E::VOID, "{} is void", E::TAG);
+        }
+
+        non_void::<Html>();
+        non_void::<Div>();
+        non_void::<P>();
+        non_void::<Table>();
+        non_void::<Select>();
+        non_void::<Textarea>();
+        non_void::<Script>();
+        non_void::<Template>();
+        non_void::<Ins>();
+    }
+    #[test]
+    fn test_custom_elements() {
+        fn valid<P: CanContain<C>, C>() {}
+
+        valid::<Custom, Li>();
+        valid::<Custom, Text>();
+        valid::<Custom, Custom>();
+        valid::<P, Custom>();
+        valid::<A, Custom>();
+        valid::<Div, Custom>();
+
+        for name in ["my-widget", "x-a.b_c", "math-\u{3b1}"] {
+            assert!(is_custom_element_name(name), "{name}");
+        }
+        for name in [
+            "widget",
+            "My-widget",
+            "my-Widget",
+            "1-a",
+            "-a",
+            "my widget",
+            "font-face",
+        ] {
+            assert!(!is_custom_element_name(name), "{name}");
+        }
+    }
 }
@@ -9,12 +9,14 @@
 //!
 //! See [`ironhtml::html!`](https://docs.rs/ironhtml/latest/ironhtml/macro.html.html)
 //! for full documentation and tested examples covering elements, attributes,
-//! text content, Rust expressions, loops, and conditionals.
+//! text content, Rust expressions, computed children, fragments, loops,
+//! conditionals, and `match`.
 
+use ironhtml_attributes::EVENT_HANDLER_NAMES;
+use ironhtml_elements::{EXPERIMENTAL_TAG_NAMES, TAG_NAMES};
 use proc_macro::TokenStream;
-use proc_macro2::TokenStream as TokenStream2;
-use quote::{quote, ToTokens};
-use syn::parse::discouraged::Speculative;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{quote, quote_spanned, ToTokens};
 use syn::parse::{Parse, ParseStream};
 use syn::{braced, token, Expr, Ident, LitStr, Result, Token};
 
@@ -23,18 +25,78 @@ use syn::{braced, token, Expr, Ident, LitStr, Result, Token};
 /// See the [crate-level documentation](crate) for syntax and examples.
 #[proc_macro]
 pub fn html(input: TokenStream) -> TokenStream {
-    let node = syn::parse_macro_input!(input as Node);
-    let expanded = node.to_token_stream();
+    let node = syn::parse_macro_input!(input with parse_root);
+    // `#expr` children pick their kind by method lookup, which needs these
+    // traits in scope; see `ironhtml::typed::interpolate`.
+    let expanded = quote! {{
+        #[allow(unused_imports)]
+        use ::ironhtml::typed::interpolate::{AsComponent as _, AsElement as _, AsText as _};
+        #node
+    }};
     expanded.into()
 }
 
-/// A node in the HTML tree: element, text, expression, loop, or conditional.
+/// Parse the single root node, pointing at whatever follows it.
+///
+/// Leading `let` statements scope the root, which must then be one
+/// element or fragment.
+fn parse_root(input: ParseStream) -> Result<Node> {
+    if input.peek(Token![let]) {
+        let stmt = parse_let_stmt(input)?;
+        let span = input.span();
+        let root = if input.is_empty() {
+            None
+        } else {
+            Some(parse_root_after_let(input)?)
+        };
+        return match root {
+            Some(root) if input.is_empty() => Ok(Node::Let(LetNode {
+                stmt,
+                rest: vec![root],
+            })),
+            _ => Err(syn::Error::new(
+                if input.is_empty() { span } else { input.span() },
+                "expected a single root element after `let`",
+            )),
+        };
+    }
+    let node = input.parse()?;
+    if !input.is_empty() {
+        return Err(input.error("html! takes a single root node; wrap siblings in `frag { ... }`"));
+    }
+    Ok(node)
+}
+
+/// Parse the root that follows a top-level `let`: another `let`, or an
+/// element or fragment.
+fn parse_root_after_let(input: ParseStream) -> Result<Node> {
+    if input.peek(Token![let]) {
+        return parse_root(input);
+    }
+    let span = input.span();
+    match input.parse()? {
+        node @ (Node::Element(_) | Node::Fragment(_)) => Ok(node),
+        _ => Err(syn::Error::new(
+            span,
+            "expected a single root element after `let`",
+        )),
+    }
+}
+
+/// A node in the HTML tree: element, fragment, text, expression, raw HTML,
+/// computed child, `for` or `while` loop, conditional, match, or `let` binding.
 enum Node {
     Element(ElementNode),
+    Fragment(FragmentNode),
     Text(LitStr),
     Expr(Expr),
-    For(ForLoop),
+    Raw(Expr),
+    Block(syn::Block),
+    For(Box<ForLoop>),
     If(IfNode),
+    Match(MatchNode),
+    While(Box<WhileLoop>),
+    Let(LetNode),
 }
 
 impl Parse for Node {
@@ -44,14 +106,40 @@ impl Parse for Node {
         } else if input.peek(Token![#]) {
             input.parse::<Token![#]>()?;
             Ok(Self::Expr(input.parse()?))
+        } else if input.peek(Token![@]) {
+            input.parse::<Token![@]>()?;
+            let keyword: Ident = input.parse()?;
+            if keyword != "raw" {
+                return Err(syn::Error::new(keyword.span(), "expected `@raw`"));
+            }
+            Ok(Self::Raw(input.parse()?))
         } else if input.peek(Token![for]) {
             Ok(Self::For(input.parse()?))
         } else if input.peek(Token![if]) {
             Ok(Self::If(input.parse()?))
+        } else if input.peek(Token![match]) {
+            Ok(Self::Match(input.parse()?))
+        } else if input.peek(Token![while]) {
+            Ok(Self::While(input.parse()?))
+        } else if input.peek(Token![let]) {
+            Ok(Self::Let(input.parse()?))
+        } else if input.peek(token::Brace) {
+            // An element body always follows a tag ident, so a brace in node
+            // position is a Rust block computing a child element.
+            Ok(Self::Block(input.parse()?))
+        } else if input.peek(Ident) && input.fork().parse::<Ident>()? == "frag" {
+            Ok(Self::Fragment(input.parse()?))
         } else if input.peek(Ident) {
             Ok(Self::Element(input.parse()?))
+        } else if input.peek(Token![<]) {
+            Err(input.error("elements are written `tag { ... }`, not `<tag>`"))
+        } else if input.peek(syn::Lit) {
+            Err(input
+                .error("text must be a string literal; write `#value` to insert another value"))
         } else {
-            Err(input.error("expected element, text literal, or expression"))
+            Err(input.error(
+                "expected an element, a string literal, `#expr`, `@raw expr`, or a `{ ... }` block",
+            ))
         }
     }
 }
@@ -60,14 +148,29 @@ impl ToTokens for Node {
     fn to_tokens(&self, tokens: &mut TokenStream2) {
         match self {
             Self::Element(elem) => elem.to_tokens(tokens),
+            Self::Fragment(frag) => frag.to_tokens(tokens),
             Self::Text(lit) => {
                 tokens.extend(quote! { .text(#lit) });
             }
             Self::Expr(expr) => {
-                tokens.extend(quote! { .text(#expr) });
+                tokens.extend(quote! { .text_display(#expr) });
+            }
+            Self::Raw(expr) => {
+                tokens.extend(quote! { .raw_html(#expr) });
+            }
+            Self::Block(block) => {
+                tokens.extend(block_child(block));
             }
             Self::For(for_loop) => for_loop.to_tokens(tokens),
             Self::If(if_node) => if_node.to_tokens(tokens),
+            Self::Match(match_node) => match_node.to_tokens(tokens),
+            Self::While(while_loop) => while_loop.to_tokens(tokens),
+            Self::Let(let_node) => {
+                // At the top level the binding scopes the root element.
+                let stmt = &let_node.stmt;
+                let rest = &let_node.rest;
+                tokens.extend(quote! { { #stmt #(#rest)* } });
+            }
         }
     }
 }
@@ -75,29 +178,47 @@ impl ToTokens for Node {
 /// An HTML element with tag, attributes, and children.
 struct ElementNode {
     tag: Ident,
-    attrs: Vec<Attribute>,
+    /// The tag of a custom element, `element("my-widget")`.
+    custom: Option<LitStr>,
+    attrs: Vec<ElementAttr>,
     children: Vec<Node>,
 }
 
 impl Parse for ElementNode {
     fn parse(input: ParseStream) -> Result<Self> {
         let tag: Ident = input.parse()?;
+        let custom = if tag == "element" && input.peek(token::Paren) {
+            let content;
+            syn::parenthesized!(content in input);
+            let name: LitStr = content.parse()?;
+            validate_custom_tag(&name)?;
+            Some(name)
+        } else {
+            check_known_tag(&tag)?;
+            None
+        };
 
-        // Parse attributes (method chain style: .class("x").id("y"))
+        // Parse attributes (method chain style: .class("x").id("y")) and
+        // spreads (..extra), in order
         let mut attrs = Vec::new();
-        while input.peek(Token![.]) {
-            input.parse::<Token![.]>()?;
-            attrs.push(input.parse()?);
+        loop {
+            if input.peek(Token![..]) {
+                input.parse::<Token![..]>()?;
+                attrs.push(ElementAttr::Spread(parse_spread(input)?));
+            } else if input.peek(Token![.]) {
+                input.parse::<Token![.]>()?;
+                attrs.push(ElementAttr::Attr(input.parse()?));
+            } else {
+                break;
+            }
         }
 
         // Parse children (inside braces)
         let children = if input.peek(token::Brace) {
             let content;
             braced!(content in input);
-            let mut children = Vec::new();
-            while !content.is_empty() {
-                children.push(content.parse()?);
-            }
+            let children = parse_nodes(&content)?;
+            check_no_trailing_attr(input, &tag)?;
             children
         } else {
             Vec::new()
@@ -105,83 +226,163 @@ impl Parse for ElementNode {
 
         Ok(Self {
             tag,
+            custom,
             attrs,
             children,
         })
     }
 }
 
+impl ElementNode {
+    /// The expression creating the element, before attributes and children.
+    fn constructor(&self) -> TokenStream2 {
+        if let Some(name) = &self.custom {
+            return quote! { ::ironhtml::typed::CustomElement::named(#name) };
+        }
+        let tag_ident = self.type_ident();
+        quote! { ::ironhtml::typed::Element::<::ironhtml_elements::#tag_ident>::new() }
+    }
+
+    /// The element type in `ironhtml_elements`, e.g. `Div` for `div`.
+    fn type_ident(&self) -> Ident {
+        Ident::new(&to_pascal_case(&self.tag.to_string()), self.tag.span())
+    }
+
+    /// The call adding this element as a child of the element being built.
+    ///
+    /// Standard elements go through `.child::<Tag, _>`, which checks the
+    /// content model; custom elements are appended whole.
+    fn child_call(&self) -> TokenStream2 {
+        let attrs = &self.attrs;
+        let nested = generate_children(&self.children);
+        if self.custom.is_some() {
+            let constructor = self.constructor();
+            return quote! { .append_child(#constructor #(#attrs)* #nested) };
+        }
+        let b = builder_ident();
+        let tag_ident = self.type_ident();
+        quote! {
+            .child::<::ironhtml_elements::#tag_ident, _>(|#b| #b #(#attrs)* #nested)
+        }
+    }
+}
+
 impl ToTokens for ElementNode {
     fn to_tokens(&self, tokens: &mut TokenStream2) {
-        let tag = &self.tag;
-        let tag_pascal = to_pascal_case(&tag.to_string());
-        let tag_ident = Ident::new(&tag_pascal, tag.span());
+        let constructor = self.constructor();
+        let attrs = &self.attrs;
+        let children = generate_children(&self.children);
+        tokens.extend(quote! { #constructor #(#attrs)* #children });
+    }
+}
 
-        // Generate attribute calls
-        let attr_calls: Vec<_> = self
-            .attrs
-            .iter()
-            .map(quote::ToTokens::to_token_stream)
-            .collect();
+/// Parse nodes up to the end of `input`.
+///
+/// Each node may be followed by one `,` or `;`, which is ignored, so input
+/// that was generated or copied with separators between siblings parses
+/// the same as without them.
+fn parse_nodes(input: ParseStream) -> Result<Vec<Node>> {
+    let mut nodes = Vec::new();
+    while !input.is_empty() {
+        nodes.push(input.parse()?);
+        if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+        } else if input.peek(Token![;]) {
+            input.parse::<Token![;]>()?;
+        }
+    }
+    Ok(nodes)
+}
 
-        if self.children.is_empty() {
-            // No children - just create element with attributes
-            tokens.extend(quote! {
-                ::ironhtml::typed::Element::<::ironhtml_elements::#tag_ident>::new()
-                    #(#attr_calls)*
-            });
+/// Reject an attribute written after an element's child block, as in
+/// `div { } .class("x")`, pointing at the attribute.
+fn check_no_trailing_attr(input: ParseStream, tag: &Ident) -> Result<()> {
+    if !input.peek(Token![.]) {
+        return Ok(());
+    }
+    let fork = input.fork();
+    if fork.peek(Token![..]) {
+        let dots: Token![..] = fork.parse()?;
+        return Err(syn::Error::new_spanned(
+            dots,
+            format!("attributes must come before the child block; move the spread after `{tag}`"),
+        ));
+    }
+    let dot: Token![.] = fork.parse()?;
+    let Ok(name) = fork.parse::<Ident>() else {
+        return Err(syn::Error::new_spanned(
+            dot,
+            "attributes must come before the child block",
+        ));
+    };
+    Err(syn::Error::new_spanned(
+        quote! { #dot #name },
+        format!("attributes must come before the child block; move `.{name}(...)` after `{tag}`"),
+    ))
+}
+
+/// Sibling nodes without a wrapper: `frag { ... }`, or `frag<ul> { ... }`
+/// to check the children against a parent other than `<div>`.
+///
+/// At the top level this builds a `Fragment`; nested in an element, its
+/// children are added to that element directly.
+struct FragmentNode {
+    parent: Option<Ident>,
+    children: Vec<Node>,
+}
+
+impl Parse for FragmentNode {
+    fn parse(input: ParseStream) -> Result<Self> {
+        input.parse::<Ident>()?;
+        let parent = if input.peek(Token![<]) {
+            input.parse::<Token![<]>()?;
+            let parent: Ident = input.parse()?;
+            check_known_tag(&parent)?;
+            input.parse::<Token![>]>()?;
+            Some(parent)
         } else {
-            // Has children - need to generate child calls
-            let mut child_tokens = TokenStream2::new();
-
-            for child in &self.children {
-                match child {
-                    Node::Element(elem) => {
-                        let child_tag = &elem.tag;
-                        let child_pascal = to_pascal_case(&child_tag.to_string());
-                        let child_ident = Ident::new(&child_pascal, child_tag.span());
-
-                        let child_attrs: Vec<_> = elem
-                            .attrs
-                            .iter()
-                            .map(quote::ToTokens::to_token_stream)
-                            .collect();
-
-                        if elem.children.is_empty() {
-                            child_tokens.extend(quote! {
-                                .child::<::ironhtml_elements::#child_ident, _>(|e| e #(#child_attrs)*)
-                            });
-                        } else {
-                            let nested = generate_children(&elem.children);
-                            child_tokens.extend(quote! {
-                                .child::<::ironhtml_elements::#child_ident, _>(|e| e #(#child_attrs)* #nested)
-                            });
-                        }
-                    }
-                    Node::Text(lit) => {
-                        child_tokens.extend(quote! { .text(#lit) });
-                    }
-                    Node::Expr(expr) => {
-                        child_tokens.extend(quote! { .text(#expr) });
-                    }
-                    Node::For(for_loop) => {
-                        for_loop.to_tokens(&mut child_tokens);
-                    }
-                    Node::If(if_node) => {
-                        if_node.to_tokens(&mut child_tokens);
-                    }
-                }
-            }
+            None
+        };
+        let content;
+        braced!(content in input);
+        let children = parse_nodes(&content)?;
+        Ok(Self { parent, children })
+    }
+}
 
-            tokens.extend(quote! {
-                ::ironhtml::typed::Element::<::ironhtml_elements::#tag_ident>::new()
-                    #(#attr_calls)*
-                    #child_tokens
-            });
-        }
+impl ToTokens for FragmentNode {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let parent = self.parent.as_ref().map_or_else(
+            || Ident::new("Div", Span::call_site()),
+            |tag| Ident::new(&to_pascal_case(&tag.to_string()), tag.span()),
+        );
+        let children = generate_children(&self.children);
+        tokens.extend(quote! {
+            ::ironhtml::typed::Element::<::ironhtml_elements::#parent>::new()
+                #children
+                .into_fragment()
+        });
     }
 }
 
+/// The closure parameter that holds the element being built.
+///
+/// It has a mixed-site span, so user code in `#expr` and `{ ... }` cannot see
+/// it, and a user variable of the same name is never shadowed by it.
+fn builder_ident() -> Ident {
+    Ident::new("__ironhtml_el", Span::mixed_site())
+}
+
+/// Generate the call appending a computed `{ ... }` child.
+///
+/// The braces are re-created with a macro span so the user's code isn't
+/// flagged by the `unused_braces` lint for the block they wrote.
+fn block_child(block: &syn::Block) -> TokenStream2 {
+    let stmts = &block.stmts;
+    let body = quote_spanned! { Span::mixed_site()=> { #(#stmts)* } };
+    quote! { .append_child(#body) }
+}
+
 /// Generate token stream for a list of child nodes.
 fn generate_children(children: &[Node]) -> TokenStream2 {
     let mut tokens = TokenStream2::new();
@@ -189,32 +390,24 @@ fn generate_children(children: &[Node]) -> TokenStream2 {
     for child in children {
         match child {
             Node::Element(elem) => {
-                let child_tag = &elem.tag;
-                let child_pascal = to_pascal_case(&child_tag.to_string());
-                let child_ident = Ident::new(&child_pascal, child_tag.span());
-
-                let child_attrs: Vec<_> = elem
-                    .attrs
-                    .iter()
-                    .map(quote::ToTokens::to_token_stream)
-                    .collect();
-
-                if elem.children.is_empty() {
-                    tokens.extend(quote! {
-                        .child::<::ironhtml_elements::#child_ident, _>(|e| e #(#child_attrs)*)
-                    });
-                } else {
-                    let nested = generate_children(&elem.children);
-                    tokens.extend(quote! {
-                        .child::<::ironhtml_elements::#child_ident, _>(|e| e #(#child_attrs)* #nested)
-                    });
-                }
+                tokens.extend(elem.child_call());
+            }
+            Node::Fragment(frag) => {
+                tokens.extend(generate_children(&frag.children));
             }
             Node::Text(lit) => {
                 tokens.extend(quote! { .text(#lit) });
             }
             Node::Expr(expr) => {
-                tokens.extend(quote! { .text(#expr) });
+                tokens.extend(quote! {
+                    .append_child((&&::ironhtml::typed::interpolate::Interpolate(&(#expr))).child())
+                });
+            }
+            Node::Raw(expr) => {
+                tokens.extend(quote! { .raw_html(#expr) });
+            }
+            Node::Block(block) => {
+                tokens.extend(block_child(block));
             }
             Node::For(for_loop) => {
                 for_loop.to_tokens(&mut tokens);
@@ -222,15 +415,56 @@ fn generate_children(children: &[Node]) -> TokenStream2 {
             Node::If(if_node) => {
                 if_node.to_tokens(&mut tokens);
             }
+            Node::Match(match_node) => {
+                match_node.to_tokens(&mut tokens);
+            }
+            Node::While(while_loop) => {
+                while_loop.to_tokens(&mut tokens);
+            }
+            Node::Let(let_node) => {
+                let_node.to_tokens(&mut tokens);
+            }
         }
     }
 
     tokens
 }
 
-/// An attribute on an element: name(value) or name (boolean).
+/// Parse the operand of a spread: a path, or any expression in
+/// parentheses. Anything longer would swallow the attributes after it, as
+/// `..extra.class("x")` is a method call on `extra`.
+fn parse_spread(input: ParseStream) -> Result<Expr> {
+    if input.peek(token::Paren) {
+        let content;
+        syn::parenthesized!(content in input);
+        content.parse()
+    } else {
+        Ok(Expr::Path(input.parse()?))
+    }
+}
+
+/// An entry in an element's attribute list: an attribute, or ..name or
+/// ..(expr) spreading an iterator of name-value pairs.
+enum ElementAttr {
+    Attr(Attribute),
+    Spread(Expr),
+}
+
+impl ToTokens for ElementAttr {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        match self {
+            Self::Attr(attr) => attr.to_tokens(tokens),
+            Self::Spread(expr) => tokens.extend(quote! { .attrs(#expr) }),
+        }
+    }
+}
+
+/// An attribute on an element: name(value), a boolean name alone or with
+/// empty parens, attr("name", value) for names that are not Rust
+/// identifiers, or on(event, handler) for an inline event handler.
 struct Attribute {
     name: Ident,
+    literal_name: Option<LitStr>,
     value: Option<AttrValue>,
 }
 
@@ -239,27 +473,180 @@ enum AttrValue {
     Expr(Expr),
 }
 
+impl ToTokens for AttrValue {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        match self {
+            Self::Lit(lit) => lit.to_tokens(tokens),
+            Self::Expr(expr) => expr.to_tokens(tokens),
+        }
+    }
+}
+
+impl AttrValue {
+    fn parse_value(content: ParseStream) -> Result<Self> {
+        if content.peek(Token![#]) {
+            content.parse::<Token![#]>()?;
+            Ok(Self::Expr(content.parse()?))
+        } else if content.peek(LitStr) {
+            Ok(Self::Lit(content.parse()?))
+        } else {
+            Ok(Self::Expr(content.parse()?))
+        }
+    }
+}
+
+/// Check that a string-literal attribute name can be emitted verbatim.
+///
+/// Follows the HTML syntax for attribute names: at least one character and
+/// no whitespace, controls, quotes, `>`, `/`, or `=`.
+fn validate_attr_name(lit: &LitStr) -> Result<()> {
+    let name = lit.value();
+    if name.is_empty() {
+        return Err(syn::Error::new(
+            lit.span(),
+            "attribute name must not be empty",
+        ));
+    }
+    if let Some(c) = name
+        .chars()
+        .find(|&c| c.is_whitespace() || c.is_control() || matches!(c, '"' | '\'' | '>' | '/' | '='))
+    {
+        return Err(syn::Error::new(
+            lit.span(),
+            format!("invalid character {c:?} in attribute name {name:?}"),
+        ));
+    }
+    Ok(())
+}
+
+/// Check a literal `id`: one non-empty token, since an id with whitespace
+/// cannot be matched by `#id` selectors or fragment links.
+fn validate_id(lit: &LitStr) -> Result<()> {
+    let id = lit.value();
+    if id.is_empty() {
+        return Err(syn::Error::new(lit.span(), "id must not be empty"));
+    }
+    if id.contains(|c: char| c.is_ascii_whitespace()) {
+        return Err(syn::Error::new(
+            lit.span(),
+            format!("id {id:?} must not contain whitespace"),
+        ));
+    }
+    Ok(())
+}
+
+/// Check a literal `class` against the rules for a set of space-separated
+/// tokens: at least one token, no token repeated, and no whitespace other
+/// than ASCII whitespace, which browsers do not treat as a separator, so
+/// `"a\u{a0}b"` would be one class that `.a` and `.b` both miss.
+fn validate_class(lit: &LitStr) -> Result<()> {
+    let class = lit.value();
+    let mut tokens: Vec<&str> = Vec::new();
+    for token in class.split_ascii_whitespace() {
+        if token.contains(char::is_whitespace) {
+            return Err(syn::Error::new(
+                lit.span(),
+                format!(
+                    "class {token:?} contains non-ASCII whitespace, which does not separate classes"
+                ),
+            ));
+        }
+        if tokens.contains(&token) {
+            return Err(syn::Error::new(
+                lit.span(),
+                format!("duplicate class {token:?}"),
+            ));
+        }
+        tokens.push(token);
+    }
+    if tokens.is_empty() {
+        return Err(syn::Error::new(lit.span(), "class must not be empty"));
+    }
+    Ok(())
+}
+
+/// Check the tag of `element("...")` against the rules for custom element
+/// names, shared with the runtime check through
+/// [`is_custom_element_name`](ironhtml_elements::is_custom_element_name).
+fn validate_custom_tag(lit: &LitStr) -> Result<()> {
+    let name = lit.value();
+    if ironhtml_elements::is_custom_element_name(&name) {
+        return Ok(());
+    }
+    let message = if ironhtml_elements::RESERVED_CUSTOM_ELEMENT_NAMES.contains(&name.as_str()) {
+        format!("{name:?} is reserved and cannot be a custom element name")
+    } else {
+        format!(
+            "invalid custom element name {name:?}; it must start with a lowercase \
+             letter, contain a `-`, and have no uppercase letters or whitespace"
+        )
+    };
+    Err(syn::Error::new(lit.span(), message))
+}
+
 impl Parse for Attribute {
     fn parse(input: ParseStream) -> Result<Self> {
         let name: Ident = input.parse()?;
 
-        let value = if input.peek(token::Paren) {
-            let content;
-            syn::parenthesized!(content in input);
+        if !input.peek(token::Paren) {
+            return Ok(Self {
+                name,
+                literal_name: None,
+                value: None,
+            });
+        }
 
-            if content.peek(Token![#]) {
-                content.parse::<Token![#]>()?;
-                Some(AttrValue::Expr(content.parse()?))
-            } else if content.peek(LitStr) {
-                Some(AttrValue::Lit(content.parse()?))
-            } else {
-                Some(AttrValue::Expr(content.parse()?))
-            }
-        } else {
-            None
-        };
+        let content;
+        syn::parenthesized!(content in input);
 
-        Ok(Self { name, value })
+        // required(): a boolean attribute, like a bare `required`
+        if content.is_empty() {
+            return Ok(Self {
+                name,
+                literal_name: None,
+                value: None,
+            });
+        }
+
+        // attr("@click", value): a name that is not a valid Rust identifier
+        if name == "attr" && content.peek(LitStr) && content.peek2(Token![,]) {
+            let literal: LitStr = content.parse()?;
+            validate_attr_name(&literal)?;
+            content.parse::<Token![,]>()?;
+            let value = AttrValue::parse_value(&content)?;
+            return Ok(Self {
+                name,
+                literal_name: Some(literal),
+                value: Some(value),
+            });
+        }
+
+        // on(click, "js"): the `onclick` handler, with the event checked
+        if name == "on" && content.peek(Ident) && content.peek2(Token![,]) {
+            let event: Ident = content.parse()?;
+            check_known_event(&event)?;
+            content.parse::<Token![,]>()?;
+            let value = AttrValue::parse_value(&content)?;
+            return Ok(Self {
+                name,
+                literal_name: Some(LitStr::new(&format!("on{event}"), event.span())),
+                value: Some(value),
+            });
+        }
+
+        let value = AttrValue::parse_value(&content)?;
+        if let AttrValue::Lit(lit) = &value {
+            if name == "id" {
+                validate_id(lit)?;
+            } else if name == "class" {
+                validate_class(lit)?;
+            }
+        }
+        Ok(Self {
+            name,
+            literal_name: None,
+            value: Some(value),
+        })
     }
 }
 
@@ -268,9 +655,16 @@ impl ToTokens for Attribute {
         let name = &self.name;
         let name_str = name.to_string();
 
+        // The parser always pairs a literal name with a value.
+        if let Some(literal) = &self.literal_name {
+            let value = &self.value;
+            tokens.extend(quote! { .attr(#literal, #value) });
+            return;
+        }
+
         // Handle special attribute names
         let method_name = match name_str.as_str() {
-            "class" | "id" => name.clone(),
+            "class" | "id" | "key" => name.clone(),
             _ => Ident::new("attr", name.span()),
         };
 
@@ -280,7 +674,7 @@ impl ToTokens for Attribute {
 
         match &self.value {
             Some(AttrValue::Lit(lit)) => {
-                if name_str == "class" || name_str == "id" {
+                if matches!(name_str.as_str(), "class" | "id" | "key") {
                     tokens.extend(quote! { .#method_name(#lit) });
                 } else {
                     let attr_name = convert_attr_name(&name_str);
@@ -288,7 +682,7 @@ impl ToTokens for Attribute {
                 }
             }
             Some(AttrValue::Expr(expr)) => {
-                if name_str == "class" || name_str == "id" {
+                if matches!(name_str.as_str(), "class" | "id" | "key") {
                     tokens.extend(quote! { .#method_name(#expr) });
                 } else {
                     let attr_name = convert_attr_name(&name_str);
@@ -298,17 +692,21 @@ impl ToTokens for Attribute {
             None => {
                 // Boolean attribute
                 let attr_name = convert_attr_name(&name_str);
-                tokens.extend(quote! { .bool_attr(#attr_name) });
+                tokens.extend(quote! { .flag(#attr_name) });
             }
         }
     }
 }
 
-/// A for loop: for item in #expr { children }
+/// A for loop: for item in #expr { children }, optionally keyed with
+/// for item in #expr use key = item.id { children }, and optionally
+/// followed by else { children } for an empty collection
 struct ForLoop {
     pat: syn::Pat,
     expr: Expr,
+    key: Option<Box<Expr>>,
     children: Vec<Node>,
+    else_children: Option<Vec<Node>>,
 }
 
 impl Parse for ForLoop {
@@ -318,16 +716,29 @@ impl Parse for ForLoop {
         input.parse::<Token![in]>()?;
         input.parse::<Token![#]>()?;
 
-        // Parse expression but stop before brace (use ExprPath or similar)
-        // We need to be careful not to consume the following brace
-        let expr = parse_expr_before_brace(input)?;
+        // Like Rust's own `for`, a brace ends the expression rather than
+        // starting a struct literal, so `#&items {` and `#rows.iter() {` work.
+        let expr = Expr::parse_without_eager_brace(input)?;
+
+        // Optional hydration key: `use key = expr`
+        let key = if input.peek(Token![use]) {
+            input.parse::<Token![use]>()?;
+            let name: Ident = input.parse()?;
+            if name != "key" {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "expected `key = ...` after `use`",
+                ));
+            }
+            input.parse::<Token![=]>()?;
+            Some(Box::new(Expr::parse_without_eager_brace(input)?))
+        } else {
+            None
+        };
 
         let content;
         braced!(content in input);
-        let mut children = Vec::new();
-        while !content.is_empty() {
-            children.push(content.parse()?);
-        }
+        let children = parse_nodes(&content)?;
 
         if children.len() != 1 || !matches!(children.first(), Some(Node::Element(_))) {
             return Err(syn::Error::new(
@@ -336,32 +747,27 @@ impl Parse for ForLoop {
             ));
         }
 
+        // Optional fallback for an empty collection: `else { ... }`
+        let else_children = if input.peek(Token![else]) {
+            input.parse::<Token![else]>()?;
+            let content;
+            braced!(content in input);
+            let nodes = parse_nodes(&content)?;
+            Some(nodes)
+        } else {
+            None
+        };
+
         Ok(Self {
             pat,
             expr,
+            key,
             children,
+            else_children,
         })
     }
 }
 
-/// Parse an expression that stops before a brace.
-fn parse_expr_before_brace(input: ParseStream) -> Result<Expr> {
-    // Fork to try parsing without consuming
-    let fork = input.fork();
-
-    // Try to parse as a simple path/ident first
-    if let Ok(path) = fork.parse::<syn::ExprPath>() {
-        // Check if next token is brace
-        if fork.peek(token::Brace) {
-            input.advance_to(&fork);
-            return Ok(Expr::Path(path));
-        }
-    }
-
-    // Otherwise parse a full expression
-    input.parse()
-}
-
 impl ToTokens for ForLoop {
     fn to_tokens(&self, tokens: &mut TokenStream2) {
         let pat = &self.pat;
@@ -369,27 +775,158 @@ impl ToTokens for ForLoop {
 
         // For loops need to know the child element type
         // We expect exactly one child element in the loop body
-        if let Some(Node::Element(elem)) = self.children.first() {
-            let child_tag = &elem.tag;
-            let child_pascal = to_pascal_case(&child_tag.to_string());
-            let child_ident = Ident::new(&child_pascal, child_tag.span());
-
-            let child_attrs: Vec<_> = elem
-                .attrs
-                .iter()
-                .map(quote::ToTokens::to_token_stream)
-                .collect();
-            let nested = generate_children(&elem.children);
+        let Some(Node::Element(elem)) = self.children.first() else {
+            return;
+        };
+        let attrs = &elem.attrs;
+        let key = self.key.as_ref().map(|key| quote! { .hydration_key(#key) });
+        let nested = generate_children(&elem.children);
+        let b = builder_ident();
 
+        if elem.custom.is_some() {
+            // `children` needs a typed element, so custom elements are
+            // appended one by one in a plain loop.
+            let constructor = elem.constructor();
+            let empty = Ident::new("__ironhtml_empty", Span::mixed_site());
+            let fallback = self.else_children.as_ref().map(|else_children| {
+                let fallback = generate_children(else_children);
+                quote! {
+                    if #empty {
+                        #b = #b #fallback;
+                    }
+                }
+            });
             tokens.extend(quote! {
-                .children(#expr, |#pat, e: ::ironhtml::typed::Element<::ironhtml_elements::#child_ident>| {
-                    e #(#child_attrs)* #nested
+                .when(true, |mut #b| {
+                    let mut #empty = true;
+                    for #pat in #expr {
+                        #empty = false;
+                        #b = #b.append_child(#constructor #(#attrs)* #key #nested);
+                    }
+                    #fallback
+                    #b
                 })
             });
+            return;
+        }
+
+        let child_ident = elem.type_ident();
+        let item = quote! {
+            |#pat, #b: ::ironhtml::typed::Element<::ironhtml_elements::#child_ident>| {
+                #b #(#attrs)* #key #nested
+            }
+        };
+
+        if let Some(else_children) = &self.else_children {
+            let fallback = generate_children(else_children);
+            tokens.extend(quote! {
+                .children_or_else(#expr, #item, |#b| #b #fallback)
+            });
+        } else {
+            tokens.extend(quote! {
+                .children(#expr, #item)
+            });
         }
     }
 }
 
+/// A while loop: while #expr { children }, or
+/// while let pattern = #expr { children }
+struct WhileLoop {
+    pat: Option<syn::Pat>,
+    expr: Expr,
+    children: Vec<Node>,
+}
+
+impl Parse for WhileLoop {
+    fn parse(input: ParseStream) -> Result<Self> {
+        input.parse::<Token![while]>()?;
+        let pat = if input.peek(Token![let]) {
+            input.parse::<Token![let]>()?;
+            let pat = syn::Pat::parse_multi_with_leading_vert(input)?;
+            input.parse::<Token![=]>()?;
+            Some(pat)
+        } else {
+            None
+        };
+        input.parse::<Token![#]>()?;
+        let expr = Expr::parse_without_eager_brace(input)?;
+
+        let content;
+        braced!(content in input);
+        let children = parse_nodes(&content)?;
+
+        Ok(Self {
+            pat,
+            expr,
+            children,
+        })
+    }
+}
+
+impl ToTokens for WhileLoop {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let expr = &self.expr;
+        let child_tokens = generate_children(&self.children);
+        let b = builder_ident();
+
+        // The element moves through each iteration, so the pattern's
+        // bindings are in scope for the children.
+        let binding = self.pat.as_ref().map(|pat| quote! { let #pat = });
+        tokens.extend(quote! {
+            .when(true, |mut #b| {
+                while #binding #expr {
+                    #b = #b #child_tokens;
+                }
+                #b
+            })
+        });
+    }
+}
+
+/// A let binding: let pattern = expr; followed by the rest of the block,
+/// which can use the binding
+struct LetNode {
+    stmt: syn::Stmt,
+    rest: Vec<Node>,
+}
+
+impl Parse for LetNode {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let stmt = parse_let_stmt(input)?;
+
+        // The binding is in scope for every node after it in the same
+        // block, so those nodes belong to this one.
+        let rest = parse_nodes(input)?;
+
+        Ok(Self { stmt, rest })
+    }
+}
+
+/// Parse a `let` statement, without the nodes it scopes.
+fn parse_let_stmt(input: ParseStream) -> Result<syn::Stmt> {
+    let stmt: syn::Stmt = input.parse()?;
+    if !matches!(stmt, syn::Stmt::Local(_)) {
+        return Err(input.error("expected `let` statement"));
+    }
+    Ok(stmt)
+}
+
+impl ToTokens for LetNode {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let stmt = &self.stmt;
+        let child_tokens = generate_children(&self.rest);
+        let b = builder_ident();
+
+        tokens.extend(quote! {
+            .when(true, |#b| {
+                #stmt
+                #b #child_tokens
+            })
+        });
+    }
+}
+
 /// An if conditional: if #expr { children }
 struct IfNode {
     cond: Expr,
@@ -402,14 +939,11 @@ impl Parse for IfNode {
         input.parse::<Token![#]>()?;
 
         // Parse expression but stop before brace
-        let cond = parse_expr_before_brace(input)?;
+        let cond = Expr::parse_without_eager_brace(input)?;
 
         let content;
         braced!(content in input);
-        let mut children = Vec::new();
-        while !content.is_empty() {
-            children.push(content.parse()?);
-        }
+        let children = parse_nodes(&content)?;
 
         Ok(Self { cond, children })
     }
@@ -419,13 +953,154 @@ impl ToTokens for IfNode {
     fn to_tokens(&self, tokens: &mut TokenStream2) {
         let cond = &self.cond;
         let child_tokens = generate_children(&self.children);
+        let b = builder_ident();
 
         tokens.extend(quote! {
-            .when(#cond, |e| e #child_tokens)
+            .when(#cond, |#b| #b #child_tokens)
         });
     }
 }
 
+/// A match: match #expr { Pattern => { children }, ... }
+struct MatchNode {
+    expr: Expr,
+    arms: Vec<MatchArm>,
+}
+
+/// One arm of a match, with an optional `if` guard.
+struct MatchArm {
+    pat: syn::Pat,
+    guard: Option<Expr>,
+    children: Vec<Node>,
+}
+
+impl Parse for MatchNode {
+    fn parse(input: ParseStream) -> Result<Self> {
+        input.parse::<Token![match]>()?;
+        input.parse::<Token![#]>()?;
+        let expr = Expr::parse_without_eager_brace(input)?;
+
+        let body;
+        braced!(body in input);
+        let mut arms = Vec::new();
+        while !body.is_empty() {
+            let pat = syn::Pat::parse_multi_with_leading_vert(&body)?;
+            let guard = if body.peek(Token![if]) {
+                body.parse::<Token![if]>()?;
+                Some(body.parse()?)
+            } else {
+                None
+            };
+            body.parse::<Token![=>]>()?;
+
+            let content;
+            braced!(content in body);
+            let children = parse_nodes(&content)?;
+            if body.peek(Token![,]) {
+                body.parse::<Token![,]>()?;
+            }
+
+            arms.push(MatchArm {
+                pat,
+                guard,
+                children,
+            });
+        }
+
+        Ok(Self { expr, arms })
+    }
+}
+
+impl ToTokens for MatchNode {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let expr = &self.expr;
+        let b = builder_ident();
+        let arms = self.arms.iter().map(|arm| {
+            let pat = &arm.pat;
+            let guard = arm.guard.as_ref().map(|guard| quote! { if #guard });
+            let child_tokens = generate_children(&arm.children);
+            quote! { #pat #guard => #b #child_tokens, }
+        });
+
+        tokens.extend(quote! {
+            .when(true, |#b| match #expr { #(#arms)* })
+        });
+    }
+}
+
+/// Reject an `on(event, ...)` event with no handler attribute in
+/// [`EVENT_HANDLER_NAMES`], suggesting the closest known name, so `Click`
+/// or `onclick` fail to compile instead of rendering a handler that never
+/// runs.
+fn check_known_event(event: &Ident) -> Result<()> {
+    let name = event.to_string();
+    let known_events = || {
+        EVENT_HANDLER_NAMES
+            .iter()
+            .filter_map(|attr| attr.strip_prefix("on"))
+    };
+    if known_events().any(|known| known == name) {
+        return Ok(());
+    }
+
+    let lower = name.to_ascii_lowercase();
+    let lower = lower.strip_prefix("on").unwrap_or(&lower);
+    let closest = known_events()
+        .map(|known| (edit_distance(lower, known), known))
+        .min_by_key(|&(distance, _)| distance)
+        .filter(|&(distance, _)| distance <= 2.max(lower.len() / 3));
+    let message = match closest {
+        Some((_, known)) => format!("unknown event `{event}`; did you mean `{known}`?"),
+        None => format!(
+            "unknown event `{event}`; use `attr(\"on{name}\", ...)` for a handler \
+             attribute `html!` does not know"
+        ),
+    };
+    Err(syn::Error::new(event.span(), message))
+}
+
+/// Reject a tag that names no known element, suggesting the closest one.
+///
+/// Without this, a typo such as `diiv` only surfaces as "cannot find type
+/// `Diiv`" in the generated code.
+fn check_known_tag(tag: &Ident) -> Result<()> {
+    let name = tag.to_string().to_ascii_lowercase();
+    if TAG_NAMES.contains(&name.as_str()) || EXPERIMENTAL_TAG_NAMES.contains(&name.as_str()) {
+        return Ok(());
+    }
+
+    let closest = TAG_NAMES
+        .iter()
+        .chain(EXPERIMENTAL_TAG_NAMES)
+        .map(|known| (edit_distance(&name, known), *known))
+        .min_by_key(|&(distance, _)| distance)
+        .filter(|&(distance, _)| distance <= 2.max(name.len() / 3));
+    let message = match closest {
+        Some((_, known)) => format!("unknown element `{tag}`; did you mean `{known}`?"),
+        None => format!(
+            "unknown element `{tag}`; write custom elements as \
+             `element(\"my-widget\") {{ ... }}`"
+        ),
+    };
+    Err(syn::Error::new(tag.span(), message))
+}
+
+/// Levenshtein distance between two ASCII strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.as_bytes();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, &ca) in a.as_bytes().iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitute = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitute.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
 /// Convert `snake_case` or lowercase to `PascalCase`.
 fn to_pascal_case(s: &str) -> String {
     let mut result = String::new();
@@ -11,6 +11,7 @@
 //! - Handles malformed HTML gracefully
 //! - `no_std` compatible (with alloc)
 //! - Produces a DOM tree that can be traversed and validated
+//! - Sanitizes untrusted HTML against an allowlist ([`sanitize`])
 //!
 //! ## Example
 //!
@@ -40,11 +41,13 @@ extern crate std;
 extern crate alloc;
 
 mod dom;
+mod sanitize;
 mod tokenizer;
 mod tree_builder;
 mod validator;
 
 pub use dom::{Attribute, Document, Element, Node, NodeType, Text};
+pub use sanitize::{sanitize, SanitizePolicy};
 pub use tokenizer::{Token, Tokenizer};
 pub use tree_builder::TreeBuilder;
 pub use validator::{ValidationError, ValidationResult, Validator};
@@ -0,0 +1,480 @@
+//! Allowlist-based sanitizing of parsed HTML.
+//!
+//! [`sanitize`] rewrites a parsed fragment so it only contains the elements,
+//! attributes, and URL schemes a [`SanitizePolicy`] allows, which makes
+//! user-generated content safe to embed in a page.
+//!
+//! - Elements whose content is code or otherwise never meant to be shown
+//!   (`<script>`, `<style>`, `<iframe>`, `<svg>`, ...) are removed together
+//!   with everything inside them.
+//! - Other elements that are not allowed are unwrapped: the tag goes, its
+//!   sanitized children stay.
+//! - Attributes that are not allowed are removed, and event handlers
+//!   (`on*`) are always removed, even if a policy allows them.
+//! - URL attributes (`href`, `src`, ...) are removed unless the URL is
+//!   relative or uses an allowed scheme, so `javascript:` links are gone.
+//! - Comments are removed, and `<` and `>` in text are escaped so that
+//!   text left next to a removed element cannot form a new tag.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use ironhtml_parser::{parse_fragment, sanitize, SanitizePolicy};
+//!
+//! let nodes = parse_fragment(
+//!     r#"<p onclick="steal()">Hi <a href="javascript:steal()">there</a><script>steal()</script></p>"#,
+//! );
+//! let clean: String = sanitize(nodes, &SanitizePolicy::new())
+//!     .iter()
+//!     .map(|node| node.to_html())
+//!     .collect();
+//! assert_eq!(clean, "<p>Hi <a>there</a></p>");
+//! ```
+//!
+//! ## Reference
+//!
+//! - [OWASP XSS Prevention Cheat Sheet](https://cheatsheetseries.owasp.org/cheatsheets/Cross_Site_Scripting_Prevention_Cheat_Sheet.html)
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::dom::{Attribute, Node};
+
+/// Elements the default policy allows.
+const DEFAULT_TAGS: &[&str] = &[
+    "a",
+    "abbr",
+    "b",
+    "blockquote",
+    "br",
+    "caption",
+    "cite",
+    "code",
+    "dd",
+    "del",
+    "dfn",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "figcaption",
+    "figure",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "i",
+    "img",
+    "ins",
+    "kbd",
+    "li",
+    "mark",
+    "ol",
+    "p",
+    "pre",
+    "q",
+    "s",
+    "samp",
+    "small",
+    "span",
+    "strong",
+    "sub",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "tfoot",
+    "th",
+    "thead",
+    "time",
+    "tr",
+    "u",
+    "ul",
+];
+
+/// Attributes the default policy allows, by element; `*` is any element.
+const DEFAULT_ATTRIBUTES: &[(&str, &str)] = &[
+    ("*", "title"),
+    ("*", "lang"),
+    ("*", "dir"),
+    ("a", "href"),
+    ("abbr", "title"),
+    ("blockquote", "cite"),
+    ("del", "cite"),
+    ("del", "datetime"),
+    ("img", "src"),
+    ("img", "alt"),
+    ("img", "width"),
+    ("img", "height"),
+    ("ins", "cite"),
+    ("ins", "datetime"),
+    ("ol", "start"),
+    ("ol", "reversed"),
+    ("q", "cite"),
+    ("td", "colspan"),
+    ("td", "rowspan"),
+    ("th", "colspan"),
+    ("th", "rowspan"),
+    ("th", "scope"),
+    ("time", "datetime"),
+];
+
+/// URL schemes the default policy allows.
+const DEFAULT_SCHEMES: &[&str] = &["http", "https", "mailto"];
+
+/// Elements removed together with their content, whatever the policy.
+const DROP_WITH_CONTENT: &[&str] = &[
+    "applet",
+    "base",
+    "embed",
+    "frame",
+    "frameset",
+    "head",
+    "iframe",
+    "link",
+    "math",
+    "meta",
+    "noembed",
+    "noframes",
+    "noscript",
+    "object",
+    "plaintext",
+    "script",
+    "select",
+    "style",
+    "svg",
+    "template",
+    "textarea",
+    "title",
+    "xmp",
+];
+
+/// Attributes whose value is a URL and is checked against the allowed
+/// schemes.
+const URL_ATTRIBUTES: &[&str] = &[
+    "action",
+    "background",
+    "cite",
+    "formaction",
+    "href",
+    "poster",
+    "src",
+];
+
+/// Which elements, attributes, and URL schemes [`sanitize`] keeps.
+///
+/// [`SanitizePolicy::new`] is a conservative policy for user comments and
+/// posts: text formatting, lists, tables, links, and images, with no
+/// styling, forms, or scripting. Extend or narrow it with the builder
+/// methods.
+///
+/// ## Example
+///
+/// ```rust
+/// use ironhtml_parser::{parse_fragment, sanitize, SanitizePolicy};
+///
+/// let policy = SanitizePolicy::new()
+///     .deny_tags(["img"])
+///     .allow_attributes("span", ["class"]);
+/// let clean = sanitize(
+///     parse_fragment(r#"<span class="x">a</span><img src="/t.gif">"#),
+///     &policy,
+/// );
+/// assert_eq!(clean.len(), 1);
+/// assert_eq!(clean[0].to_html(), r#"<span class="x">a</span>"#);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SanitizePolicy {
+    tags: Vec<String>,
+    attributes: Vec<(String, String)>,
+    schemes: Vec<String>,
+}
+
+impl SanitizePolicy {
+    /// Create the default conservative policy.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            tags: DEFAULT_TAGS.iter().map(ToString::to_string).collect(),
+            attributes: DEFAULT_ATTRIBUTES
+                .iter()
+                .map(|(tag, name)| ((*tag).to_string(), (*name).to_string()))
+                .collect(),
+            schemes: DEFAULT_SCHEMES.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    /// Create a policy that allows no elements, keeping only text.
+    #[must_use]
+    pub const fn text_only() -> Self {
+        Self {
+            tags: Vec::new(),
+            attributes: Vec::new(),
+            schemes: Vec::new(),
+        }
+    }
+
+    /// Allow more elements.
+    ///
+    /// Elements that are always removed with their content, such as
+    /// `<script>` and `<iframe>`, cannot be allowed.
+    #[must_use]
+    pub fn allow_tags<'a>(mut self, tags: impl IntoIterator<Item = &'a str>) -> Self {
+        for tag in tags {
+            let tag = tag.to_ascii_lowercase();
+            if !self.tags.contains(&tag) {
+                self.tags.push(tag);
+            }
+        }
+        self
+    }
+
+    /// Stop allowing elements; they are unwrapped, keeping their content.
+    #[must_use]
+    pub fn deny_tags<'a>(mut self, tags: impl IntoIterator<Item = &'a str>) -> Self {
+        for tag in tags {
+            self.tags
+                .retain(|allowed| !allowed.eq_ignore_ascii_case(tag));
+        }
+        self
+    }
+
+    /// Allow attributes on an element, or on every allowed element if `tag`
+    /// is `"*"`.
+    ///
+    /// Event handler attributes (`onclick`, ...) are removed even if
+    /// allowed here.
+    #[must_use]
+    pub fn allow_attributes<'a>(
+        mut self,
+        tag: &str,
+        names: impl IntoIterator<Item = &'a str>,
+    ) -> Self {
+        let tag = tag.to_ascii_lowercase();
+        for name in names {
+            let pair = (tag.clone(), name.to_ascii_lowercase());
+            if !self.attributes.contains(&pair) {
+                self.attributes.push(pair);
+            }
+        }
+        self
+    }
+
+    /// Allow URLs with more schemes, such as `tel`. Relative URLs are
+    /// always allowed.
+    #[must_use]
+    pub fn allow_url_schemes<'a>(mut self, schemes: impl IntoIterator<Item = &'a str>) -> Self {
+        for scheme in schemes {
+            let scheme = scheme.to_ascii_lowercase();
+            if !self.schemes.contains(&scheme) {
+                self.schemes.push(scheme);
+            }
+        }
+        self
+    }
+
+    fn allows_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|allowed| allowed == tag)
+    }
+
+    fn allows_attribute(&self, tag: &str, attribute: &Attribute) -> bool {
+        let name = attribute.name.to_ascii_lowercase();
+        if name.starts_with("on") {
+            return false;
+        }
+        let allowed = self
+            .attributes
+            .iter()
+            .any(|(t, n)| (t == "*" || t == tag) && *n == name);
+        if !allowed {
+            return false;
+        }
+        if name == "srcset" {
+            return attribute
+                .value
+                .split(',')
+                .all(|candidate| self.allows_url(candidate.trim()));
+        }
+        !URL_ATTRIBUTES.contains(&name.as_str()) || self.allows_url(&attribute.value)
+    }
+
+    /// Whether `url` is relative or has an allowed scheme.
+    ///
+    /// Browsers ignore tabs and newlines in URLs and leading control
+    /// characters and spaces, so `java\tscript:` counts as `javascript:`.
+    /// A scheme containing `&` is rejected outright, in case the output is
+    /// later decoded and `&#115;` turns into a letter.
+    fn allows_url(&self, url: &str) -> bool {
+        let url: String = url
+            .trim_start_matches(|c: char| c <= ' ')
+            .chars()
+            .filter(|c| !matches!(c, '\t' | '\n' | '\r'))
+            .collect();
+        let Some(colon) = url.find(':') else {
+            return true;
+        };
+        let scheme = &url[..colon];
+        if scheme.contains(['/', '?', '#']) && !scheme.contains('&') {
+            // The colon is in the path, query, or fragment: relative.
+            return true;
+        }
+        self.schemes
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(scheme))
+    }
+}
+
+impl Default for SanitizePolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Remove everything `policy` does not allow from `nodes`.
+///
+/// See the [module documentation](self) for exactly what is removed. Render
+/// the result with [`Node::to_html`].
+#[must_use]
+pub fn sanitize(nodes: Vec<Node>, policy: &SanitizePolicy) -> Vec<Node> {
+    let mut output = Vec::new();
+    sanitize_into(nodes, policy, &mut output);
+    output
+}
+
+fn sanitize_into(nodes: Vec<Node>, policy: &SanitizePolicy, output: &mut Vec<Node>) {
+    for node in nodes {
+        match node {
+            Node::Text(mut text) => {
+                if text.data.contains(['<', '>']) {
+                    text.data = text.data.replace('<', "&lt;").replace('>', "&gt;");
+                }
+                output.push(Node::Text(text));
+            }
+            Node::Comment(_) => {}
+            Node::Element(mut element) => {
+                let tag = element.tag_name.to_ascii_lowercase();
+                if DROP_WITH_CONTENT.contains(&tag.as_str()) {
+                    continue;
+                }
+                let children = core::mem::take(&mut element.children);
+                if policy.allows_tag(&tag) {
+                    element
+                        .attributes
+                        .retain(|attribute| policy.allows_attribute(&tag, attribute));
+                    sanitize_into(children, policy, &mut element.children);
+                    output.push(Node::Element(element));
+                } else {
+                    sanitize_into(children, policy, output);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_fragment;
+
+    fn clean(html: &str) -> String {
+        sanitize(parse_fragment(html), &SanitizePolicy::new())
+            .iter()
+            .map(Node::to_html)
+            .collect()
+    }
+
+    #[test]
+    fn test_script_removed_with_content() {
+        assert_eq!(clean("<p>a<script>alert(1)</script>b</p>"), "<p>ab</p>");
+        assert_eq!(clean("<SCRIPT>if (a<b) alert(1)</SCRIPT>"), "");
+        assert_eq!(clean("<svg><script>1</script></svg>ok"), "ok");
+        assert_eq!(clean("<style>p{}</style><iframe src=x></iframe>"), "");
+    }
+
+    #[test]
+    fn test_event_handlers_removed() {
+        assert_eq!(
+            clean(r#"<img src="/a.png" onerror="alert(1)" alt="A">"#),
+            r#"<img src="/a.png" alt="A" />"#
+        );
+
+        // Even when a policy allows them.
+        let policy = SanitizePolicy::new().allow_attributes("*", ["onclick", "class"]);
+        let nodes = sanitize(
+            parse_fragment(r#"<b onclick="x()" class="c">b</b>"#),
+            &policy,
+        );
+        assert_eq!(nodes[0].to_html(), r#"<b class="c">b</b>"#);
+    }
+
+    #[test]
+    fn test_dangerous_urls_removed() {
+        assert_eq!(clean(r#"<a href="javascript:alert(1)">x</a>"#), "<a>x</a>");
+        assert_eq!(clean(r#"<a href=" JaVaScRiPt:alert(1)">x</a>"#), "<a>x</a>");
+        assert_eq!(clean("<a href=\"java\tscript:alert(1)\">x</a>"), "<a>x</a>");
+        assert_eq!(
+            clean(r#"<a href="java&#115;cript:alert(1)">x</a>"#),
+            "<a>x</a>"
+        );
+        assert_eq!(clean(r#"<img src="data:text/html,x">"#), "<img />");
+
+        assert_eq!(
+            clean(r#"<a href="https://example.com/?q=a:b">x</a>"#),
+            r#"<a href="https://example.com/?q=a:b">x</a>"#
+        );
+        assert_eq!(
+            clean(r#"<a href="/docs/a:b#c:d">x</a>"#),
+            r#"<a href="/docs/a:b#c:d">x</a>"#
+        );
+        assert_eq!(
+            clean(r#"<a href="mailto:a@example.com">x</a>"#),
+            r#"<a href="mailto:a@example.com">x</a>"#
+        );
+    }
+
+    #[test]
+    fn test_unknown_elements_unwrapped() {
+        assert_eq!(
+            clean(r#"<form action="/x"><label>Name <input name="n"></label></form>"#),
+            "Name "
+        );
+        assert_eq!(clean("<p>a<!-- hidden -->b</p>"), "<p>ab</p>");
+        assert_eq!(
+            clean(r#"<div style="color:red" class="x" title="t">y</div>"#),
+            r#"<div title="t">y</div>"#
+        );
+    }
+
+    #[test]
+    fn test_text_cannot_form_tags() {
+        // Removing the script must not splice `<` and `script>` together.
+        assert_eq!(
+            clean("<<script>x</script>script>alert(1)"),
+            "&lt;script&gt;alert(1)"
+        );
+        assert_eq!(clean("a < b &amp; c"), "a &lt; b &amp; c");
+    }
+
+    #[test]
+    fn test_policy_builder() {
+        let policy = SanitizePolicy::text_only();
+        let nodes = sanitize(parse_fragment("<p><b>bold</b> text</p>"), &policy);
+        let html: String = nodes.iter().map(Node::to_html).collect();
+        assert_eq!(html, "bold text");
+
+        let policy = SanitizePolicy::new()
+            .allow_tags(["SCRIPT", "details"])
+            .allow_url_schemes(["tel"]);
+        let nodes = sanitize(
+            parse_fragment(r#"<details><script>x</script><a href="tel:123">call</a></details>"#),
+            &policy,
+        );
+        assert_eq!(
+            nodes[0].to_html(),
+            r#"<details><a href="tel:123">call</a></details>"#
+        );
+    }
+}
@@ -306,11 +306,9 @@ impl TreeBuilder {
         let name_lower = name.to_ascii_lowercase();
 
         match self.insertion_mode {
-            InsertionMode::InHead => {
-                if name_lower == "head" {
-                    self.pop_element();
-                    self.insertion_mode = InsertionMode::AfterHead;
-                }
+            InsertionMode::InHead if name_lower == "head" => {
+                self.pop_element();
+                self.insertion_mode = InsertionMode::AfterHead;
             }
 
             InsertionMode::InBody => {
@@ -322,10 +320,8 @@ impl TreeBuilder {
                 }
             }
 
-            InsertionMode::AfterBody => {
-                if name_lower == "html" {
-                    self.insertion_mode = InsertionMode::AfterAfterBody;
-                }
+            InsertionMode::AfterBody if name_lower == "html" => {
+                self.insertion_mode = InsertionMode::AfterAfterBody;
             }
 
             _ => {}
@@ -90,6 +90,12 @@ impl Validator {
         // Check attribute values
         self.check_attribute_values(elem);
 
+        // Check file upload forms
+        self.check_form_enctype(elem);
+
+        // Check caption placement
+        self.check_table_caption(elem);
+
         // Recursively validate children
         for child in &elem.children {
             if let Node::Element(child_elem) = child {
@@ -167,58 +173,52 @@ impl Validator {
                 // href is not strictly required (can be a placeholder link)
                 // but we could warn if it's missing
             }
-            "input" => {
-                // Check for label association if not a hidden input
-                if elem.get_attribute("type") != Some("hidden") && !elem.has_attribute("id") {
-                    // This is a soft warning - input should have id for label association
-                }
+            "input"
+                if elem.get_attribute("type") != Some("hidden") && !elem.has_attribute("id") =>
+            {
+                // This is a soft warning - input should have id for label association
             }
             "script" => {
                 // script requires either src or inline content
             }
-            "link" => {
-                if elem.get_attribute("rel") == Some("stylesheet") && !elem.has_attribute("href") {
-                    self.errors.push(ValidationError {
-                        kind: ValidationErrorKind::MissingRequiredAttribute,
-                        element: elem.tag_name.clone(),
-                        message:
-                            "The <link rel=\"stylesheet\"> element requires an 'href' attribute"
-                                .into(),
-                    });
-                }
+            "link"
+                if elem.get_attribute("rel") == Some("stylesheet")
+                    && !elem.has_attribute("href") =>
+            {
+                self.errors.push(ValidationError {
+                    kind: ValidationErrorKind::MissingRequiredAttribute,
+                    element: elem.tag_name.clone(),
+                    message: "The <link rel=\"stylesheet\"> element requires an 'href' attribute"
+                        .into(),
+                });
             }
-            "form" => {
-                if !elem.has_attribute("action") {
-                    // action is technically optional in HTML5, defaults to current URL
-                }
+            "form" if !elem.has_attribute("action") => {
+                // action is technically optional in HTML5, defaults to current URL
             }
-            "iframe" => {
-                if !elem.has_attribute("src") && !elem.has_attribute("srcdoc") {
-                    self.errors.push(ValidationError {
-                        kind: ValidationErrorKind::MissingRequiredAttribute,
-                        element: elem.tag_name.clone(),
-                        message: "The <iframe> element requires either 'src' or 'srcdoc' attribute"
-                            .into(),
-                    });
-                }
+            "iframe" if !elem.has_attribute("src") && !elem.has_attribute("srcdoc") => {
+                self.errors.push(ValidationError {
+                    kind: ValidationErrorKind::MissingRequiredAttribute,
+                    element: elem.tag_name.clone(),
+                    message: "The <iframe> element requires either 'src' or 'srcdoc' attribute"
+                        .into(),
+                });
             }
-            "video" | "audio" => {
-                // Should have src attribute or source children
+            // Should have src attribute or source children
+            "video" | "audio"
                 if !elem.has_attribute("src")
                     && !elem
                         .children
                         .iter()
-                        .any(|c| matches!(c, Node::Element(e) if e.tag_name == "source"))
-                {
-                    self.errors.push(ValidationError {
-                        kind: ValidationErrorKind::MissingRequiredAttribute,
-                        element: elem.tag_name.clone(),
-                        message: alloc::format!(
-                            "The <{}> element requires either 'src' attribute or <source> children",
-                            elem.tag_name
-                        ),
-                    });
-                }
+                        .any(|c| matches!(c, Node::Element(e) if e.tag_name == "source")) =>
+            {
+                self.errors.push(ValidationError {
+                    kind: ValidationErrorKind::MissingRequiredAttribute,
+                    element: elem.tag_name.clone(),
+                    message: alloc::format!(
+                        "The <{}> element requires either 'src' attribute or <source> children",
+                        elem.tag_name
+                    ),
+                });
             }
             "meta" => {
                 // meta should have either charset, name+content, http-equiv+content, or itemprop
@@ -236,23 +236,19 @@ impl Validator {
                     });
                 }
             }
-            "area" => {
-                if !elem.has_attribute("alt") {
-                    self.errors.push(ValidationError {
-                        kind: ValidationErrorKind::MissingRequiredAttribute,
-                        element: elem.tag_name.clone(),
-                        message: "The <area> element requires an 'alt' attribute".into(),
-                    });
-                }
+            "area" if !elem.has_attribute("alt") => {
+                self.errors.push(ValidationError {
+                    kind: ValidationErrorKind::MissingRequiredAttribute,
+                    element: elem.tag_name.clone(),
+                    message: "The <area> element requires an 'alt' attribute".into(),
+                });
             }
-            "optgroup" => {
-                if !elem.has_attribute("label") {
-                    self.errors.push(ValidationError {
-                        kind: ValidationErrorKind::MissingRequiredAttribute,
-                        element: elem.tag_name.clone(),
-                        message: "The <optgroup> element requires a 'label' attribute".into(),
-                    });
-                }
+            "optgroup" if !elem.has_attribute("label") => {
+                self.errors.push(ValidationError {
+                    kind: ValidationErrorKind::MissingRequiredAttribute,
+                    element: elem.tag_name.clone(),
+                    message: "The <optgroup> element requires a 'label' attribute".into(),
+                });
             }
             "progress" => {
                 // value and max are optional but recommended
@@ -349,6 +345,77 @@ impl Validator {
             }
         }
     }
+
+    /// A form with a file input must be submitted as `multipart/form-data`
+    /// over POST, or the browser sends only the file names.
+    fn check_form_enctype(&mut self, elem: &Element) {
+        if elem.tag_name != "form" || !contains_file_input(elem) {
+            return;
+        }
+
+        let is_post = elem
+            .get_attribute("method")
+            .is_some_and(|m| m.eq_ignore_ascii_case("post"));
+        if !is_post {
+            self.errors.push(ValidationError {
+                kind: ValidationErrorKind::InvalidAttributeValue,
+                element: elem.tag_name.clone(),
+                message: "A <form> containing a file input must use method=\"post\"".into(),
+            });
+        }
+
+        match elem.get_attribute("enctype") {
+            Some(enctype) if enctype.eq_ignore_ascii_case("multipart/form-data") => {}
+            Some(enctype) => self.errors.push(ValidationError {
+                kind: ValidationErrorKind::InvalidAttributeValue,
+                element: elem.tag_name.clone(),
+                message: alloc::format!(
+                    "A <form> containing a file input must use enctype=\"multipart/form-data\", not '{enctype}'"
+                ),
+            }),
+            None => self.errors.push(ValidationError {
+                kind: ValidationErrorKind::MissingRequiredAttribute,
+                element: elem.tag_name.clone(),
+                message: "A <form> containing a file input requires enctype=\"multipart/form-data\""
+                    .into(),
+            }),
+        }
+    }
+
+    /// A `<table>` may have one `<caption>`, and it must be the first child
+    /// element.
+    fn check_table_caption(&mut self, elem: &Element) {
+        if elem.tag_name != "table" {
+            return;
+        }
+
+        let children = elem.children.iter().filter_map(|child| match child {
+            Node::Element(e) => Some(e),
+            _ => None,
+        });
+        for (i, child) in children.enumerate() {
+            if child.tag_name == "caption" && i > 0 {
+                self.errors.push(ValidationError {
+                    kind: ValidationErrorKind::InvalidNesting,
+                    element: child.tag_name.clone(),
+                    message: "<caption> must be the first child of <table>".into(),
+                });
+            }
+        }
+    }
+}
+
+/// Whether any descendant of `elem` is an `<input type="file">`.
+fn contains_file_input(elem: &Element) -> bool {
+    elem.children.iter().any(|child| match child {
+        Node::Element(e) => {
+            (e.tag_name == "input"
+                && e.get_attribute("type")
+                    .is_some_and(|t| t.eq_ignore_ascii_case("file")))
+                || contains_file_input(e)
+        }
+        _ => false,
+    })
 }
 
 impl Default for Validator {
@@ -426,4 +493,53 @@ mod tests {
             .any(|e| e.kind == ValidationErrorKind::InvalidAttributeValue
                 && e.message.contains("input type")));
     }
+
+    #[test]
+    fn test_file_input_in_get_form() {
+        let doc = parse(r#"<form method="get"><input type="file" name="upload"></form>"#);
+        let errors = Validator::new().validate(&doc);
+        assert!(errors
+            .iter()
+            .any(|e| e.kind == ValidationErrorKind::InvalidAttributeValue
+                && e.message.contains("method")));
+        assert!(errors
+            .iter()
+            .any(|e| e.kind == ValidationErrorKind::MissingRequiredAttribute
+                && e.message.contains("enctype")));
+    }
+
+    #[test]
+    fn test_file_input_wrong_enctype() {
+        let doc = parse(
+            r#"<form method="post" enctype="text/plain"><div><input type="file"></div></form>"#,
+        );
+        let errors = Validator::new().validate(&doc);
+        assert!(errors
+            .iter()
+            .any(|e| e.kind == ValidationErrorKind::InvalidAttributeValue
+                && e.message.contains("text/plain")));
+    }
+
+    #[test]
+    fn test_file_input_multipart_form() {
+        let doc = parse(
+            r#"<form method="post" enctype="multipart/form-data"><input type="file"></form>"#,
+        );
+        let errors = Validator::new().validate(&doc);
+        assert!(!errors.iter().any(|e| e.element == "form"));
+    }
+
+    #[test]
+    fn test_caption_not_first_in_table() {
+        let nodes = parse_fragment("<table><tr><td>1</td></tr><caption>Totals</caption></table>");
+        let errors = Validator::new().validate_nodes(&nodes);
+        assert!(errors
+            .iter()
+            .any(|e| e.kind == ValidationErrorKind::InvalidNesting && e.element == "caption"));
+
+        let nodes =
+            parse_fragment("<table>\n  <caption>Totals</caption>\n  <tr><td>1</td></tr></table>");
+        let errors = Validator::new().validate_nodes(&nodes);
+        assert!(!errors.iter().any(|e| e.element == "caption"));
+    }
 }
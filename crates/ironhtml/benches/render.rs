@@ -1,7 +1,12 @@
-use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
 use ironhtml::html;
 use ironhtml::typed::Element;
-use ironhtml_elements::Li;
+use ironhtml::Render;
+use ironhtml_attributes::{
+    AttributeValue, Autocomplete, CrossOrigin, InputType, Loading, ReferrerPolicy,
+};
+use ironhtml_elements::{Input, Li};
+use std::hint::black_box;
 
 // ============================================================================
 // Escape functions
@@ -254,6 +259,75 @@ fn bench_untyped_children_sizes(c: &mut Criterion) {
     group.finish();
 }
 
+// ============================================================================
+// Attribute values
+// ============================================================================
+
+fn bench_attribute_values(c: &mut Criterion) {
+    let mut group = c.benchmark_group("attr_value");
+
+    // Enum values are `Cow::Borrowed` static strings: no allocation.
+    group.bench_function("enum_borrowed", |b| {
+        b.iter(|| {
+            black_box([
+                black_box(InputType::Email).to_attr_value(),
+                black_box(Autocomplete::Email).to_attr_value(),
+                black_box(Loading::Lazy).to_attr_value(),
+                black_box(CrossOrigin::Anonymous).to_attr_value(),
+                black_box(ReferrerPolicy::NoReferrer).to_attr_value(),
+            ])
+        });
+    });
+
+    // The same values built as owned strings: one allocation each.
+    group.bench_function("string_owned", |b| {
+        b.iter(|| {
+            black_box([
+                String::from(black_box("email")),
+                String::from(black_box("email")),
+                String::from(black_box("lazy")),
+                String::from(black_box("anonymous")),
+                String::from(black_box("no-referrer")),
+            ])
+        });
+    });
+
+    group.bench_function("typed_input_enum_attrs", |b| {
+        b.iter(|| {
+            Element::<Input>::new()
+                .attr_value("type", &InputType::Email)
+                .attr_value("autocomplete", &Autocomplete::Email)
+                .render()
+        });
+    });
+
+    group.finish();
+}
+
+// ============================================================================
+// Pooled vs fresh output buffers
+// ============================================================================
+
+fn bench_pooled_render(c: &mut Criterion) {
+    let items: Vec<String> = (0..100).map(|i| format!("item {i}")).collect();
+    let list = Element::<ironhtml_elements::Ul>::new()
+        .children(&items, |item, li: Element<Li>| li.text(item.as_str()));
+
+    let mut group = c.benchmark_group("render_buffer");
+
+    // A new `String` per render, grown from empty.
+    group.bench_function("fresh", |b| {
+        b.iter(|| black_box(list.render()).len());
+    });
+
+    // A buffer from the thread-local pool, returned on drop.
+    group.bench_function("pooled", |b| {
+        b.iter(|| black_box(list.render_pooled()).len());
+    });
+
+    group.finish();
+}
+
 // ============================================================================
 // Criterion groups
 // ============================================================================
@@ -269,5 +343,7 @@ criterion_group!(
     bench_macro_conditional,
     bench_typed_children_sizes,
     bench_untyped_children_sizes,
+    bench_attribute_values,
+    bench_pooled_render,
 );
 criterion_main!(benches);
@@ -0,0 +1,645 @@
+//! Machine-readable values for the `datetime` attribute.
+//!
+//! `<time>`, `<ins>`, and `<del>` carry a `datetime` attribute whose value
+//! must follow one of the date, time, or duration formats defined by the
+//! HTML spec. [`DatetimeValue`] turns typed values into those strings:
+//! [`core::time::Duration`] always, `chrono` types with the `chrono`
+//! feature, and `time` types with the `time` feature. Plain strings are
+//! accepted too, after checking them with [`is_valid_datetime`].
+//!
+//! ## Example
+//!
+//! ```rust
+//! use core::time::Duration;
+//! use ironhtml::typed::Element;
+//! use ironhtml_elements::Time;
+//!
+//! let published = Element::<Time>::new().datetime("2024-03-15").text("March 15");
+//! assert_eq!(
+//!     published.render(),
+//!     r#"<time datetime="2024-03-15">March 15</time>"#
+//! );
+//!
+//! let runtime = Element::<Time>::new()
+//!     .datetime(Duration::from_secs(5400))
+//!     .text("1h 30m");
+//! assert_eq!(runtime.render(), r#"<time datetime="PT5400S">1h 30m</time>"#);
+//! ```
+//!
+//! ## Reference
+//!
+//! - [The `time` element](https://html.spec.whatwg.org/multipage/text-level-semantics.html#the-time-element)
+//! - [Dates and times](https://html.spec.whatwg.org/multipage/common-microsyntaxes.html#dates-and-times)
+
+use alloc::string::String;
+use core::fmt::Write;
+
+/// A value that can be written as a `datetime` attribute.
+pub trait DatetimeValue {
+    /// Format the value as an HTML date, time, or duration string.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, string implementations panic if the string is not a
+    /// valid value (see [`is_valid_datetime`]); release builds write it
+    /// as-is. Typed implementations panic only for values HTML cannot
+    /// represent, such as years before 0001 or negative durations.
+    fn to_datetime_string(&self) -> String;
+}
+
+impl<T: DatetimeValue + ?Sized> DatetimeValue for &T {
+    fn to_datetime_string(&self) -> String {
+        (**self).to_datetime_string()
+    }
+}
+
+impl DatetimeValue for str {
+    fn to_datetime_string(&self) -> String {
+        debug_assert!(is_valid_datetime(self), "invalid datetime value {self:?}");
+        String::from(self)
+    }
+}
+
+impl DatetimeValue for String {
+    fn to_datetime_string(&self) -> String {
+        self.as_str().to_datetime_string()
+    }
+}
+
+impl DatetimeValue for core::time::Duration {
+    fn to_datetime_string(&self) -> String {
+        let mut out = String::new();
+        push_duration(&mut out, self.as_secs(), self.subsec_millis());
+        out
+    }
+}
+
+// =============================================================================
+// Formatting
+// =============================================================================
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn push_date(out: &mut String, year: i32, month: u32, day: u32) {
+    assert!(year >= 1, "year {year} cannot be written as an HTML date");
+    let _ = write!(out, "{year:04}-{month:02}-{day:02}");
+}
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn push_time(out: &mut String, hour: u32, minute: u32, second: u32, millis: u32) {
+    let _ = write!(out, "{hour:02}:{minute:02}:{second:02}");
+    if millis > 0 {
+        let _ = write!(out, ".{millis:03}");
+    }
+}
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+/// Write a time-zone offset given in seconds east of UTC. HTML offsets have
+/// minute precision, so any seconds are dropped.
+fn push_offset(out: &mut String, seconds: i32) {
+    if seconds == 0 {
+        out.push('Z');
+        return;
+    }
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let minutes = seconds.unsigned_abs() / 60;
+    let _ = write!(out, "{sign}{:02}:{:02}", minutes / 60, minutes % 60);
+}
+
+fn push_duration(out: &mut String, seconds: u64, millis: u32) {
+    let _ = write!(out, "PT{seconds}");
+    if millis > 0 {
+        let _ = write!(out, ".{millis:03}");
+    }
+    out.push('S');
+}
+
+// =============================================================================
+// chrono
+// =============================================================================
+
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    use super::{push_date, push_duration, push_offset, push_time, DatetimeValue};
+    use alloc::string::String;
+    use chrono::{Datelike, Offset, TimeZone, Timelike};
+
+    const fn millis(nanos: u32) -> u32 {
+        // chrono represents a leap second as nanos >= 1_000_000_000
+        (nanos % 1_000_000_000) / 1_000_000
+    }
+
+    impl DatetimeValue for chrono::NaiveDate {
+        fn to_datetime_string(&self) -> String {
+            let mut out = String::new();
+            push_date(&mut out, self.year(), self.month(), self.day());
+            out
+        }
+    }
+
+    impl DatetimeValue for chrono::NaiveTime {
+        fn to_datetime_string(&self) -> String {
+            let mut out = String::new();
+            push_time(
+                &mut out,
+                self.hour(),
+                self.minute(),
+                self.second(),
+                millis(self.nanosecond()),
+            );
+            out
+        }
+    }
+
+    impl DatetimeValue for chrono::NaiveDateTime {
+        fn to_datetime_string(&self) -> String {
+            let mut out = self.date().to_datetime_string();
+            out.push('T');
+            out.push_str(&self.time().to_datetime_string());
+            out
+        }
+    }
+
+    impl<Tz: TimeZone> DatetimeValue for chrono::DateTime<Tz> {
+        fn to_datetime_string(&self) -> String {
+            let mut out = self.naive_local().to_datetime_string();
+            push_offset(&mut out, self.offset().fix().local_minus_utc());
+            out
+        }
+    }
+
+    impl DatetimeValue for chrono::TimeDelta {
+        fn to_datetime_string(&self) -> String {
+            let duration = self
+                .to_std()
+                .expect("negative durations cannot be written as an HTML duration");
+            let mut out = String::new();
+            push_duration(&mut out, duration.as_secs(), duration.subsec_millis());
+            out
+        }
+    }
+}
+
+// =============================================================================
+// time
+// =============================================================================
+
+#[cfg(feature = "time")]
+mod time_impls {
+    use super::{push_date, push_duration, push_offset, push_time, DatetimeValue};
+    use alloc::string::String;
+
+    impl DatetimeValue for time::Date {
+        fn to_datetime_string(&self) -> String {
+            let mut out = String::new();
+            push_date(
+                &mut out,
+                self.year(),
+                u32::from(u8::from(self.month())),
+                u32::from(self.day()),
+            );
+            out
+        }
+    }
+
+    impl DatetimeValue for time::Time {
+        fn to_datetime_string(&self) -> String {
+            let mut out = String::new();
+            push_time(
+                &mut out,
+                u32::from(self.hour()),
+                u32::from(self.minute()),
+                u32::from(self.second()),
+                u32::from(self.millisecond()),
+            );
+            out
+        }
+    }
+
+    impl DatetimeValue for time::PrimitiveDateTime {
+        fn to_datetime_string(&self) -> String {
+            let mut out = self.date().to_datetime_string();
+            out.push('T');
+            out.push_str(&self.time().to_datetime_string());
+            out
+        }
+    }
+
+    impl DatetimeValue for time::OffsetDateTime {
+        fn to_datetime_string(&self) -> String {
+            let mut out = self.date().to_datetime_string();
+            out.push('T');
+            out.push_str(&self.time().to_datetime_string());
+            push_offset(&mut out, self.offset().whole_seconds());
+            out
+        }
+    }
+
+    impl DatetimeValue for time::Duration {
+        fn to_datetime_string(&self) -> String {
+            assert!(
+                !self.is_negative(),
+                "negative durations cannot be written as an HTML duration"
+            );
+            let mut out = String::new();
+            push_duration(
+                &mut out,
+                self.whole_seconds().unsigned_abs(),
+                self.subsec_milliseconds().unsigned_abs().into(),
+            );
+            out
+        }
+    }
+}
+
+// =============================================================================
+// Validation
+// =============================================================================
+
+/// Whether `s` is a valid `datetime` value for `<time>`.
+///
+/// Accepts every format the HTML spec allows: a month (`2024-03`), a date
+/// (`2024-03-15`), a yearless date (`03-15`), a time (`14:30`,
+/// `14:30:05.250`), a local date and time (`2024-03-15T14:30`), a
+/// time-zone offset (`Z`, `+05:30`), a global date and time
+/// (`2024-03-15T14:30Z`), a week (`2024-W11`), a year (`2024`), or a
+/// duration (`PT1H30M`, `1h 30m`).
+///
+/// ## Example
+///
+/// ```rust
+/// use ironhtml::datetime::is_valid_datetime;
+///
+/// assert!(is_valid_datetime("2024-02-29"));
+/// assert!(!is_valid_datetime("2023-02-29"));
+/// assert!(is_valid_datetime("2024-03-15T14:30:00+01:00"));
+/// assert!(!is_valid_datetime("March 15"));
+/// ```
+#[must_use]
+pub fn is_valid_datetime(s: &str) -> bool {
+    let b = s.as_bytes();
+    parse_full(b, month)
+        || parse_full(b, date)
+        || parse_full(b, yearless_date)
+        || parse_full(b, time)
+        || parse_full(b, local_datetime)
+        || parse_full(b, tz_offset)
+        || parse_full(b, global_datetime)
+        || parse_full(b, week)
+        || parse_full(b, |b| year(b).map(|(_, rest)| rest))
+        || iso_duration(b)
+        || component_duration(s)
+}
+
+/// A parser consumes a prefix of its input and returns the rest.
+type Rest<'a> = Option<&'a [u8]>;
+
+fn parse_full(b: &[u8], parser: impl Fn(&[u8]) -> Rest<'_>) -> bool {
+    parser(b).is_some_and(<[u8]>::is_empty)
+}
+
+/// Exactly `n` ASCII digits.
+fn digits(b: &[u8], n: usize) -> Option<(u32, &[u8])> {
+    if b.len() < n || !b[..n].iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+    let value = b[..n]
+        .iter()
+        .fold(0, |acc, d| acc * 10 + u32::from(d - b'0'));
+    Some((value, &b[n..]))
+}
+
+/// One or more ASCII digits.
+fn number(b: &[u8]) -> Option<(u64, &[u8])> {
+    let len = b.iter().take_while(|c| c.is_ascii_digit()).count();
+    if len == 0 {
+        return None;
+    }
+    let value = b[..len].iter().try_fold(0u64, |acc, d| {
+        acc.checked_mul(10)?.checked_add(u64::from(d - b'0'))
+    })?;
+    Some((value, &b[len..]))
+}
+
+fn byte(b: &[u8], c: u8) -> Rest<'_> {
+    b.strip_prefix(&[c])
+}
+
+/// Four or more digits, greater than zero.
+fn year(b: &[u8]) -> Option<(u64, &[u8])> {
+    let (value, rest) = number(b)?;
+    (b.len() - rest.len() >= 4 && value > 0).then_some((value, rest))
+}
+
+const fn is_leap(year: u64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+const fn days_in_month(month: u32, leap: bool) -> u32 {
+    match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+fn month_number(b: &[u8]) -> Option<(u32, &[u8])> {
+    digits(b, 2).filter(|(m, _)| (1..=12).contains(m))
+}
+
+fn month(b: &[u8]) -> Rest<'_> {
+    let (_, rest) = year(b)?;
+    let (_, rest) = month_number(byte(rest, b'-')?)?;
+    Some(rest)
+}
+
+fn date(b: &[u8]) -> Rest<'_> {
+    let (y, rest) = year(b)?;
+    let (m, rest) = month_number(byte(rest, b'-')?)?;
+    let (d, rest) = digits(byte(rest, b'-')?, 2)?;
+    (1..=days_in_month(m, is_leap(y)))
+        .contains(&d)
+        .then_some(rest)
+}
+
+fn yearless_date(b: &[u8]) -> Rest<'_> {
+    let b = b.strip_prefix(b"--").unwrap_or(b);
+    let (m, rest) = month_number(b)?;
+    let (d, rest) = digits(byte(rest, b'-')?, 2)?;
+    (1..=days_in_month(m, true)).contains(&d).then_some(rest)
+}
+
+fn time(b: &[u8]) -> Rest<'_> {
+    let (h, rest) = digits(b, 2)?;
+    let (m, mut rest) = digits(byte(rest, b':')?, 2)?;
+    if h > 23 || m > 59 {
+        return None;
+    }
+    if let Some(after) = byte(rest, b':') {
+        let (s, after) = digits(after, 2)?;
+        if s > 59 {
+            return None;
+        }
+        rest = after;
+        if let Some(after) = byte(rest, b'.') {
+            let len = after.iter().take_while(|c| c.is_ascii_digit()).count();
+            if !(1..=3).contains(&len) {
+                return None;
+            }
+            rest = &after[len..];
+        }
+    }
+    Some(rest)
+}
+
+fn local_datetime(b: &[u8]) -> Rest<'_> {
+    let rest = date(b)?;
+    let rest = byte(rest, b'T').or_else(|| byte(rest, b' '))?;
+    time(rest)
+}
+
+fn tz_offset(b: &[u8]) -> Rest<'_> {
+    if let Some(rest) = byte(b, b'Z') {
+        return Some(rest);
+    }
+    let rest = byte(b, b'+').or_else(|| byte(b, b'-'))?;
+    let (h, rest) = digits(rest, 2)?;
+    let rest = byte(rest, b':').unwrap_or(rest);
+    let (m, rest) = digits(rest, 2)?;
+    (h <= 23 && m <= 59).then_some(rest)
+}
+
+fn global_datetime(b: &[u8]) -> Rest<'_> {
+    tz_offset(local_datetime(b)?)
+}
+
+fn week(b: &[u8]) -> Rest<'_> {
+    let (y, rest) = year(b)?;
+    let (w, rest) = digits(byte(rest, b'-')?.strip_prefix(b"W")?, 2)?;
+    (1..=weeks_in_year(y))
+        .contains(&u64::from(w))
+        .then_some(rest)
+}
+
+/// ISO weeks in `year`: 53 if it starts on a Thursday, or on a Wednesday
+/// in a leap year; otherwise 52.
+const fn weeks_in_year(year: u64) -> u64 {
+    // Day of week of January 1st, 0 = Sunday (Gauss's algorithm).
+    let y = year - 1;
+    let jan1 = (1 + 5 * (y % 4) + 4 * (y % 100) + 6 * (y % 400)) % 7;
+    if jan1 == 4 || (jan1 == 3 && is_leap(year)) {
+        53
+    } else {
+        52
+    }
+}
+
+/// `P[nD][T[nH][nM][n[.fff]S]]` with at least one component.
+fn iso_duration(b: &[u8]) -> bool {
+    let Some(mut rest) = byte(b, b'P') else {
+        return false;
+    };
+    let has_days = if let Some((_, after)) = number(rest) {
+        let Some(after) = byte(after, b'D') else {
+            return false;
+        };
+        rest = after;
+        true
+    } else {
+        false
+    };
+    let has_time = if let Some(after) = byte(rest, b'T') {
+        rest = after;
+        let mut any_time = false;
+        for unit in [b'H', b'M'] {
+            if let Some((_, after)) = number(rest) {
+                if let Some(after) = byte(after, unit) {
+                    rest = after;
+                    any_time = true;
+                }
+            }
+        }
+        if let Some((_, after)) = number(rest) {
+            let after = match byte(after, b'.') {
+                Some(frac) => {
+                    let len = frac.iter().take_while(|c| c.is_ascii_digit()).count();
+                    if !(1..=3).contains(&len) {
+                        return false;
+                    }
+                    &frac[len..]
+                }
+                None => after,
+            };
+            let Some(after) = byte(after, b'S') else {
+                return false;
+            };
+            rest = after;
+            any_time = true;
+        }
+        // A `T` must be followed by at least one time component.
+        if !any_time {
+            return false;
+        }
+        true
+    } else {
+        false
+    };
+    (has_days || has_time) && rest.is_empty()
+}
+
+/// Whitespace-separated components such as `1w 2d 4h 18m 3.5s`, each unit
+/// at most once.
+fn component_duration(s: &str) -> bool {
+    let mut seen = [false; 5];
+    let mut any = false;
+    let mut rest = s
+        .trim_start_matches(|c: char| c.is_ascii_whitespace())
+        .as_bytes();
+    while !rest.is_empty() {
+        let Some((_, after)) = number(rest) else {
+            return false;
+        };
+        let mut after = after;
+        let fraction = if let Some(frac) = byte(after, b'.') {
+            let len = frac.iter().take_while(|c| c.is_ascii_digit()).count();
+            if !(1..=3).contains(&len) {
+                return false;
+            }
+            after = &frac[len..];
+            true
+        } else {
+            false
+        };
+        let len = after.iter().take_while(|c| c.is_ascii_whitespace()).count();
+        after = &after[len..];
+        let slot = match after.first().map(u8::to_ascii_lowercase) {
+            Some(b'w') => 0,
+            Some(b'd') => 1,
+            Some(b'h') => 2,
+            Some(b'm') => 3,
+            Some(b's') => 4,
+            _ => return false,
+        };
+        if seen[slot] || (fraction && slot != 4) {
+            return false;
+        }
+        seen[slot] = true;
+        any = true;
+        after = &after[1..];
+        let len = after.iter().take_while(|c| c.is_ascii_whitespace()).count();
+        rest = &after[len..];
+    }
+    any
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_datetimes() {
+        for valid in [
+            "2024",
+            "2024-03",
+            "2024-03-15",
+            "2024-02-29",
+            "03-15",
+            "--02-29",
+            "14:30",
+            "14:30:05",
+            "14:30:05.250",
+            "2024-03-15T14:30",
+            "2024-03-15 14:30:00",
+            "Z",
+            "+05:30",
+            "-0800",
+            "2024-03-15T14:30:00Z",
+            "2024-03-15T14:30:00.5+01:00",
+            "2024-W11",
+            "2020-W53",
+            "PT1H30M",
+            "P2D",
+            "P1DT0.5S",
+            "1h 30m",
+            "4h18m3.25s",
+            "1w 2D",
+        ] {
+            assert!(is_valid_datetime(valid), "{valid} should be valid");
+        }
+    }
+
+    #[test]
+    fn test_invalid_datetimes() {
+        for invalid in [
+            "",
+            "March 15",
+            "24-03-15",
+            "2023-02-29",
+            "2024-13",
+            "2024-04-31",
+            "24:00",
+            "14:60",
+            "14:30:05.2500",
+            "2024-03-15T",
+            "+24:00",
+            "2023-W53",
+            "2024-W00",
+            "P",
+            "PT",
+            "P1Y",
+            "PT1.5M",
+            "1h 1h",
+            "1.5h",
+            "0000",
+        ] {
+            assert!(!is_valid_datetime(invalid), "{invalid} should be invalid");
+        }
+    }
+
+    #[test]
+    fn test_std_duration() {
+        use core::time::Duration;
+        assert_eq!(Duration::from_secs(90).to_datetime_string(), "PT90S");
+        assert_eq!(Duration::from_millis(1500).to_datetime_string(), "PT1.500S");
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "invalid datetime value")]
+    fn test_invalid_string_panics() {
+        let _ = "yesterday".to_datetime_string();
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_chrono_values() {
+        use chrono::{FixedOffset, NaiveDate, TimeZone};
+        let date = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+        assert_eq!(date.to_datetime_string(), "2024-03-15");
+        let local = date.and_hms_milli_opt(14, 30, 5, 250).unwrap();
+        assert_eq!(local.to_datetime_string(), "2024-03-15T14:30:05.250");
+        let zoned = FixedOffset::east_opt(-(5 * 3600 + 30 * 60))
+            .unwrap()
+            .from_local_datetime(&date.and_hms_opt(9, 0, 0).unwrap())
+            .unwrap();
+        assert_eq!(zoned.to_datetime_string(), "2024-03-15T09:00:00-05:30");
+        assert!(is_valid_datetime(&zoned.to_datetime_string()));
+        assert_eq!(
+            chrono::TimeDelta::minutes(90).to_datetime_string(),
+            "PT5400S"
+        );
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_time_values() {
+        use time::{Date, Month, PrimitiveDateTime, Time, UtcOffset};
+        let date = Date::from_calendar_date(2024, Month::March, 15).unwrap();
+        assert_eq!(date.to_datetime_string(), "2024-03-15");
+        let local = PrimitiveDateTime::new(date, Time::from_hms(14, 30, 0).unwrap());
+        assert_eq!(local.to_datetime_string(), "2024-03-15T14:30:00");
+        let utc = local.assume_offset(UtcOffset::UTC);
+        assert_eq!(utc.to_datetime_string(), "2024-03-15T14:30:00Z");
+        assert_eq!(time::Duration::minutes(90).to_datetime_string(), "PT5400S");
+    }
+}
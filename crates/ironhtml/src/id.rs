@@ -0,0 +1,152 @@
+//! Deterministic generation of unique ids and class names.
+//!
+//! Linking elements together (`<label for>`, `aria-describedby`, scoped
+//! class names) needs tokens that do not collide. [`IdGenerator`] derives
+//! them by hashing a seed and a counter, so it needs no random number
+//! source and works under `no_std`.
+//!
+//! ## Determinism
+//!
+//! A generator built with [`IdGenerator::new`] or
+//! [`IdGenerator::from_scope`] yields the same sequence of tokens on every
+//! run and every platform: the hash is 64-bit FNV-1a over the little-endian
+//! bytes of the seed and counter. [`IdGenerator::at_caller`] seeds from the
+//! source location of its caller, so it is stable for a given build but
+//! changes when the calling code moves. This makes rendered output
+//! reproducible, which keeps snapshot tests and caches stable.
+//!
+//! With the `std` feature, [`IdGenerator::random`] seeds from the operating
+//! system's randomness instead, for ids that must differ between runs.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use ironhtml::id::IdGenerator;
+//!
+//! let mut ids = IdGenerator::from_scope("signup-form");
+//! let email = ids.next_id("email");
+//! let hint = ids.next_id("hint");
+//! assert_ne!(email, hint);
+//! assert!(email.starts_with("email-"));
+//!
+//! // The same scope always gives the same ids.
+//! assert_eq!(IdGenerator::from_scope("signup-form").next_id("email"), email);
+//! ```
+
+use alloc::string::String;
+use core::fmt::Write;
+
+use crate::{fnv1a, FNV_OFFSET};
+
+/// A source of unique, deterministic id and class tokens.
+///
+/// Each call to [`next_id`](Self::next_id) returns a new token: 48 bits of
+/// hash, so a page would need millions of ids before a collision became
+/// likely. Give independent parts of a page their own scope so their
+/// sequences do not overlap.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdGenerator {
+    seed: u64,
+    counter: u64,
+}
+
+impl IdGenerator {
+    /// Create a generator from a numeric seed.
+    #[must_use]
+    pub const fn new(seed: u64) -> Self {
+        Self { seed, counter: 0 }
+    }
+
+    /// Create a generator seeded from a scope name, such as a component
+    /// name.
+    #[must_use]
+    pub const fn from_scope(scope: &str) -> Self {
+        Self::new(fnv1a(FNV_OFFSET, scope.as_bytes()))
+    }
+
+    /// Create a generator seeded from the file, line, and column of the
+    /// call, so each call site gets its own sequence.
+    #[must_use]
+    #[track_caller]
+    pub const fn at_caller() -> Self {
+        let location = core::panic::Location::caller();
+        let hash = fnv1a(FNV_OFFSET, location.file().as_bytes());
+        let hash = fnv1a(hash, &location.line().to_le_bytes());
+        Self::new(fnv1a(hash, &location.column().to_le_bytes()))
+    }
+
+    /// Create a generator seeded from the operating system's randomness,
+    /// for ids that differ on every run.
+    ///
+    /// Requires the `std` feature.
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn random() -> Self {
+        use std::hash::{BuildHasher, Hasher};
+
+        let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+        hasher.write_u64(FNV_OFFSET);
+        Self::new(hasher.finish())
+    }
+
+    /// Return the next token, `{prefix}-` followed by 12 hex digits.
+    ///
+    /// The result is a valid `id` and class name as long as `prefix`
+    /// contains no whitespace.
+    pub fn next_id(&mut self, prefix: &str) -> String {
+        let hash = fnv1a(
+            fnv1a(FNV_OFFSET, &self.seed.to_le_bytes()),
+            &self.counter.to_le_bytes(),
+        );
+        self.counter += 1;
+
+        let mut id = String::with_capacity(prefix.len() + 13);
+        id.push_str(prefix);
+        let _ = write!(id, "-{:012x}", hash & 0xFFFF_FFFF_FFFF);
+        crate::debug_check_id(&id);
+        id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_ids_are_deterministic_and_unique() {
+        let mut a = IdGenerator::new(7);
+        let mut b = IdGenerator::new(7);
+        let ids: Vec<String> = (0..100).map(|_| a.next_id("x")).collect();
+        for id in &ids {
+            assert_eq!(*id, b.next_id("x"));
+            assert_eq!(id.len(), "x-".len() + 12);
+        }
+        for (i, id) in ids.iter().enumerate() {
+            assert!(!ids[i + 1..].contains(id), "{id} repeated");
+        }
+
+        // Known value, so the sequence cannot change between releases.
+        assert_eq!(IdGenerator::new(0).next_id("id"), "id-1fb960ff6465");
+        assert_ne!(
+            IdGenerator::from_scope("a").next_id("id"),
+            IdGenerator::from_scope("b").next_id("id")
+        );
+    }
+
+    #[test]
+    fn test_at_caller_seeds_per_call_site() {
+        fn make() -> IdGenerator {
+            IdGenerator::at_caller()
+        }
+        assert_eq!(make(), make());
+        assert_ne!(make(), IdGenerator::at_caller());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_random_ids_are_valid() {
+        let id = IdGenerator::random().next_id("r");
+        assert!(id.starts_with("r-"));
+    }
+}
@@ -0,0 +1,209 @@
+//! `serde` serialization of element trees, for handing a server-rendered
+//! tree to a client-side runtime.
+//!
+//! Every tree type serializes the same way, through its [`TreeNode`] view:
+//!
+//! - an element becomes `{"tag": ..., "attrs": {...}, "children": [...]}`,
+//!   with attributes in insertion order and `"children": []` for void
+//!   elements, plus `"key": ...` after the tag if it has a key;
+//! - text becomes a plain string, unescaped;
+//! - raw HTML becomes `{"raw": ...}`, so the client can tell it apart from
+//!   text and insert it without escaping;
+//! - a list of root nodes ([`Html`](crate::Html),
+//!   [`Document`](crate::typed::Document)) becomes an array.
+
+use alloc::string::String;
+use serde::ser::{Serialize, SerializeMap, SerializeSeq, SerializeStruct, Serializer};
+
+use crate::{Element, Html, Node, NodeView, TreeNode};
+
+/// Serialize an element from its parts.
+pub fn serialize_element<S, N, A>(
+    serializer: S,
+    tag: &str,
+    attrs: &[(A, String)],
+    key: Option<&str>,
+    children: &[N],
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    N: TreeNode,
+    A: AsRef<str>,
+{
+    let mut element = serializer.serialize_struct("Element", 3 + usize::from(key.is_some()))?;
+    element.serialize_field("tag", tag)?;
+    if let Some(key) = key {
+        element.serialize_field("key", key)?;
+    }
+    element.serialize_field("attrs", &Attrs(attrs))?;
+    element.serialize_field("children", &Nodes(children))?;
+    element.end()
+}
+
+/// Serialize a node through its [`TreeNode`] view.
+pub fn serialize_node<S, N>(serializer: S, node: &N) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    N: TreeNode,
+{
+    match node.view() {
+        NodeView::Element {
+            tag,
+            attrs,
+            key,
+            children,
+            ..
+        } => serialize_element(serializer, tag, attrs, key, children),
+        NodeView::Text(text) => serializer.serialize_str(text),
+        NodeView::Raw(html) => {
+            let mut raw = serializer.serialize_struct("Raw", 1)?;
+            raw.serialize_field("raw", html)?;
+            raw.end()
+        }
+    }
+}
+
+/// Serialize a list of nodes as an array.
+pub fn serialize_nodes<S, N>(serializer: S, nodes: &[N]) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    N: TreeNode,
+{
+    Nodes(nodes).serialize(serializer)
+}
+
+/// Serialize `value` as a JSON string.
+pub fn to_json(value: &impl Serialize) -> String {
+    // Serializing a tree cannot fail: every map key is a string and every
+    // value is a string, struct, map, or array.
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+struct Attrs<'a, A>(&'a [(A, String)]);
+
+impl<A: AsRef<str>> Serialize for Attrs<'_, A> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (name, value) in self.0 {
+            map.serialize_entry(name.as_ref(), value)?;
+        }
+        map.end()
+    }
+}
+
+struct Nodes<'a, N>(&'a [N]);
+
+impl<N: TreeNode> Serialize for Nodes<'_, N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+        for node in self.0 {
+            seq.serialize_element(&NodeRef(node))?;
+        }
+        seq.end()
+    }
+}
+
+struct NodeRef<'a, N>(&'a N);
+
+impl<N: TreeNode> Serialize for NodeRef<'_, N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_node(serializer, self.0)
+    }
+}
+
+impl Serialize for Element {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_element(
+            serializer,
+            &self.tag,
+            &self.attrs,
+            self.key.as_deref(),
+            &self.children,
+        )
+    }
+}
+
+impl Serialize for Node {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_node(serializer, self)
+    }
+}
+
+impl Serialize for Html {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_nodes(serializer, &self.nodes)
+    }
+}
+
+impl Element {
+    /// Serialize this tree to JSON, for hydrating it on the client.
+    ///
+    /// Elements become `{"tag", "attrs", "children"}` objects, text becomes
+    /// a string, and raw HTML becomes `{"raw": ...}` so the client knows not
+    /// to escape it. Void elements have `"children": []`. Requires the
+    /// `serde` feature, which also implements [`serde::Serialize`] for the
+    /// tree types.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::Element;
+    ///
+    /// let div = Element::new("div")
+    ///     .class("note")
+    ///     .text("Hi ")
+    ///     .raw("<b>there</b>");
+    /// assert_eq!(
+    ///     div.to_json(),
+    ///     r#"{"tag":"div","attrs":{"class":"note"},"children":["Hi ",{"raw":"<b>there</b>"}]}"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        to_json(self)
+    }
+}
+
+impl Html {
+    /// Serialize the root nodes to a JSON array. See [`Element::to_json`].
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        to_json(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_json() {
+        let form = Element::new("form")
+            .attr("action", "/search?q=\"x\"")
+            .child("input", |i| i.attr("type", "text").bool_attr("required"))
+            .child("p", |p| p.text("a < b"));
+        assert_eq!(
+            form.to_json(),
+            concat!(
+                r#"{"tag":"form","attrs":{"action":"/search?q=\"x\""},"children":["#,
+                r#"{"tag":"input","attrs":{"type":"text","required":""},"children":[]},"#,
+                r#"{"tag":"p","attrs":{},"children":["a < b"]}]}"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_key_to_json() {
+        let item = Element::new("li").key(3).text("x");
+        assert_eq!(
+            item.to_json(),
+            r#"{"tag":"li","key":"3","attrs":{},"children":["x"]}"#
+        );
+    }
+
+    #[test]
+    fn test_html_to_json() {
+        let html = Html::new().raw("<!-- x -->").text("y");
+        assert_eq!(html.to_json(), r#"[{"raw":"<!-- x -->"},"y"]"#);
+    }
+}
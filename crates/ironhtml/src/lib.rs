@@ -8,6 +8,7 @@
 //! Enable it with `features = ["macros"]`:
 //!
 //! ```rust
+//! # #[cfg(feature = "macros")] {
 //! use ironhtml::html;
 //!
 //! let page = html! {
@@ -24,11 +25,13 @@
 //! };
 //!
 //! assert!(page.render().contains("<h1>Welcome</h1>"));
+//! # }
 //! ```
 //!
 //! The macro supports attributes, Rust expressions, loops, and conditionals:
 //!
 //! ```rust
+//! # #[cfg(feature = "macros")] {
 //! use ironhtml::html;
 //!
 //! let items = vec!["Apple", "Banana", "Cherry"];
@@ -50,6 +53,7 @@
 //! let html = nav.render();
 //! assert!(html.contains("<li>Apple</li>"));
 //! assert!(html.contains("Fruit List"));
+//! # }
 //! ```
 //!
 //! ## Typed API
@@ -115,6 +119,13 @@ extern crate std;
 
 extern crate alloc;
 
+#[cfg(feature = "typed")]
+pub mod datetime;
+pub mod id;
+#[cfg(feature = "serde")]
+mod json;
+#[cfg(feature = "pool")]
+pub mod pool;
 #[cfg(feature = "typed")]
 pub mod typed;
 
@@ -150,6 +161,36 @@ pub mod typed;
 /// let _ = html! { div { span { } } };       // Nested elements
 /// ```
 ///
+/// Sibling nodes need no separator, but one `,` or `;` after a node is
+/// accepted and ignored, so `ul { li { "a" }, li { "b" } }` also works.
+///
+/// Names are checked against the standard elements, and a misspelled one
+/// is reported with the closest match: for `diiv`, the error suggests
+/// `div`.
+///
+/// Custom elements (web components) are written `element("my-widget")`,
+/// followed by attributes and children as usual. The name is checked at
+/// compile time against the custom element naming rules, but the content
+/// model is not: a custom element accepts any children and can go in any
+/// element that accepts phrasing content. See
+/// [`CustomElement`](typed::CustomElement).
+///
+/// ```rust
+/// use ironhtml::html;
+///
+/// let card = html! {
+///     div {
+///         element("user-card").attr("user-id", "42") {
+///             span.attr("slot", "name") { "Ada" }
+///         }
+///     }
+/// };
+/// assert_eq!(
+///     card.render(),
+///     r#"<div><user-card user-id="42"><span slot="name">Ada</span></user-card></div>"#
+/// );
+/// ```
+///
 /// ## Attributes
 ///
 /// Attributes use method-call syntax with `.`:
@@ -163,6 +204,68 @@ pub mod typed;
 /// let _ = html! { a.href("/").target("_blank") };
 /// ```
 ///
+/// An attribute without a value, written `.required` or `.required()`,
+/// renders as just its name, as with [`typed::Element::flag`]. Use this for the boolean attributes, whose
+/// presence alone means true: `allowfullscreen`, `async`, `autofocus`,
+/// `autoplay`, `checked`, `controls`, `default`, `defer`, `disabled`,
+/// `formnovalidate`, `hidden`, `inert`, `ismap`, `itemscope`, `loop`,
+/// `multiple`, `muted`, `nomodule`, `novalidate`, `open`, `playsinline`,
+/// `readonly`, `required`, `reversed`, and `selected`. Other attributes
+/// are not checked, since some, such as `download`, also take no value:
+///
+/// ```rust
+/// use ironhtml::html;
+///
+/// let input = html! { input.type_("email").required().autofocus };
+/// assert_eq!(input.render(), r#"<input type="email" required autofocus />"#);
+/// ```
+///
+/// A string literal given to `.class` or `.id` is checked at compile time:
+/// an id must be one token without whitespace, and a class list must hold
+/// at least one class, with no class repeated. Values computed at runtime
+/// are not checked:
+///
+/// ```compile_fail
+/// use ironhtml::html;
+///
+/// let _ = html! { div.id("main content") };
+/// ```
+///
+/// Attribute names that are not Rust identifiers, such as those used by
+/// Alpine.js or Vue, can be given as a string literal with `.attr(name, value)`.
+/// The name is emitted verbatim; the value is escaped as usual:
+///
+/// ```rust
+/// use ironhtml::html;
+///
+/// let button = html! { button.attr("@click", "open = true") { "Open" } };
+/// assert_eq!(button.render(), r#"<button @click="open = true">Open</button>"#);
+/// ```
+///
+/// The name must be non-empty and must not contain whitespace, quotes, `>`,
+/// `/`, or `=`; anything else is a compile error:
+///
+/// ```compile_fail
+/// use ironhtml::html;
+///
+/// let _ = html! { div.attr("on click", "x") };
+/// ```
+///
+/// Inline event handlers use `.on(event, handler)`, with the event name
+/// written without the `on` prefix. The name is checked at compile time,
+/// so a typo such as `Click` or `onclick` is an error rather than a handler
+/// that never runs:
+///
+/// ```rust
+/// use ironhtml::html;
+///
+/// let form = html! { form.on(submit, "return check(this)") { button { "Go" } } };
+/// assert_eq!(
+///     form.render(),
+///     r#"<form onsubmit="return check(this)"><button>Go</button></form>"#
+/// );
+/// ```
+///
 /// ## Text Content
 ///
 /// String literals inside braces become text content:
@@ -179,7 +282,9 @@ pub mod typed;
 ///
 /// ## Rust Expressions
 ///
-/// Use `#` prefix to embed Rust expressions:
+/// Use `#` prefix to embed Rust expressions. In child position the value
+/// can be any [`Display`](core::fmt::Display) type, formatted and then
+/// escaped as text (see [`typed::Element::text_display`]):
 ///
 /// ```rust
 /// use ironhtml::html;
@@ -188,11 +293,97 @@ pub mod typed;
 /// let p = html! { p { "Hello, " #name "!" } };
 /// assert_eq!(p.render(), "<p>Hello, World!</p>");
 ///
+/// let (count, price) = (3, 9.5);
+/// let p = html! { p { #count " items at $" #price } };
+/// assert_eq!(p.render(), "<p>3 items at $9.5</p>");
+///
 /// let classes = "btn btn-primary";
 /// let btn = html! { button.class(#classes) { "Click" } };
 /// assert!(btn.render().contains("btn btn-primary"));
 /// ```
 ///
+/// Use `@raw` to insert a string as HTML without escaping it. As with
+/// [`typed::Element::raw_html`], the caller must make sure the markup is safe:
+///
+/// ```rust
+/// use ironhtml::html;
+///
+/// let trusted = "<em>already sanitized</em>";
+/// let untrusted = "<script>alert(1)</script>";
+/// let div = html! { div { @raw trusted " " #untrusted } };
+/// assert_eq!(
+///     div.render(),
+///     "<div><em>already sanitized</em> &lt;script&gt;alert(1)&lt;/script&gt;</div>"
+/// );
+/// ```
+///
+/// Spread a runtime list of name-value pairs into an element with `..name`,
+/// or `..(expression)` for anything other than a variable or path, anywhere
+/// in its attribute chain. See [`typed::Element::attrs`] for escaping and
+/// duplicate names:
+///
+/// ```rust
+/// use ironhtml::html;
+///
+/// let mut extra = vec![("data-role".to_string(), "menu".to_string())];
+/// extra.push(("aria-expanded".to_string(), "false".to_string()));
+/// let button = html! { button.class("top") ..extra { "Menu" } };
+/// assert_eq!(
+///     button.render(),
+///     r#"<button class="top" data-role="menu" aria-expanded="false">Menu</button>"#
+/// );
+/// ```
+///
+/// ## Computed Children
+///
+/// A `{ ... }` block in child position is evaluated as Rust code and its
+/// value is appended as a child element. The value must implement
+/// [`IntoChild`](typed::IntoChild) for the parent, so the content model is
+/// still checked:
+///
+/// ```rust
+/// use ironhtml::html;
+/// use ironhtml::typed::Element;
+/// use ironhtml_elements::{Small, Strong};
+///
+/// fn large() -> Element<Strong> { html! { strong { "large" } } }
+/// fn small() -> Element<Strong> { html! { strong.class("small") { "small" } } }
+///
+/// let big = true;
+/// let p = html! { p { "Size: " { if big { large() } else { small() } } } };
+/// assert_eq!(p.render(), "<p>Size: <strong>large</strong></p>");
+/// ```
+///
+/// A reference to a [`typed::Component`] can also be used, either as
+/// `{ &widget }` or as `#widget`. `#` picks an element or component over
+/// [`Display`](core::fmt::Display), so only other values become text.
+///
+/// A `Vec` of children, or a mapped iterator such as
+/// `{ items.iter().map(row) }`, adds each child in order, as an alternative
+/// to a `for` loop.
+///
+/// ## Fragments
+///
+/// `frag { ... }` builds sibling nodes with no wrapper element, as a
+/// [`Fragment`](typed::Fragment). Its children are checked as flow content
+/// (the children of a `<div>`); `frag<ul> { ... }` checks them against
+/// another parent. A fragment is added to a matching parent as a computed
+/// child:
+///
+/// ```rust
+/// use ironhtml::html;
+/// use ironhtml::typed::Fragment;
+/// use ironhtml_elements::Ul;
+///
+/// fn items() -> Fragment<Ul> {
+///     html! { frag<ul> { li { "One" } li { "Two" } } }
+/// }
+///
+/// assert_eq!(items().render(), "<li>One</li><li>Two</li>");
+/// let list = html! { ul { { items() } } };
+/// assert_eq!(list.render(), "<ul><li>One</li><li>Two</li></ul>");
+/// ```
+///
 /// ## Loops
 ///
 /// Use `for` to iterate:
@@ -213,6 +404,117 @@ pub mod typed;
 /// assert!(ul.render().contains("<li>Apple</li>"));
 /// ```
 ///
+/// The collection can be any expression, as in a Rust `for` loop; iterate
+/// `#&items` to borrow it and keep using it afterwards. Borrowed
+/// [`Render`] components can be inserted as computed children directly.
+///
+/// The loop body must be exactly one element. Give it a stable key for
+/// client-side diffing with `.key(expr)`. Keys are kept apart from the
+/// attributes: they appear in the `serde` output, and in the HTML only when
+/// rendered with [`RenderOptions::keys`]:
+///
+/// ```rust
+/// use ironhtml::html;
+/// use ironhtml::typed::Element;
+/// use ironhtml::RenderOptions;
+/// use ironhtml_elements::Li;
+///
+/// let rows = [(3, "Ada"), (5, "Grace")];
+/// let ul = html! {
+///     ul {
+///         for (id, name) in #rows {
+///             li.key(id) { #name }
+///         }
+///     }
+/// };
+/// assert_eq!(ul.render(), "<ul><li>Ada</li><li>Grace</li></ul>");
+/// assert_eq!(
+///     ul.render_with(&RenderOptions::new().keys(true)),
+///     r#"<ul><li data-ih-key="3">Ada</li><li data-ih-key="5">Grace</li></ul>"#
+/// );
+/// ```
+///
+/// For client hydration, add `use key = expr` to the loop instead: each
+/// item gets the key and always renders it as `data-ih-key`, as with
+/// [`typed::Element::hydration_key`]:
+///
+/// ```rust
+/// use ironhtml::html;
+/// use ironhtml::typed::Element;
+/// use ironhtml_elements::Li;
+///
+/// let rows = [(3, "Ada"), (5, "Grace")];
+/// let ul = html! {
+///     ul {
+///         for (id, name) in #rows use key = id {
+///             li { #name }
+///         }
+///     }
+/// };
+/// assert_eq!(
+///     ul.render(),
+///     r#"<ul><li data-ih-key="3">Ada</li><li data-ih-key="5">Grace</li></ul>"#
+/// );
+/// ```
+///
+/// An `else` block after the loop renders instead when the collection is
+/// empty. The collection is iterated only once:
+///
+/// ```rust
+/// use ironhtml::html;
+/// use ironhtml::typed::Element;
+/// use ironhtml_elements::Li;
+///
+/// let results: Vec<&str> = vec![];
+/// let ul = html! {
+///     ul {
+///         for r in #results {
+///             li { #r }
+///         } else {
+///             li.class("empty") { "No results" }
+///         }
+///     }
+/// };
+/// assert_eq!(ul.render(), r#"<ul><li class="empty">No results</li></ul>"#);
+/// ```
+///
+/// `while` and `while let` loops add their children on every iteration,
+/// and the body can use the variables the pattern binds:
+///
+/// ```rust
+/// use ironhtml::html;
+///
+/// let mut queue = vec!["b", "a"];
+/// let ul = html! {
+///     ul {
+///         while let Some(item) = #queue.pop() {
+///             li { #item }
+///         }
+///     }
+/// };
+/// assert_eq!(ul.render(), "<ul><li>a</li><li>b</li></ul>");
+/// ```
+///
+/// ## Let Bindings
+///
+/// A `let` statement inside a block computes a value once for the nodes
+/// after it in the same block:
+///
+/// ```rust
+/// use ironhtml::html;
+///
+/// let price_cents = 1999;
+/// let p = html! {
+///     p {
+///         let dollars = format!("${}.{:02}", price_cents / 100, price_cents % 100);
+///         let label = "Price: ";
+///         span { #label }
+///         strong { #dollars }
+///     }
+/// };
+/// assert_eq!(p.render(), "<p><span>Price: </span><strong>$19.99</strong></p>");
+/// ```
+///
 /// ## Conditionals
 ///
 /// Use `if` for conditional rendering:
@@ -230,29 +532,71 @@ pub mod typed;
 /// };
 /// assert!(div.render().contains("Visible"));
 /// ```
+///
+/// ## Match
+///
+/// Use `match` to render different markup per case. Each arm's body is a
+/// brace block of nodes, and arms may have `if` guards. The arms are passed
+/// to a real Rust `match`, so the compiler checks exhaustiveness:
+///
+/// ```rust
+/// use ironhtml::html;
+///
+/// enum Status {
+///     Ok,
+///     Err(String),
+/// }
+///
+/// let status = Status::Err("timeout".into());
+/// let div = html! {
+///     div {
+///         match #status {
+///             Status::Ok => { span.class("ok") { "ok" } }
+///             Status::Err(e) => { span.class("err") { "failed: " #e } }
+///         }
+///     }
+/// };
+/// assert_eq!(div.render(), r#"<div><span class="err">failed: timeout</span></div>"#);
+/// ```
 #[cfg(feature = "macros")]
 pub use ironhtml_macro::html;
 
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
+use core::fmt::Write;
 
 /// An HTML element with tag, attributes, and children.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Element {
     tag: String,
     attrs: Vec<(String, String)>,
     children: Vec<Node>,
     self_closing: bool,
+    key: Option<String>,
 }
 
 /// A node in the HTML tree - either an element or text.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Node {
     Element(Element),
     Text(String),
     Raw(String),
 }
 
+/// Append `text`, merging it into the last node if that is also text.
+///
+/// A `Display` value is formatted straight into the existing node. Escaping
+/// works character by character, so one merged node renders exactly like
+/// the separate ones. Raw nodes are never merged.
+pub(crate) fn push_text<N: TreeNode>(nodes: &mut Vec<N>, text: impl core::fmt::Display) {
+    use core::fmt::Write as _;
+    if let Some(last) = nodes.last_mut().and_then(TreeNode::text_mut) {
+        let _ = write!(last, "{text}");
+    } else {
+        nodes.push(N::from_text(text.to_string()));
+    }
+}
+
 /// HTML builder for constructing HTML documents.
 #[derive(Debug, Clone, Default)]
 pub struct Html {
@@ -285,56 +629,179 @@ impl Element {
             attrs: Vec::new(),
             children: Vec::new(),
             self_closing,
+            key: None,
         }
     }
 
     /// Add an attribute to this element.
+    ///
+    /// `class`, `rel`, `headers`, and `aria-describedby` hold token lists:
+    /// setting one again appends the new tokens to the existing attribute,
+    /// in order, skipping duplicates.
     #[must_use]
     pub fn attr(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
-        self.attrs.push((name.into(), value.into()));
+        self.set_attr(name, value);
         self
     }
 
-    /// Add a boolean attribute (no value, e.g., `disabled`, `checked`).
+    /// Add a boolean attribute (no value, e.g., `disabled`, `checked`),
+    /// rendered as just its name: `<input required />`.
     #[must_use]
-    pub fn bool_attr(mut self, name: impl Into<String>) -> Self {
+    pub fn flag(mut self, name: impl Into<String>) -> Self {
         self.attrs.push((name.into(), String::new()));
         self
     }
 
-    /// Add a class attribute. If class already exists, appends to it.
+    /// Add a boolean attribute. Same as [`flag`](Self::flag).
+    #[must_use]
+    pub fn bool_attr(self, name: impl Into<String>) -> Self {
+        self.flag(name)
+    }
+
+    /// Set the key that identifies this element among its siblings, for
+    /// client code that diffs or hydrates the tree.
+    ///
+    /// The key is not an attribute: it is left out of the HTML unless
+    /// rendered with [`RenderOptions::keys`], and serialized as `"key"`
+    /// with the `serde` feature. Setting it again replaces it.
+    #[must_use]
+    pub fn key(mut self, key: impl core::fmt::Display) -> Self {
+        self.key = Some(key.to_string());
+        self
+    }
+
+    /// Add a class attribute. If class already exists, appends to it,
+    /// skipping classes already present.
+    ///
+    /// `class` may hold several space-separated classes; use
+    /// [`class_token`](Self::class_token) to insist on exactly one.
     #[must_use]
     pub fn class(mut self, class: impl Into<String>) -> Self {
+        self.set_class(class);
+        self
+    }
+
+    /// Add a single class.
+    ///
+    /// In debug builds, panics if `class` contains whitespace, which
+    /// usually means several classes were passed by mistake; use
+    /// [`classes`](Self::classes) to add several at once.
+    #[must_use]
+    pub fn class_token(self, class: impl Into<String>) -> Self {
         let class = class.into();
-        if let Some(pos) = self.attrs.iter().position(|(k, _)| k == "class") {
-            self.attrs[pos].1.push(' ');
-            self.attrs[pos].1.push_str(&class);
-        } else {
-            self.attrs.push(("class".to_string(), class));
+        debug_check_class(&class);
+        self.class(class)
+    }
+
+    /// Add several classes, one token per item.
+    ///
+    /// In debug builds, panics if an item contains whitespace.
+    #[must_use]
+    pub fn classes<I>(mut self, classes: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        for class in classes {
+            let class = class.as_ref();
+            debug_check_class(class);
+            self = self.class(class);
         }
         self
     }
 
+    /// Add a class only if `condition` is true.
+    ///
+    /// Like [`class`](Self::class), a class already present is not added
+    /// again, and skipped classes leave no extra whitespace.
+    #[must_use]
+    pub fn class_if(self, condition: bool, class: impl Into<String>) -> Self {
+        if condition {
+            self.class(class)
+        } else {
+            self
+        }
+    }
+
     /// Add an id attribute.
+    ///
+    /// In debug builds, panics if `id` contains whitespace, which HTML
+    /// forbids.
     #[must_use]
-    pub fn id(self, id: impl Into<String>) -> Self {
-        self.attr("id", id)
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.set_id(id);
+        self
+    }
+
+    /// Add an attribute in place. The `&mut` counterpart of [`attr`](Self::attr),
+    /// for setting attributes in a loop without rebinding.
+    pub fn set_attr(&mut self, name: impl Into<String>, value: impl Into<String>) -> &mut Self {
+        let name = name.into();
+        let value = value.into();
+        if !is_token_list_attr(&name) {
+            self.attrs.push((name, value));
+        } else if let Some(pos) = self.attrs.iter().position(|(k, _)| *k == name) {
+            merge_tokens(&mut self.attrs[pos].1, &value);
+        } else {
+            let mut tokens = String::new();
+            merge_tokens(&mut tokens, &value);
+            self.attrs.push((name, tokens));
+        }
+        self
+    }
+
+    /// Add a class in place. The `&mut` counterpart of [`class`](Self::class).
+    pub fn set_class(&mut self, class: impl Into<String>) -> &mut Self {
+        self.set_attr("class", class.into())
+    }
+
+    /// Add an id in place. The `&mut` counterpart of [`id`](Self::id).
+    pub fn set_id(&mut self, id: impl Into<String>) -> &mut Self {
+        let id = id.into();
+        debug_check_id(&id);
+        self.set_attr("id", id)
     }
 
     /// Add a text child node.
+    ///
+    /// Text added right after other text joins the same node, so the tree
+    /// stays small when text is built up piece by piece.
+    ///
+    /// The text is escaped when rendered, except inside `<script>` and
+    /// `<style>`: their content is raw text that the browser does not
+    /// decode, so it is written as given.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, rendering panics if the text of a `<script>` or
+    /// `<style>` contains `</script` or `</style`, which would end the
+    /// element early; escape it in the script or stylesheet itself, such as
+    /// `<\/script` in a JavaScript string. Release builds write it as
+    /// `<\/script`.
     #[must_use]
     pub fn text(mut self, content: impl Into<String>) -> Self {
-        self.children.push(Node::Text(content.into()));
+        push_text(&mut self.children, content.into());
         self
     }
 
-    /// Add raw HTML (not escaped).
+    /// Add raw HTML, written to the output exactly as given.
+    ///
+    /// Nothing is escaped, so sanitizing the content is the caller's
+    /// responsibility; never pass user input. See
+    /// [`typed::Element::raw_html`](crate::typed::Element::raw_html).
     #[must_use]
-    pub fn raw(mut self, html: impl Into<String>) -> Self {
+    #[doc(alias = "PreEscaped")]
+    pub fn raw_html(mut self, html: impl Into<String>) -> Self {
         self.children.push(Node::Raw(html.into()));
         self
     }
 
+    /// Add raw HTML. Same as [`raw_html`](Self::raw_html).
+    #[must_use]
+    pub fn raw(self, html: impl Into<String>) -> Self {
+        self.raw_html(html)
+    }
+
     /// Add a child element using a builder function.
     #[must_use]
     pub fn child<F>(mut self, tag: impl Into<String>, f: F) -> Self
@@ -369,6 +836,31 @@ impl Element {
         self
     }
 
+    /// Append every node produced by an iterator, in order.
+    ///
+    /// Items can be [`Element`]s, [`Node`]s, or strings (added as text), so
+    /// a list built with iterator adapters goes in with one call.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::Element;
+    ///
+    /// let users = ["Ada", "Grace"];
+    /// let list = Element::new("ul")
+    ///     .extend_children(users.iter().map(|name| Element::new("li").text(*name)));
+    /// assert_eq!(list.render(), "<ul><li>Ada</li><li>Grace</li></ul>");
+    /// ```
+    #[must_use]
+    pub fn extend_children<I>(mut self, children: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Node>,
+    {
+        self.extend(children);
+        self
+    }
+
     /// Conditionally add content.
     #[must_use]
     pub fn when<F>(self, condition: bool, f: F) -> Self
@@ -396,28 +888,166 @@ impl Element {
         }
     }
 
+    /// A hash of this element's structure: tag, attributes, and children.
+    ///
+    /// Equal trees hash equal, in every process, so the hash can key a
+    /// cache of rendered fragments. It covers the tree, not the rendered
+    /// string: `.text("ab")` and `.text("a").raw("b")` render the same but
+    /// hash differently.
+    #[must_use]
+    pub fn content_hash(&self) -> u64 {
+        content_hash_of(self)
+    }
+
+    /// The number of nodes in this tree: this element, its descendant
+    /// elements, and their text and raw HTML nodes.
+    ///
+    /// Adjacent text merges into one node, so `.text("a").text("b")`
+    /// counts once.
+    #[must_use]
+    pub fn node_count(&self) -> usize {
+        1 + count_nodes(&self.children)
+    }
+
+    /// The number of nodes on the longest path from this element down to
+    /// a leaf, counting both ends: an empty element has depth 1, and
+    /// `<p>text</p>` has depth 2.
+    #[must_use]
+    pub fn max_depth(&self) -> usize {
+        1 + nesting_depth(&self.children)
+    }
+
+    /// A rough size in bytes of the rendered HTML, computed in one pass
+    /// over the tree.
+    ///
+    /// [`render`](Self::render) uses it to allocate its string once instead
+    /// of growing it as it goes. It is only a hint: escaped text and
+    /// pretty-printing can make the output longer.
+    #[must_use]
+    pub fn estimated_size(&self) -> usize {
+        estimated_size(&self.tag, &self.attrs, &self.children)
+    }
+
     /// Render this element to a string.
+    ///
+    /// With the `validate-ids` feature, debug builds panic if two elements
+    /// in the tree have the same `id`, naming the id. Release builds never
+    /// check.
     #[must_use]
     pub fn render(&self) -> String {
-        let mut output = String::new();
+        let mut output = String::with_capacity(self.estimated_size());
         self.render_to(&mut output);
         output
     }
 
+    /// Render this element to a string using the given options.
+    #[must_use]
+    pub fn render_with(&self, options: &RenderOptions) -> String {
+        let mut output = String::with_capacity(self.estimated_size());
+        self.render_to_with(&mut output, options);
+        output
+    }
+
     /// Render this element to an existing string buffer.
     pub fn render_to(&self, output: &mut String) {
+        self.render_to_with(output, &RenderOptions::default());
+    }
+
+    /// Render this element with block-level children indented on their own
+    /// lines. See [`RenderOptions::pretty`].
+    #[must_use]
+    pub fn render_pretty(&self) -> String {
+        self.render_with(&RenderOptions::new().pretty(true))
+    }
+
+    /// Pretty-print this element for debugging: the output of
+    /// [`render_pretty`](Self::render_pretty), with a
+    /// `<!-- WARN: ... -->` line before each line holding an element with a
+    /// likely mistake: an `<img>` without `alt`, or an `id` already used
+    /// earlier in the tree.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::Element;
+    ///
+    /// let div = Element::new("div")
+    ///     .child("p", |p| p.id("intro"))
+    ///     .child("p", |p| p.id("intro"));
+    /// assert_eq!(
+    ///     div.render_debug_annotated(),
+    ///     "<div>\n  <p id=\"intro\"></p>\n  <!-- WARN: duplicate id 'intro' on <p> -->\n  <p id=\"intro\"></p>\n</div>"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn render_debug_annotated(&self) -> String {
+        let mut output = String::new();
+        render_pretty_element_to(
+            &mut output,
+            &RenderOptions::new().pretty(true),
+            &self.tag,
+            self.self_closing,
+            &self.attrs,
+            self.key.as_deref(),
+            &self.children,
+            0,
+            Some(&mut DebugAnnotations::default()),
+        );
+        output
+    }
+
+    /// Render this element as well-formed XHTML. See [`Syntax::Xhtml`].
+    #[must_use]
+    pub fn render_xhtml(&self) -> String {
+        self.render_with(&RenderOptions::new().syntax(Syntax::Xhtml))
+    }
+
+    /// Render this element to an existing string buffer using the given options.
+    pub fn render_to_with(&self, output: &mut String, options: &RenderOptions) {
+        validate_ids(&self.attrs, &self.children);
+        if options.pretty {
+            render_pretty_element_to(
+                output,
+                options,
+                &self.tag,
+                self.self_closing,
+                &self.attrs,
+                self.key.as_deref(),
+                &self.children,
+                0,
+                None,
+            );
+            return;
+        }
+        let _ = self.write_with(output, options);
+    }
+
+    /// Write this element to `sink` tag by tag, without first rendering
+    /// the whole tree to a string. Pretty rendering needs to measure lines,
+    /// so it still goes through a string.
+    fn write_with<W: Write + ?Sized>(
+        &self,
+        sink: &mut W,
+        options: &RenderOptions,
+    ) -> core::fmt::Result {
+        if options.pretty {
+            return sink.write_str(&self.render_with(options));
+        }
         render_element_to(
-            output,
+            sink,
+            options,
             &self.tag,
             self.self_closing,
             &self.attrs,
-            |out| {
-                for child in &self.children {
-                    child.render_to(out);
-                }
+            self.key.as_deref(),
+            |sink| {
+                self.children.iter().try_for_each(|child| match child {
+                    Node::Text(text) => write_text_to(sink, &self.tag, text, options),
+                    _ => child.write_with(sink, options),
+                })
             },
             !self.children.is_empty(),
-        );
+        )
     }
 }
 
@@ -432,25 +1062,89 @@ impl Node {
 
     /// Render this node to an existing string buffer.
     pub fn render_to(&self, output: &mut String) {
+        self.render_to_with(output, &RenderOptions::default());
+    }
+
+    /// Render this node to an existing string buffer using the given options.
+    pub fn render_to_with(&self, output: &mut String, options: &RenderOptions) {
+        match self {
+            Self::Element(elem) => elem.render_to_with(output, options),
+            _ => {
+                let _ = self.write_with(output, options);
+            }
+        }
+    }
+
+    fn write_with<W: Write + ?Sized>(
+        &self,
+        sink: &mut W,
+        options: &RenderOptions,
+    ) -> core::fmt::Result {
         match self {
-            Self::Element(elem) => elem.render_to(output),
-            Self::Text(text) => output.push_str(&escape_html(text)),
-            Self::Raw(html) => output.push_str(html),
+            Self::Element(elem) => elem.write_with(sink, options),
+            Self::Text(text) => escape_to(sink, text, false, options),
+            Self::Raw(html) => sink.write_str(html),
         }
     }
 }
 
-impl Html {
-    /// Create a new empty HTML builder.
-    #[must_use]
-    pub const fn new() -> Self {
-        Self { nodes: Vec::new() }
+impl From<Element> for Node {
+    fn from(element: Element) -> Self {
+        Self::Element(element)
     }
+}
 
-    /// Add a root element using a builder function.
-    #[must_use]
-    pub fn elem<F>(mut self, tag: impl Into<String>, f: F) -> Self
-    where
+impl From<String> for Node {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl From<&str> for Node {
+    fn from(text: &str) -> Self {
+        Self::Text(text.into())
+    }
+}
+
+/// Appends each node in order, merging adjacent text as
+/// [`Element::text`] does.
+impl<N: Into<Node>> Extend<N> for Element {
+    fn extend<I: IntoIterator<Item = N>>(&mut self, iter: I) {
+        for node in iter {
+            match node.into() {
+                Node::Text(text) => push_text(&mut self.children, text),
+                node => self.children.push(node),
+            }
+        }
+    }
+}
+
+/// Collects root nodes in order, so `iter.map(...).collect::<Html>()`
+/// builds a document from a list.
+impl<N: Into<Node>> FromIterator<N> for Html {
+    fn from_iter<I: IntoIterator<Item = N>>(iter: I) -> Self {
+        let mut html = Self::new();
+        for node in iter {
+            match node.into() {
+                Node::Text(text) => push_text(&mut html.nodes, text),
+                node => html.nodes.push(node),
+            }
+        }
+        html
+    }
+}
+
+impl Html {
+    /// Create a new empty HTML builder.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { nodes: Vec::new() }
+    }
+
+    /// Add a root element using a builder function.
+    #[must_use]
+    pub fn elem<F>(mut self, tag: impl Into<String>, f: F) -> Self
+    where
         F: FnOnce(Element) -> Element,
     {
         let elem = f(Element::new(tag));
@@ -461,7 +1155,7 @@ impl Html {
     /// Add a text node at the root level.
     #[must_use]
     pub fn text(mut self, content: impl Into<String>) -> Self {
-        self.nodes.push(Node::Text(content.into()));
+        push_text(&mut self.nodes, content.into());
         self
     }
 
@@ -475,14 +1169,645 @@ impl Html {
     /// Build the final HTML string.
     #[must_use]
     pub fn build(&self) -> String {
+        self.build_with(&RenderOptions::default())
+    }
+
+    /// Build the final HTML string using the given options.
+    #[must_use]
+    pub fn build_with(&self, options: &RenderOptions) -> String {
         let mut output = String::new();
-        for node in &self.nodes {
-            node.render_to(&mut output);
+        let _ = self.write_with(&mut output, options);
+        output
+    }
+
+    /// Write the root nodes to `sink`, each on its own line when pretty.
+    /// `build_with` and `Display` both go through here, so `{:#}` matches
+    /// [`Html::render_pretty`].
+    fn write_with<W: Write + ?Sized>(
+        &self,
+        sink: &mut W,
+        options: &RenderOptions,
+    ) -> core::fmt::Result {
+        validate_ids::<_, String>(&[], &self.nodes);
+        for (i, node) in self.nodes.iter().enumerate() {
+            if options.pretty && i > 0 {
+                sink.write_char('\n')?;
+            }
+            node.write_with(sink, options)?;
         }
+        Ok(())
+    }
+
+    /// Build the HTML with block-level elements indented on their own
+    /// lines, and each root node on its own line. See
+    /// [`RenderOptions::pretty`].
+    #[must_use]
+    pub fn render_pretty(&self) -> String {
+        self.build_with(&RenderOptions::new().pretty(true))
+    }
+
+    /// Pretty-print the root nodes for debugging, annotating likely
+    /// mistakes. See [`Element::render_debug_annotated`].
+    #[must_use]
+    pub fn render_debug_annotated(&self) -> String {
+        let mut output = String::new();
+        render_debug_annotated_to(&mut output, &self.nodes);
         output
     }
 }
 
+/// A value that renders itself as HTML.
+///
+/// This is the extension point for components: implement it for your own
+/// types, mark them with [`typed::Component`], and embed them in a typed
+/// tree with [`typed::Element::append_child`] or a computed child
+/// (`{ &widget }`) in [`html!`]. The output is inserted as-is, so the
+/// implementation is responsible for escaping its own text with
+/// [`escape_html`] and [`escape_attr`], and it is not checked against the
+/// parent's content model.
+///
+/// ## Example
+///
+/// ```rust
+/// use core::fmt::{self, Write};
+/// use ironhtml::{escape_html, Render};
+///
+/// struct Badge<'a>(&'a str);
+///
+/// impl Render for Badge<'_> {
+///     fn render_into(&self, sink: &mut impl Write) -> fmt::Result {
+///         write!(sink, r#"<span class="badge">{}</span>"#, escape_html(self.0))
+///     }
+/// }
+///
+/// let mut html = String::new();
+/// Badge("New & hot").render_into(&mut html).unwrap();
+/// assert_eq!(html, r#"<span class="badge">New &amp; hot</span>"#);
+/// ```
+pub trait Render {
+    /// Write this value's HTML to `sink`.
+    ///
+    /// The crate's own types write tag by tag as they walk the tree, so a
+    /// large page never exists as one string in memory, and the output is
+    /// identical to [`Element::render`]. Rendering stops at the first
+    /// error. This works without `std`; see
+    /// [`render_to_io`](Self::render_to_io) for sockets and files.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to `sink` fails. Writing to a `String`
+    /// never fails.
+    #[doc(alias = "render_to_fmt")]
+    fn render_into(&self, sink: &mut impl Write) -> core::fmt::Result;
+
+    /// Append this value's HTML to `buf`.
+    ///
+    /// The default goes through [`render_into`](Self::render_into). The
+    /// crate's own types override it to write straight into `buf` without
+    /// an intermediate allocation.
+    fn render_to_buffer(&self, buf: &mut String) {
+        let _ = self.render_into(buf);
+    }
+
+    /// Write this value's HTML to an [`std::io::Write`] sink, such as a file
+    /// or socket.
+    ///
+    /// Output streams through [`render_into`](Self::render_into) in many
+    /// small writes, so wrap an unbuffered writer in a
+    /// [`BufWriter`](std::io::BufWriter). Requires the `std` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first IO error reported by `writer`, unchanged, so its
+    /// [`kind`](std::io::Error::kind) can be inspected. An error from
+    /// [`render_into`](Self::render_into) that did not come from the writer
+    /// is reported as [`std::io::ErrorKind::Other`].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::{Element, Render};
+    ///
+    /// let mut out = Vec::new();
+    /// Element::new("p").text("Hi").render_to_io(&mut out).unwrap();
+    /// assert_eq!(out, b"<p>Hi</p>");
+    /// ```
+    #[cfg(feature = "std")]
+    #[doc(alias = "render_to_writer")]
+    fn render_to_io<W: std::io::Write + ?Sized>(&self, writer: &mut W) -> std::io::Result<()> {
+        let mut adapter = IoAdapter {
+            writer,
+            error: None,
+        };
+        match self.render_into(&mut adapter) {
+            Ok(()) => Ok(()),
+            Err(core::fmt::Error) => Err(adapter
+                .error
+                .unwrap_or_else(|| std::io::Error::other("formatter error"))),
+        }
+    }
+
+    /// Render into a reusable buffer from a thread-local pool.
+    ///
+    /// The buffer returns to the pool when the [`PooledString`](pool::PooledString)
+    /// is dropped. Requires the `pool` feature; see the [`pool`] module.
+    #[cfg(feature = "pool")]
+    fn render_pooled(&self) -> pool::PooledString {
+        pool::PooledString::render(self)
+    }
+}
+
+/// Bridges `fmt::Write` to `io::Write`, keeping the IO error that
+/// `fmt::Error` cannot carry.
+#[cfg(feature = "std")]
+struct IoAdapter<'a, W: std::io::Write + ?Sized> {
+    writer: &'a mut W,
+    error: Option<std::io::Error>,
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write + ?Sized> Write for IoAdapter<'_, W> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.writer.write_all(s.as_bytes()).map_err(|e| {
+            self.error = Some(e);
+            core::fmt::Error
+        })
+    }
+}
+
+/// A reference renders like the value it points to, so borrowed components
+/// (including `&&T` from iterating a slice of references) can be passed
+/// wherever a `Render` value is expected, without cloning.
+impl<T: Render + ?Sized> Render for &T {
+    fn render_into(&self, sink: &mut impl Write) -> core::fmt::Result {
+        (**self).render_into(sink)
+    }
+
+    fn render_to_buffer(&self, buf: &mut String) {
+        (**self).render_to_buffer(buf);
+    }
+}
+
+/// A reusable output buffer for rendering many documents.
+///
+/// Each [`render_reset`](Self::render_reset) call clears the buffer and
+/// renders into it, keeping the capacity from earlier renders, so a
+/// long-lived `Renderer` stops allocating once it has grown to fit the
+/// largest output. This suits a single-threaded handler that renders one
+/// response at a time.
+///
+/// The returned `&str` borrows the buffer, so it must be used (written to
+/// the socket, copied) before the next `render_reset` call; the borrow
+/// checker rejects holding it across one.
+///
+/// ## Example
+///
+/// ```rust
+/// use ironhtml::{Element, Renderer};
+///
+/// let mut renderer = Renderer::new();
+/// for name in ["Ada", "Grace"] {
+///     let html = renderer.render_reset(&Element::new("p").text(name));
+///     assert!(html.starts_with("<p>"));
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Renderer {
+    buf: String,
+}
+
+impl Renderer {
+    /// Create a renderer with an empty buffer.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { buf: String::new() }
+    }
+
+    /// Create a renderer whose buffer can hold `capacity` bytes before
+    /// growing.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            buf: String::with_capacity(capacity),
+        }
+    }
+
+    /// Clear the buffer, render `value` into it, and return the result.
+    ///
+    /// The returned string is valid until the next call on this renderer.
+    pub fn render_reset(&mut self, value: &(impl Render + ?Sized)) -> &str {
+        self.buf.clear();
+        value.render_to_buffer(&mut self.buf);
+        &self.buf
+    }
+
+    /// The buffer's current capacity in bytes.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+}
+
+impl Render for Element {
+    fn render_into(&self, sink: &mut impl Write) -> core::fmt::Result {
+        validate_ids(&self.attrs, &self.children);
+        self.write_with(sink, &RenderOptions::default())
+    }
+
+    fn render_to_buffer(&self, buf: &mut String) {
+        self.render_to(buf);
+    }
+}
+
+impl Render for Node {
+    fn render_into(&self, sink: &mut impl Write) -> core::fmt::Result {
+        self.write_with(sink, &RenderOptions::default())
+    }
+
+    fn render_to_buffer(&self, buf: &mut String) {
+        self.render_to(buf);
+    }
+}
+
+/// Formats as the rendered HTML, escaped exactly as by
+/// [`Element::render`], so elements work with `format!`, `write!`, and any
+/// API that takes `impl Display`. The alternate flag (`{:#}`) renders
+/// pretty, as [`Element::render_pretty`] does.
+///
+/// ## Example
+///
+/// ```rust
+/// use ironhtml::Element;
+///
+/// let p = Element::new("p").text("Fish & chips");
+/// assert_eq!(format!("{p}"), "<p>Fish &amp; chips</p>");
+/// ```
+impl core::fmt::Display for Element {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        validate_ids(&self.attrs, &self.children);
+        self.write_with(f, &RenderOptions::new().pretty(f.alternate()))
+    }
+}
+
+/// Formats as the rendered HTML; see the `Display` impl of [`Element`].
+impl core::fmt::Display for Node {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.write_with(f, &RenderOptions::new().pretty(f.alternate()))
+    }
+}
+
+/// Formats as the rendered HTML; see the `Display` impl of [`Element`].
+impl core::fmt::Display for Html {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.write_with(f, &RenderOptions::new().pretty(f.alternate()))
+    }
+}
+
+impl Render for Html {
+    fn render_into(&self, sink: &mut impl Write) -> core::fmt::Result {
+        self.write_with(sink, &RenderOptions::default())
+    }
+
+    fn render_to_buffer(&self, buf: &mut String) {
+        for node in &self.nodes {
+            node.render_to(buf);
+        }
+    }
+}
+
+/// Strategy for escaping text content and attribute values.
+///
+/// Markup-significant characters are always escaped; the policy only
+/// controls what happens to the remaining characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EscapePolicy {
+    /// Escape only `&`, `<`, `>`, and — in attribute values — `"` and `'`.
+    /// Everything else is emitted as UTF-8.
+    #[default]
+    Minimal,
+    /// Escape as [`Minimal`](Self::Minimal) does, and additionally emit every
+    /// non-ASCII character as a hexadecimal character reference (`é` becomes
+    /// `&#xE9;`). Useful for consumers that cannot handle UTF-8 input.
+    AsciiEntities,
+}
+
+/// What to do with control characters found in text and attribute values.
+///
+/// HTML forbids U+0000 and most other control characters in text and
+/// attribute values; a parser either drops them or replaces them with
+/// U+FFFD, and some downstream sinks truncate at the first NUL. Tab, line
+/// feed, form feed, and carriage return are whitespace, not disallowed
+/// controls, and are always kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ControlChars {
+    /// Emit control characters unchanged.
+    #[default]
+    Keep,
+    /// Drop disallowed control characters from the output.
+    Strip,
+    /// Replace each disallowed control character with U+FFFD, as the
+    /// HTML parser does for NUL.
+    Replace,
+}
+
+/// The markup syntax an element tree is serialized in.
+///
+/// Void elements are closed with ` />` and non-void elements always get an
+/// end tag in both syntaxes, so the only difference is how attributes with
+/// an empty value are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Syntax {
+    /// HTML syntax: an attribute with an empty value is written as its
+    /// bare name, as in `<input disabled />`.
+    #[default]
+    Html,
+    /// XHTML syntax, well-formed XML: every attribute has a quoted value,
+    /// as in `<input disabled="" />`.
+    ///
+    /// Raw HTML added with `raw()` is written as-is and must already be
+    /// well-formed.
+    Xhtml,
+}
+
+/// Options controlling how an element tree is serialized.
+///
+/// The default options produce the same output as `render()`.
+///
+/// ## Example
+///
+/// ```rust
+/// use ironhtml::{Element, EscapePolicy, RenderOptions};
+///
+/// let options = RenderOptions::new().escape(EscapePolicy::AsciiEntities);
+/// let html = Element::new("p").text("café").render_with(&options);
+/// assert_eq!(html, "<p>caf&#xE9;</p>");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RenderOptions {
+    /// How text and attribute values are escaped.
+    pub escape: EscapePolicy,
+    /// How disallowed control characters in text and attribute values are
+    /// handled.
+    pub control_chars: ControlChars,
+    /// Indent block-level children onto their own lines for reading.
+    ///
+    /// Elements containing text or inline elements stay on one line, so
+    /// no whitespace is added where it would change how the page renders.
+    pub pretty: bool,
+    /// In pretty mode, the longest a line holding an opening tag may be
+    /// before its attributes are moved onto their own indented lines.
+    /// `None` (the default) never wraps.
+    pub max_line_width: Option<usize>,
+    /// Render each element's attributes sorted by name instead of in the
+    /// order they were added, for a canonical form that diffs cleanly.
+    ///
+    /// Each attribute keeps its value, including the whole accumulated
+    /// `class` list; attributes with the same name keep their relative
+    /// order.
+    pub sort_attributes: bool,
+    /// Write HTML or XHTML syntax.
+    pub syntax: Syntax,
+    /// Render each element's key as a `data-ih-key` attribute, after its
+    /// other attributes. Keys are otherwise left out of the HTML.
+    pub keys: bool,
+}
+
+impl RenderOptions {
+    /// Create the default render options.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            escape: EscapePolicy::Minimal,
+            control_chars: ControlChars::Keep,
+            pretty: false,
+            max_line_width: None,
+            sort_attributes: false,
+            syntax: Syntax::Html,
+            keys: false,
+        }
+    }
+
+    /// Set the escaping policy.
+    #[must_use]
+    pub const fn escape(mut self, policy: EscapePolicy) -> Self {
+        self.escape = policy;
+        self
+    }
+
+    /// Set how disallowed control characters are handled. They are kept by
+    /// default.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::{ControlChars, Element, RenderOptions};
+    ///
+    /// let p = Element::new("p").text("a\0b");
+    /// assert_eq!(p.render(), "<p>a\0b</p>");
+    /// assert_eq!(
+    ///     p.render_with(&RenderOptions::new().control_chars(ControlChars::Strip)),
+    ///     "<p>ab</p>"
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn control_chars(mut self, handling: ControlChars) -> Self {
+        self.control_chars = handling;
+        self
+    }
+
+    /// Set whether to pretty-print.
+    #[must_use]
+    pub const fn pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+
+    /// Set the line width past which pretty mode wraps attributes.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::{Element, RenderOptions};
+    ///
+    /// let options = RenderOptions::new().pretty(true).max_line_width(20);
+    /// let link = Element::new("a").attr("href", "/docs").attr("title", "Docs").text("Docs");
+    /// assert_eq!(
+    ///     link.render_with(&options),
+    ///     "<a\n  href=\"/docs\"\n  title=\"Docs\"\n>Docs</a>"
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn max_line_width(mut self, width: usize) -> Self {
+        self.max_line_width = Some(width);
+        self
+    }
+
+    /// Set whether to sort attributes by name.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::{Element, RenderOptions};
+    ///
+    /// let link = Element::new("a").attr("href", "/").class("nav").attr("aria-current", "page");
+    /// assert_eq!(
+    ///     link.render_with(&RenderOptions::new().sort_attributes(true)),
+    ///     r#"<a aria-current="page" class="nav" href="/"></a>"#
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn sort_attributes(mut self, sort: bool) -> Self {
+        self.sort_attributes = sort;
+        self
+    }
+
+    /// Set the markup syntax.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::{Element, RenderOptions, Syntax};
+    ///
+    /// let input = Element::new("input").bool_attr("checked");
+    /// assert_eq!(input.render(), "<input checked />");
+    /// assert_eq!(
+    ///     input.render_with(&RenderOptions::new().syntax(Syntax::Xhtml)),
+    ///     r#"<input checked="" />"#
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn syntax(mut self, syntax: Syntax) -> Self {
+        self.syntax = syntax;
+        self
+    }
+
+    /// Set whether to render element keys as `data-ih-key` attributes,
+    /// for client code that matches the rendered elements to their data.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::{Element, RenderOptions};
+    ///
+    /// let item = Element::new("li").key(7).text("Ada");
+    /// assert_eq!(item.render(), "<li>Ada</li>");
+    /// assert_eq!(
+    ///     item.render_with(&RenderOptions::new().keys(true)),
+    ///     r#"<li data-ih-key="7">Ada</li>"#
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn keys(mut self, keys: bool) -> Self {
+        self.keys = keys;
+        self
+    }
+}
+
+/// 64-bit FNV-1a, a small deterministic hasher.
+///
+/// Unlike `std`'s randomly seeded hasher it gives the same value in every
+/// process, so content hashes can key a shared or persistent cache.
+struct Fnv1a(u64);
+
+pub(crate) const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// Feed `bytes` into the FNV-1a state `hash`.
+pub(crate) const fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    let mut i = 0;
+    while i < bytes.len() {
+        hash ^= bytes[i] as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+        i += 1;
+    }
+    hash
+}
+
+impl core::hash::Hasher for Fnv1a {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.0 = fnv1a(self.0, bytes);
+    }
+}
+
+/// Hash `value` with [`Fnv1a`].
+pub(crate) fn content_hash_of(value: &impl core::hash::Hash) -> u64 {
+    let mut hasher = Fnv1a(FNV_OFFSET);
+    value.hash(&mut hasher);
+    core::hash::Hasher::finish(&hasher)
+}
+
+/// Attributes whose value is a set of space-separated tokens. Setting one
+/// again merges the new tokens into the existing value instead of adding a
+/// second attribute.
+pub(crate) fn is_token_list_attr(name: &str) -> bool {
+    matches!(name, "class" | "rel" | "headers" | "aria-describedby")
+}
+
+/// Boolean attributes, where presence means true and any value, even
+/// `"false"`, is ignored.
+pub(crate) fn is_boolean_attr(name: &str) -> bool {
+    matches!(
+        name,
+        "allowfullscreen"
+            | "async"
+            | "autofocus"
+            | "autoplay"
+            | "checked"
+            | "controls"
+            | "default"
+            | "defer"
+            | "disabled"
+            | "formnovalidate"
+            | "hidden"
+            | "inert"
+            | "ismap"
+            | "itemscope"
+            | "loop"
+            | "multiple"
+            | "muted"
+            | "nomodule"
+            | "novalidate"
+            | "open"
+            | "playsinline"
+            | "readonly"
+            | "required"
+            | "reversed"
+            | "selected"
+    )
+}
+
+/// Append each whitespace-separated token of `tokens` to the list in
+/// `existing`, in order, skipping tokens already present.
+pub(crate) fn merge_tokens(existing: &mut String, tokens: &str) {
+    for token in tokens.split_ascii_whitespace() {
+        if !existing.split_ascii_whitespace().any(|t| t == token) {
+            if !existing.is_empty() {
+                existing.push(' ');
+            }
+            existing.push_str(token);
+        }
+    }
+}
+
+/// Panic in debug builds if `id` contains ASCII whitespace.
+pub(crate) fn debug_check_id(id: &str) {
+    debug_assert!(
+        !id.contains(|c: char| c.is_ascii_whitespace()),
+        "id {id:?} must not contain whitespace"
+    );
+}
+
+/// Panic in debug builds if `class` is more than one token.
+pub(crate) fn debug_check_class(class: &str) {
+    debug_assert!(
+        !class.contains(|c: char| c.is_ascii_whitespace()),
+        "class {class:?} contains whitespace; use .classes([...]) to add several classes"
+    );
+}
+
 /// Escape special HTML characters in text content.
 #[must_use]
 pub fn escape_html(s: &str) -> String {
@@ -500,52 +1825,672 @@ pub fn escape_attr(s: &str) -> String {
 /// Shared escaping logic for both text content and attribute values.
 fn escape(s: &str, quotes: bool) -> String {
     let mut output = String::with_capacity(s.len());
-    for c in s.chars() {
-        match c {
-            '&' => output.push_str("&amp;"),
-            '<' => output.push_str("&lt;"),
-            '>' => output.push_str("&gt;"),
-            '"' if quotes => output.push_str("&quot;"),
-            '\'' if quotes => output.push_str("&#x27;"),
-            _ => output.push(c),
+    let _ = escape_to(&mut output, s, quotes, &RenderOptions::new());
+    output
+}
+
+/// Whether `c` is a control character HTML does not allow in text or
+/// attribute values. ASCII whitespace controls are allowed.
+const fn is_disallowed_control(c: char) -> bool {
+    matches!(c, '\0'..='\x08' | '\x0B' | '\x0E'..='\x1F' | '\x7F'..='\u{9F}')
+}
+
+/// Escape `s` into `output` according to `options`.
+///
+/// Runs of characters that need no escaping are written as single slices,
+/// so a streaming sink sees a few large writes rather than one per
+/// character.
+pub(crate) fn escape_to<W: Write + ?Sized>(
+    output: &mut W,
+    s: &str,
+    quotes: bool,
+    options: &RenderOptions,
+) -> core::fmt::Result {
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        let replacement = match c {
+            '&' => "&amp;",
+            '<' => "&lt;",
+            '>' => "&gt;",
+            '"' if quotes => "&quot;",
+            '\'' if quotes => "&#x27;",
+            _ if options.control_chars != ControlChars::Keep && is_disallowed_control(c) => {
+                if options.control_chars == ControlChars::Replace {
+                    "\u{FFFD}"
+                } else {
+                    ""
+                }
+            }
+            _ if options.escape == EscapePolicy::AsciiEntities && !c.is_ascii() => {
+                output.write_str(&s[start..i])?;
+                write!(output, "&#x{:X};", u32::from(c))?;
+                start = i + c.len_utf8();
+                continue;
+            }
+            _ => continue,
+        };
+        output.write_str(&s[start..i])?;
+        output.write_str(replacement)?;
+        start = i + c.len_utf8();
+    }
+    output.write_str(&s[start..])
+}
+
+/// Raw text elements, whose text is not parsed as HTML and so is written
+/// unescaped. Matches [`HtmlElement::RAW_TEXT`](ironhtml_elements::HtmlElement::RAW_TEXT),
+/// for trees that only keep the tag name.
+pub(crate) fn is_raw_text(tag: &str) -> bool {
+    tag.eq_ignore_ascii_case("script") || tag.eq_ignore_ascii_case("style")
+}
+
+/// Whether `content` at byte `i` starts a case-insensitive `</tag`.
+fn is_end_tag_at(content: &[u8], i: usize, tag: &str) -> bool {
+    let end_len = tag.len() + 2;
+    i + end_len <= content.len()
+        && content[i] == b'<'
+        && content[i + 1] == b'/'
+        && content[i + 2..i + end_len].eq_ignore_ascii_case(tag.as_bytes())
+}
+
+/// Write raw text content, which must not contain `</tag`.
+///
+/// Debug builds panic on a case-insensitive `</tag`: rewriting it would
+/// change what the script or stylesheet means, so the caller has to escape
+/// it in the content's own syntax. Release builds break it up as `<\/tag`
+/// instead, so the content still cannot close its element.
+pub(crate) fn escape_raw_text(content: &str, tag: &str) -> String {
+    let bytes = content.as_bytes();
+    debug_assert!(
+        !(0..bytes.len()).any(|i| is_end_tag_at(bytes, i, tag)),
+        "<{tag}> text must not contain `</{tag}`, which would end the element early"
+    );
+    let end_len = tag.len() + 2;
+    let mut output = String::with_capacity(content.len());
+    let mut last = 0;
+    let mut i = 0;
+    while i + end_len <= bytes.len() {
+        if is_end_tag_at(bytes, i, tag) {
+            output.push_str(&content[last..=i]);
+            output.push('\\');
+            last = i + 1;
+            i += end_len;
+        } else {
+            i += 1;
         }
     }
+    output.push_str(&content[last..]);
     output
 }
 
+/// Write `text` as a child of a `parent` element: escaped, or verbatim
+/// inside a raw text element, where escaping would corrupt the script or
+/// stylesheet (`a < b` would become `a &lt; b`).
+pub(crate) fn write_text_to<W: Write + ?Sized>(
+    sink: &mut W,
+    parent: &str,
+    text: &str,
+    options: &RenderOptions,
+) -> core::fmt::Result {
+    if is_raw_text(parent) {
+        sink.write_str(&escape_raw_text(text, parent))
+    } else {
+        escape_to(sink, text, false, options)
+    }
+}
+
+/// Render `<tag` followed by its attributes, leaving the tag open so the
+/// caller can finish it with `>` or ` />`.
+pub(crate) fn render_start_tag_to<W: Write + ?Sized, N: AsRef<str>>(
+    output: &mut W,
+    options: &RenderOptions,
+    tag: &str,
+    attrs: &[(N, String)],
+    key: Option<&str>,
+) -> core::fmt::Result {
+    output.write_char('<')?;
+    output.write_str(tag)?;
+
+    for_each_attr(options, attrs, key, |name, value| {
+        output.write_char(' ')?;
+        render_attr_to(output, options, name, value)
+    })
+}
+
+/// Call `f` with each attribute's name and value, in insertion order or,
+/// with [`RenderOptions::sort_attributes`], sorted by name; then, with
+/// [`RenderOptions::keys`], with the element's key as `data-ih-key` unless
+/// that attribute is already set.
+fn for_each_attr<N: AsRef<str>>(
+    options: &RenderOptions,
+    attrs: &[(N, String)],
+    key: Option<&str>,
+    mut f: impl FnMut(&str, &str) -> core::fmt::Result,
+) -> core::fmt::Result {
+    let needs_sort = options.sort_attributes
+        && !attrs
+            .windows(2)
+            .all(|pair| pair[0].0.as_ref() <= pair[1].0.as_ref());
+    if needs_sort {
+        let mut by_name: Vec<&(N, String)> = attrs.iter().collect();
+        by_name.sort_by(|a, b| a.0.as_ref().cmp(b.0.as_ref()));
+        by_name
+            .into_iter()
+            .try_for_each(|(name, value)| f(name.as_ref(), value))?;
+    } else {
+        attrs
+            .iter()
+            .try_for_each(|(name, value)| f(name.as_ref(), value))?;
+    }
+    match key {
+        Some(key) if options.keys && !attrs.iter().any(|(name, _)| name.as_ref() == KEY_ATTR) => {
+            f(KEY_ATTR, key)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// The attribute an element's key is rendered as with
+/// [`RenderOptions::keys`].
+pub(crate) const KEY_ATTR: &str = "data-ih-key";
+
+/// Render one `name="value"` pair, or just `name` for an empty value in
+/// HTML syntax.
+fn render_attr_to<W: Write + ?Sized>(
+    output: &mut W,
+    options: &RenderOptions,
+    name: &str,
+    value: &str,
+) -> core::fmt::Result {
+    output.write_str(name)?;
+    if value.is_empty() && options.syntax == Syntax::Html {
+        return Ok(());
+    }
+    output.write_str("=\"")?;
+    escape_to(output, value, true, options)?;
+    output.write_char('"')
+}
+
 /// Render an element's open tag, attributes, children, and close tag.
 ///
 /// Shared rendering logic used by both the untyped and typed APIs.
-pub(crate) fn render_element_to<N: AsRef<str>>(
-    output: &mut String,
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn render_element_to<W: Write + ?Sized, N: AsRef<str>>(
+    output: &mut W,
+    options: &RenderOptions,
     tag: &str,
     is_void: bool,
     attrs: &[(N, String)],
-    children: impl FnOnce(&mut String),
+    key: Option<&str>,
+    children: impl FnOnce(&mut W) -> core::fmt::Result,
     has_children: bool,
-) {
-    output.push('<');
-    output.push_str(tag);
+) -> core::fmt::Result {
+    render_start_tag_to(output, options, tag, attrs, key)?;
 
-    for (name, value) in attrs {
-        output.push(' ');
-        output.push_str(name.as_ref());
-        if !value.is_empty() {
-            output.push_str("=\"");
-            output.push_str(&escape_attr(value));
-            output.push('"');
+    if is_void && !has_children {
+        output.write_str(" />")
+    } else {
+        output.write_char('>')?;
+        children(output)?;
+        output.write_str("</")?;
+        output.write_str(tag)?;
+        output.write_char('>')
+    }
+}
+
+// Pretty rendering
+
+/// A borrowed view of a node, so the untyped and typed trees share one
+/// pretty printer and tree walk.
+pub(crate) enum NodeView<'a, N, A> {
+    Element {
+        tag: &'a str,
+        is_void: bool,
+        attrs: &'a [(A, String)],
+        key: Option<&'a str>,
+        children: &'a [N],
+    },
+    Text(&'a str),
+    Raw(&'a str),
+}
+
+/// A node that [`render_pretty_element_to`] can lay out.
+pub(crate) trait TreeNode {
+    type AttrName: AsRef<str>;
+
+    fn view(&self) -> NodeView<'_, Self, Self::AttrName>
+    where
+        Self: Sized;
+
+    /// Render this node with the given (non-pretty) options.
+    fn render_flat_to(&self, output: &mut String, options: &RenderOptions);
+
+    /// The text of a text node, for appending to it.
+    fn text_mut(&mut self) -> Option<&mut String>;
+
+    /// A text node holding `text`.
+    fn from_text(text: String) -> Self
+    where
+        Self: Sized;
+}
+
+impl TreeNode for Node {
+    type AttrName = String;
+
+    fn view(&self) -> NodeView<'_, Self, String> {
+        match self {
+            Self::Element(elem) => NodeView::Element {
+                tag: &elem.tag,
+                is_void: elem.self_closing,
+                attrs: &elem.attrs,
+                key: elem.key.as_deref(),
+                children: &elem.children,
+            },
+            Self::Text(text) => NodeView::Text(text),
+            Self::Raw(html) => NodeView::Raw(html),
         }
     }
 
-    if is_void && !has_children {
-        output.push_str(" />");
-    } else {
-        output.push('>');
-        children(output);
+    fn render_flat_to(&self, output: &mut String, options: &RenderOptions) {
+        self.render_to_with(output, options);
+    }
+
+    fn text_mut(&mut self) -> Option<&mut String> {
+        match self {
+            Self::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    fn from_text(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+/// Phrasing elements, which pretty rendering keeps on their parent's line.
+fn is_inline(tag: &str) -> bool {
+    matches!(
+        tag,
+        "a" | "abbr"
+            | "b"
+            | "bdi"
+            | "bdo"
+            | "br"
+            | "button"
+            | "cite"
+            | "code"
+            | "data"
+            | "del"
+            | "dfn"
+            | "em"
+            | "i"
+            | "img"
+            | "input"
+            | "ins"
+            | "kbd"
+            | "label"
+            | "mark"
+            | "meter"
+            | "output"
+            | "progress"
+            | "q"
+            | "s"
+            | "samp"
+            | "select"
+            | "small"
+            | "span"
+            | "strong"
+            | "sub"
+            | "sup"
+            | "textarea"
+            | "time"
+            | "u"
+            | "var"
+            | "wbr"
+    )
+}
+
+/// Elements whose content must be emitted exactly as built. Matches
+/// [`HtmlElement::PRESERVE_WHITESPACE`](ironhtml_elements::HtmlElement::PRESERVE_WHITESPACE),
+/// for trees that only keep the tag name.
+pub(crate) fn preserves_whitespace(tag: &str) -> bool {
+    matches!(tag, "pre" | "textarea" | "script" | "style")
+}
+
+/// Render an element for reading: if every child is a block-level element,
+/// each goes on its own line, indented two spaces per level; otherwise the
+/// element is rendered on one line exactly as without `pretty`.
+///
+/// With `annotations`, each line starts with the warnings for the elements
+/// on it; see [`DebugAnnotations`].
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn render_pretty_element_to<N: TreeNode, A: AsRef<str>>(
+    output: &mut String,
+    options: &RenderOptions,
+    tag: &str,
+    is_void: bool,
+    attrs: &[(A, String)],
+    key: Option<&str>,
+    children: &[N],
+    depth: usize,
+    mut annotations: Option<&mut DebugAnnotations>,
+) {
+    let blocks_only = !children.is_empty()
+        && !preserves_whitespace(tag)
+        && children.iter().all(|child| match child.view() {
+            NodeView::Element { tag, .. } => !is_inline(tag),
+            NodeView::Text(_) | NodeView::Raw(_) => false,
+        });
+
+    if let Some(annotations) = annotations.as_deref_mut() {
+        annotations.warn_to(output, depth, tag, attrs);
+        if !blocks_only {
+            annotations.warn_descendants_to(output, depth, children);
+        }
+    }
+    push_indent(output, depth);
+
+    if is_void && children.is_empty() {
+        render_pretty_start_tag_to(output, options, tag, attrs, key, true, depth);
+        return;
+    }
+    render_pretty_start_tag_to(output, options, tag, attrs, key, false, depth);
+
+    if !blocks_only {
+        let flat = RenderOptions {
+            pretty: false,
+            ..options.clone()
+        };
+        for child in children {
+            match child.view() {
+                NodeView::Text(text) => {
+                    let _ = write_text_to(output, tag, text, &flat);
+                }
+                _ => child.render_flat_to(output, &flat),
+            }
+        }
         output.push_str("</");
         output.push_str(tag);
         output.push('>');
+        return;
+    }
+
+    for child in children {
+        if let NodeView::Element {
+            tag,
+            is_void,
+            attrs,
+            key,
+            children,
+        } = child.view()
+        {
+            output.push('\n');
+            render_pretty_element_to(
+                output,
+                options,
+                tag,
+                is_void,
+                attrs,
+                key,
+                children,
+                depth + 1,
+                annotations.as_deref_mut(),
+            );
+        }
+    }
+    output.push('\n');
+    push_indent(output, depth);
+    output.push_str("</");
+    output.push_str(tag);
+    output.push('>');
+}
+
+/// Pretty-print root nodes, each on its own line, annotating likely
+/// mistakes. Backs the `render_debug_annotated` methods.
+pub(crate) fn render_debug_annotated_to<N: TreeNode>(output: &mut String, nodes: &[N]) {
+    let options = RenderOptions::new().pretty(true);
+    let flat = RenderOptions::default();
+    let mut annotations = DebugAnnotations::default();
+    for (i, node) in nodes.iter().enumerate() {
+        if i > 0 {
+            output.push('\n');
+        }
+        match node.view() {
+            NodeView::Element {
+                tag,
+                is_void,
+                attrs,
+                key,
+                children,
+            } => render_pretty_element_to(
+                output,
+                &options,
+                tag,
+                is_void,
+                attrs,
+                key,
+                children,
+                0,
+                Some(&mut annotations),
+            ),
+            _ => node.render_flat_to(output, &flat),
+        }
+    }
+}
+
+/// Likely mistakes found while pretty-printing for debugging: an `<img>`
+/// without `alt`, or an `id` already used earlier in the tree. Each is
+/// written as a `<!-- WARN: ... -->` line before the line holding the
+/// element.
+#[derive(Default)]
+pub(crate) struct DebugAnnotations {
+    seen_ids: Vec<String>,
+}
+
+impl DebugAnnotations {
+    /// Write the warnings for one element.
+    fn warn_to<A: AsRef<str>>(
+        &mut self,
+        output: &mut String,
+        depth: usize,
+        tag: &str,
+        attrs: &[(A, String)],
+    ) {
+        let attr = |name: &str| {
+            attrs
+                .iter()
+                .find(|(n, _)| n.as_ref() == name)
+                .map(|(_, v)| v.as_str())
+        };
+
+        if tag == "img" && attr("alt").is_none() {
+            warn_line_to(
+                output,
+                depth,
+                &alloc::format!("<{tag}> is missing an 'alt' attribute"),
+            );
+        }
+        if let Some(id) = attr("id") {
+            if self.seen_ids.iter().any(|seen| seen == id) {
+                // `--` cannot appear inside a comment, so keep the id out of
+                // it if it would break the marker.
+                let message = if id.contains("--") {
+                    alloc::format!("duplicate id on <{tag}>")
+                } else {
+                    alloc::format!("duplicate id '{id}' on <{tag}>")
+                };
+                warn_line_to(output, depth, &message);
+            } else {
+                self.seen_ids.push(id.to_string());
+            }
+        }
+    }
+
+    /// Write the warnings for every element below `children`, in document
+    /// order, for children rendered on their parent's line.
+    fn warn_descendants_to<N: TreeNode>(
+        &mut self,
+        output: &mut String,
+        depth: usize,
+        children: &[N],
+    ) {
+        for child in children {
+            if let NodeView::Element {
+                tag,
+                attrs,
+                children,
+                ..
+            } = child.view()
+            {
+                self.warn_to(output, depth, tag, attrs);
+                self.warn_descendants_to(output, depth, children);
+            }
+        }
+    }
+}
+
+fn warn_line_to(output: &mut String, depth: usize, message: &str) {
+    push_indent(output, depth);
+    output.push_str("<!-- WARN: ");
+    output.push_str(message);
+    output.push_str(" -->\n");
+}
+
+/// Render an opening tag at `depth`. If the line would be longer than
+/// [`RenderOptions::max_line_width`], each attribute goes on its own line
+/// and the closing `>` or `/>` on a line of its own, aligned with the `<`.
+fn render_pretty_start_tag_to<A: AsRef<str>>(
+    output: &mut String,
+    options: &RenderOptions,
+    tag: &str,
+    attrs: &[(A, String)],
+    key: Option<&str>,
+    self_closing: bool,
+    depth: usize,
+) {
+    let start = output.len();
+    let _ = render_start_tag_to(output, options, tag, attrs, key);
+    output.push_str(if self_closing { " />" } else { ">" });
+
+    let Some(max) = options.max_line_width else {
+        return;
+    };
+    let has_attrs = !attrs.is_empty() || (options.keys && key.is_some());
+    if !has_attrs || depth * 2 + output[start..].chars().count() <= max {
+        return;
+    }
+
+    output.truncate(start);
+    output.push('<');
+    output.push_str(tag);
+    let _ = for_each_attr(options, attrs, key, |name, value| {
+        output.push('\n');
+        push_indent(output, depth + 1);
+        render_attr_to(output, options, name, value)
+    });
+    output.push('\n');
+    push_indent(output, depth);
+    output.push_str(if self_closing { "/>" } else { ">" });
+}
+
+fn push_indent(output: &mut String, depth: usize) {
+    for _ in 0..depth {
+        output.push_str("  ");
+    }
+}
+
+// Tree statistics
+
+/// The number of nodes in `children` and all their descendants.
+pub(crate) fn count_nodes<N: TreeNode>(children: &[N]) -> usize {
+    children
+        .iter()
+        .map(|child| match child.view() {
+            NodeView::Element { children, .. } => 1 + count_nodes(children),
+            NodeView::Text(_) | NodeView::Raw(_) => 1,
+        })
+        .sum()
+}
+
+/// A rough size in bytes of the rendered element, used to reserve the
+/// output buffer up front. Counts both tags, attributes with their quotes,
+/// and text as written, so it only falls short when escaping grows text.
+pub(crate) fn estimated_size<N: TreeNode, A: AsRef<str>>(
+    tag: &str,
+    attrs: &[(A, String)],
+    children: &[N],
+) -> usize {
+    // `<tag>` and `</tag>`.
+    let tags = 2 * tag.len() + 5;
+    // ` name="value"`.
+    let attrs: usize = attrs
+        .iter()
+        .map(|(name, value)| name.as_ref().len() + value.len() + 4)
+        .sum();
+    let children: usize = children
+        .iter()
+        .map(|child| match child.view() {
+            NodeView::Element {
+                tag,
+                attrs,
+                children,
+                ..
+            } => estimated_size(tag, attrs, children),
+            NodeView::Text(text) | NodeView::Raw(text) => text.len(),
+        })
+        .sum();
+    tags + attrs + children
+}
+
+/// Panic if two elements in a tree share an `id`, naming the id.
+///
+/// Takes the root's attributes and children; a list of roots passes no
+/// attributes. Only debug builds with the `validate-ids` feature check
+/// anything; otherwise this is an empty function.
+#[cfg(all(feature = "validate-ids", debug_assertions))]
+pub(crate) fn validate_ids<N: TreeNode, A: AsRef<str>>(attrs: &[(A, String)], children: &[N]) {
+    fn visit<'a, N: TreeNode, A: AsRef<str>>(
+        attrs: &'a [(A, String)],
+        children: &'a [N],
+        seen: &mut alloc::collections::BTreeSet<&'a str>,
+    ) {
+        for (name, value) in attrs {
+            if name.as_ref() == "id" {
+                assert!(
+                    seen.insert(value),
+                    "duplicate id {value:?} in rendered tree"
+                );
+            }
+        }
+        for child in children {
+            if let NodeView::Element {
+                attrs, children, ..
+            } = child.view()
+            {
+                visit(attrs, children, seen);
+            }
+        }
     }
+    visit(attrs, children, &mut alloc::collections::BTreeSet::new());
+}
+
+#[cfg(not(all(feature = "validate-ids", debug_assertions)))]
+#[inline(always)]
+pub(crate) const fn validate_ids<N: TreeNode, A: AsRef<str>>(
+    _attrs: &[(A, String)],
+    _children: &[N],
+) {
+}
+
+/// The number of nodes on the longest path down from `children`.
+pub(crate) fn nesting_depth<N: TreeNode>(children: &[N]) -> usize {
+    children
+        .iter()
+        .map(|child| match child.view() {
+            NodeView::Element { children, .. } => 1 + nesting_depth(children),
+            NodeView::Text(_) | NodeView::Raw(_) => 1,
+        })
+        .max()
+        .unwrap_or(0)
 }
 
 // Convenience functions for common elements
@@ -582,7 +2527,7 @@ pub fn text_elem(tag: &str, content: impl Into<String>) -> Element {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use alloc::vec;
+    use alloc::{format, vec};
 
     #[test]
     fn test_simple_element() {
@@ -680,6 +2625,524 @@ mod tests {
         assert_eq!(html, r"<div></div>");
     }
 
+    #[test]
+    fn test_escape_policy_minimal() {
+        let html = Element::new("p")
+            .attr("title", "café")
+            .text("café")
+            .render_with(&RenderOptions::new());
+        assert_eq!(html, r#"<p title="café">café</p>"#);
+    }
+
+    #[test]
+    fn test_control_chars_stripped() {
+        let html = Element::new("p")
+            .attr("title", "a\0b")
+            .text("x\0y\u{1}z\tw")
+            .render_with(&RenderOptions::new().control_chars(ControlChars::Strip));
+        assert_eq!(html, "<p title=\"ab\">xyz\tw</p>");
+    }
+
+    #[test]
+    fn test_control_chars_replace_and_keep() {
+        let elem = Element::new("p").text("x\0y");
+        let replaced = elem.render_with(&RenderOptions::new().control_chars(ControlChars::Replace));
+        assert_eq!(replaced, "<p>x\u{FFFD}y</p>");
+        assert_eq!(elem.render(), "<p>x\0y</p>");
+        assert_eq!(escape_html("a\0\u{7F}b"), "a\0\u{7F}b");
+    }
+
+    #[test]
+    fn test_renderer_reuses_buffer() {
+        let mut renderer = Renderer::new();
+        let first = Element::new("div")
+            .class("card")
+            .child("p", |p| p.text("A longer first paragraph of text"));
+        assert_eq!(
+            renderer.render_reset(&first),
+            r#"<div class="card"><p>A longer first paragraph of text</p></div>"#
+        );
+        let capacity = renderer.capacity();
+        let ptr = renderer.render_reset(&first).as_ptr();
+
+        let second = Element::new("p").text("Short");
+        let html = renderer.render_reset(&second);
+        assert_eq!(html, "<p>Short</p>");
+        assert_eq!(html.as_ptr(), ptr);
+        assert_eq!(renderer.capacity(), capacity);
+    }
+
+    #[test]
+    fn test_classes() {
+        let html = Element::new("div").classes(["card", "shadow"]).render();
+        assert_eq!(html, r#"<div class="card shadow"></div>"#);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "must not contain whitespace")]
+    fn test_id_with_whitespace() {
+        let _ = Element::new("div").id("a b");
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "use .classes")]
+    fn test_classes_with_whitespace() {
+        let _ = Element::new("div").classes(["a b"]);
+    }
+
+    #[test]
+    fn test_render_pretty() {
+        let page = Element::new("div")
+            .class("page")
+            .child("header", |h| h.child("h1", |h1| h1.text("Title")))
+            .child("p", |p| {
+                p.text("Some ")
+                    .child("strong", |s| s.text("bold"))
+                    .text(" text")
+            })
+            .child("ul", |ul| {
+                ul.child("li", |li| li.text("One"))
+                    .child("li", |li| li.text("Two"))
+            });
+        assert_eq!(
+            page.render_pretty(),
+            "<div class=\"page\">\n  <header>\n    <h1>Title</h1>\n  </header>\n  \
+             <p>Some <strong>bold</strong> text</p>\n  <ul>\n    <li>One</li>\n    \
+             <li>Two</li>\n  </ul>\n</div>"
+        );
+        // Without `pretty` the output is unchanged.
+        assert!(!page.render().contains('\n'));
+    }
+
+    #[test]
+    fn test_render_pretty_keeps_preformatted_content() {
+        let div = Element::new("div")
+            .child("pre", |pre| pre.child("div", |d| d.text("  x\n")))
+            .child("section", |s| s.child("span", |s| s.child("div", |d| d)))
+            .child("hr", |hr| hr);
+        assert_eq!(
+            div.render_pretty(),
+            "<div>\n  <pre><div>  x\n</div></pre>\n  \
+             <section><span><div></div></span></section>\n  <hr />\n</div>"
+        );
+    }
+
+    #[test]
+    fn test_render_xhtml() {
+        let form = Element::new("form")
+            .child("input", |i| i.attr("type", "checkbox").bool_attr("checked"))
+            .child("br", |br| br)
+            .child("div", |d| d)
+            .child("option", |o| o.bool_attr("selected").attr("value", ""));
+        assert_eq!(
+            form.render_xhtml(),
+            "<form><input type=\"checkbox\" checked=\"\" /><br /><div></div>\
+             <option selected=\"\" value=\"\"></option></form>"
+        );
+        assert_eq!(
+            form.render(),
+            "<form><input type=\"checkbox\" checked /><br /><div></div>\
+             <option selected value></option></form>"
+        );
+    }
+
+    #[test]
+    fn test_class_if() {
+        let div = Element::new("div")
+            .class_if(false, "hidden")
+            .class("a")
+            .class_if(true, "b")
+            .class_if(true, "a");
+        assert_eq!(div.render(), r#"<div class="a b"></div>"#);
+    }
+
+    #[test]
+    fn test_estimated_size() {
+        let elem = Element::new("div")
+            .class("a")
+            .child("p", |p| p.text("hi"))
+            .raw("<hr>");
+        let html = elem.render();
+        assert_eq!(html, r#"<div class="a"><p>hi</p><hr></div>"#);
+        assert_eq!(elem.estimated_size(), html.len());
+        assert!(html.capacity() >= html.len());
+    }
+
+    #[test]
+    fn test_display() {
+        let elem = Element::new("div").child("p", |p| p.text("a < b"));
+        assert_eq!(format!("{elem}"), elem.render());
+        assert_eq!(format!("{elem:#}"), elem.render_pretty());
+        assert_eq!(elem.to_string(), "<div><p>a &lt; b</p></div>");
+        let html = Html::new().text("x").raw("<br>");
+        assert_eq!(format!("{html}"), "x<br>");
+        let html = Html::new()
+            .elem("p", |p| p.text("a"))
+            .elem("p", |p| p.text("b"));
+        assert_eq!(format!("{html:#}"), html.render_pretty());
+    }
+
+    #[test]
+    fn test_render_debug_annotated() {
+        let html = Html::new()
+            .elem("img", |img| img.id("logo").attr("src", "logo.png"))
+            .elem("p", |p| {
+                p.text("See ")
+                    .child("img", |img| img.id("logo").attr("src", "x.png"))
+            });
+        assert_eq!(
+            html.render_debug_annotated(),
+            "<!-- WARN: <img> is missing an 'alt' attribute -->\n\
+             <img id=\"logo\" src=\"logo.png\" />\n\
+             <!-- WARN: <img> is missing an 'alt' attribute -->\n\
+             <!-- WARN: duplicate id 'logo' on <img> -->\n\
+             <p>See <img id=\"logo\" src=\"x.png\" /></p>"
+        );
+        let clean = Element::new("ul").child("li", |li| li.text("a"));
+        assert_eq!(clean.render_debug_annotated(), clean.render_pretty());
+    }
+
+    #[cfg(all(feature = "validate-ids", debug_assertions))]
+    #[test]
+    #[should_panic(expected = r#"duplicate id "main" in rendered tree"#)]
+    fn test_validate_ids_panics_on_duplicate() {
+        let _ = Element::new("div")
+            .id("main")
+            .child("p", |p| p.child("span", |s| s.id("main")))
+            .render();
+    }
+
+    #[cfg(all(feature = "validate-ids", debug_assertions))]
+    #[test]
+    #[should_panic(expected = r#"duplicate id "a" in rendered tree"#)]
+    fn test_validate_ids_across_roots() {
+        let _ = Html::new()
+            .elem("p", |p| p.id("a"))
+            .elem("p", |p| p.id("a"))
+            .build();
+    }
+
+    #[test]
+    fn test_validate_ids_allows_unique_ids() {
+        let html = Element::new("div")
+            .id("a")
+            .child("p", |p| p.id("b"))
+            .render();
+        assert_eq!(html, r#"<div id="a"><p id="b"></p></div>"#);
+    }
+
+    #[test]
+    fn test_raw_text_elements() {
+        let script = Element::new("script").text("if (a < b) { go('&'); }");
+        assert_eq!(script.render(), "<script>if (a < b) { go('&'); }</script>");
+        let p = Element::new("p").text("a < b");
+        assert_eq!(p.render(), "<p>a &lt; b</p>");
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "<script> text must not contain `</script`")]
+    fn test_raw_text_rejects_end_tag() {
+        let _ = Element::new("script").text("go('</SCRIPT>');").render();
+    }
+
+    #[cfg(not(debug_assertions))]
+    #[test]
+    fn test_raw_text_cannot_close_element() {
+        let script = Element::new("script").text("if (a < b) { go('</SCRIPT>'); }");
+        assert_eq!(
+            script.render(),
+            r"<script>if (a < b) { go('<\/SCRIPT>'); }</script>"
+        );
+    }
+
+    #[test]
+    fn test_extend_children() {
+        let names = ["a", "b", "c"];
+        let mut list =
+            Element::new("ol").extend_children(names.iter().map(|n| Element::new("li").text(*n)));
+        list.extend(["x", "y"]);
+        assert_eq!(list.render(), "<ol><li>a</li><li>b</li><li>c</li>xy</ol>");
+        assert_eq!(list.node_count(), 8);
+
+        let html: Html = names.iter().map(|n| Element::new("p").text(*n)).collect();
+        assert_eq!(html.build(), "<p>a</p><p>b</p><p>c</p>");
+    }
+
+    #[test]
+    fn test_html_render_pretty() {
+        let html = Html::new()
+            .elem("div", |d| {
+                d.child("pre", |t| t.text("  keep\n  this"))
+                    .child("hr", |hr| hr)
+                    .child("p", |p| p.child("a", |a| a.attr("href", "/").text("Home")))
+            })
+            .elem("footer", |f| f);
+        assert_eq!(
+            html.render_pretty(),
+            "<div>\n  <pre>  keep\n  this</pre>\n  <hr />\n  \
+             <p><a href=\"/\">Home</a></p>\n</div>\n<footer></footer>"
+        );
+    }
+
+    #[test]
+    fn test_render_pretty_wraps_long_start_tags() {
+        let options = RenderOptions::new().pretty(true).max_line_width(40);
+        let head = Element::new("head").child("link", |l| {
+            l.attr("rel", "stylesheet")
+                .attr("href", "/app.css")
+                .attr("media", "screen")
+                .attr("integrity", "sha384-abc")
+                .attr("crossorigin", "anonymous")
+                .bool_attr("blocking")
+        });
+        assert_eq!(
+            head.render_with(&options),
+            "<head>\n  <link\n    rel=\"stylesheet\"\n    href=\"/app.css\"\n    media=\"screen\"\n    \
+             integrity=\"sha384-abc\"\n    crossorigin=\"anonymous\"\n    blocking\n  />\n</head>"
+        );
+
+        let button = Element::new("button")
+            .attr("type", "submit")
+            .attr("class", "btn btn-primary")
+            .attr("name", "action")
+            .attr("value", "save")
+            .attr("form", "editor")
+            .attr("title", "Save changes")
+            .text("Save");
+        assert_eq!(
+            button.render_with(&options),
+            "<button\n  type=\"submit\"\n  class=\"btn btn-primary\"\n  name=\"action\"\n  \
+             value=\"save\"\n  form=\"editor\"\n  title=\"Save changes\"\n>Save</button>"
+        );
+
+        // Short tags stay on one line.
+        let short = Element::new("p").attr("id", "x").text("Hi");
+        assert_eq!(short.render_with(&options), r#"<p id="x">Hi</p>"#);
+    }
+
+    #[test]
+    fn test_content_hash() {
+        let build =
+            |href: &str| Element::new("nav").child("a", |a| a.attr("href", href).text("Home"));
+        assert_eq!(build("/"), build("/"));
+        assert_eq!(build("/").content_hash(), build("/").content_hash());
+        assert_ne!(build("/").content_hash(), build("/home").content_hash());
+
+        // Structure, not rendered output, is hashed.
+        let split = Element::new("p").text("a").raw("b");
+        let joined = Element::new("p").text("ab");
+        assert_eq!(split.render(), joined.render());
+        assert_ne!(split.content_hash(), joined.content_hash());
+    }
+
+    #[test]
+    fn test_mut_setters() {
+        let mut row = Element::new("tr");
+        row.set_id("row-1");
+        for (i, flag) in [true, false, true].into_iter().enumerate() {
+            if flag {
+                row.set_class(alloc::format!("f{i}"));
+                row.set_attr(alloc::format!("data-f{i}"), "on");
+            }
+        }
+        assert_eq!(
+            row.render(),
+            r#"<tr id="row-1" class="f0 f2" data-f0="on" data-f2="on"></tr>"#
+        );
+    }
+
+    #[test]
+    fn test_token_attributes_merge() {
+        let div = Element::new("div")
+            .class("a")
+            .classes(["b", "c"])
+            .class("d")
+            .class("b")
+            .attr("class", "e a");
+        assert_eq!(div.render(), r#"<div class="a b c d e"></div>"#);
+
+        let link = Element::new("a")
+            .attr("rel", "noopener")
+            .attr("rel", "noreferrer noopener");
+        assert_eq!(link.render(), r#"<a rel="noopener noreferrer"></a>"#);
+
+        // Other attributes are not merged.
+        let data = Element::new("div").attr("data-x", "1").attr("data-x", "1");
+        assert_eq!(data.render(), r#"<div data-x="1" data-x="1"></div>"#);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_render_to_io_propagates_errors() {
+        /// Accepts `limit` bytes, then fails.
+        struct Limited {
+            written: Vec<u8>,
+            limit: usize,
+        }
+
+        impl std::io::Write for Limited {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                let room = self.limit - self.written.len();
+                if room == 0 {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::WriteZero,
+                        "disk full",
+                    ));
+                }
+                let n = buf.len().min(room);
+                self.written.extend_from_slice(&buf[..n]);
+                Ok(n)
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let page = Element::new("p").text("Hello, world");
+
+        let mut full = Limited {
+            written: Vec::new(),
+            limit: 64,
+        };
+        page.render_to_io(&mut full).unwrap();
+        assert_eq!(full.written, b"<p>Hello, world</p>");
+
+        let mut short = Limited {
+            written: Vec::new(),
+            limit: 5,
+        };
+        let err = page.render_to_io(&mut short).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::WriteZero);
+        assert_eq!(err.to_string(), "disk full");
+        assert_eq!(short.written, b"<p>He");
+    }
+
+    #[test]
+    fn test_render_into_streams_tag_by_tag() {
+        /// Records every write, and fails once `fail_after` writes are done.
+        struct Recorder {
+            writes: Vec<String>,
+            fail_after: usize,
+        }
+
+        impl Write for Recorder {
+            fn write_str(&mut self, s: &str) -> core::fmt::Result {
+                if self.writes.len() == self.fail_after {
+                    return Err(core::fmt::Error);
+                }
+                self.writes.push(s.into());
+                Ok(())
+            }
+        }
+
+        let table = (0..1000).fold(Element::new("table"), |table, i| {
+            table.child("tr", |tr| {
+                tr.attr("data-row", i.to_string())
+                    .child("td", |td| td.text("<a> & \"b\""))
+                    .child("td", |td| td.raw("<br>"))
+            })
+        });
+        let expected = table.render();
+
+        let mut sink = Recorder {
+            writes: Vec::new(),
+            fail_after: usize::MAX,
+        };
+        table.render_into(&mut sink).unwrap();
+        assert_eq!(sink.writes.concat(), expected);
+        assert!(sink.writes.iter().all(|w| w.len() <= "data-row".len()));
+
+        let mut failing = Recorder {
+            writes: Vec::new(),
+            fail_after: 10,
+        };
+        assert!(table.render_into(&mut failing).is_err());
+        assert_eq!(failing.writes.len(), 10);
+
+        #[cfg(feature = "typed")]
+        {
+            let mut typed = String::new();
+            let list = crate::typed::Element::<ironhtml_elements::Ul>::new()
+                .child::<ironhtml_elements::Li, _>(|li| li.text("a < b"));
+            list.render_into(&mut typed).unwrap();
+            assert_eq!(typed, list.render());
+        }
+    }
+
+    #[test]
+    fn test_adjacent_text_merges() {
+        let p = Element::new("p")
+            .text("a")
+            .text(" & b")
+            .raw("<br>")
+            .text("c");
+        assert_eq!(p.children.len(), 3);
+        assert_eq!(p.render(), "<p>a &amp; b<br>c</p>");
+
+        let html = Html::new().text("x").text("y");
+        assert_eq!(html.nodes, [Node::Text("xy".into())]);
+    }
+
+    #[test]
+    fn test_node_count_and_depth() {
+        let leaf = Element::new("br");
+        assert_eq!((leaf.node_count(), leaf.max_depth()), (1, 1));
+
+        // <ul><li>a</li><li><b>b</b>!</li></ul>
+        let list = Element::new("ul")
+            .child("li", |li| li.text("a"))
+            .child("li", |li| li.child("b", |b| b.text("b")).raw("!"));
+        assert_eq!(list.node_count(), 7);
+        assert_eq!(list.max_depth(), 4);
+    }
+
+    #[test]
+    fn test_sort_attributes() {
+        let input = Element::new("input")
+            .attr("type", "text")
+            .class("a")
+            .attr("name", "q")
+            .class("b")
+            .attr("data-x", "1");
+        assert_eq!(
+            input.render(),
+            r#"<input type="text" class="a b" name="q" data-x="1" />"#
+        );
+
+        let sorted = RenderOptions::new().sort_attributes(true);
+        assert_eq!(
+            input.render_with(&sorted),
+            r#"<input class="a b" data-x="1" name="q" type="text" />"#
+        );
+
+        // Wrapped pretty output is sorted too.
+        let wrapped = sorted.pretty(true).max_line_width(10);
+        assert_eq!(
+            Element::new("p")
+                .attr("z", "1")
+                .attr("a", "2")
+                .render_with(&wrapped),
+            "<p\n  a=\"2\"\n  z=\"1\"\n></p>"
+        );
+    }
+
+    #[test]
+    fn test_escape_policy_ascii_entities() {
+        let options = RenderOptions::new().escape(EscapePolicy::AsciiEntities);
+        let html = Element::new("p")
+            .attr("title", "café")
+            .text("café & 🦀")
+            .render_with(&options);
+        assert_eq!(
+            html,
+            r#"<p title="caf&#xE9;">caf&#xE9; &amp; &#x1F980;</p>"#
+        );
+    }
+
     #[test]
     fn test_raw_html() {
         let html = Element::new("div").raw("<strong>Bold</strong>").render();
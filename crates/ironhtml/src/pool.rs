@@ -0,0 +1,168 @@
+//! Thread-local pooling of render buffers.
+//!
+//! [`Render::render_pooled`] renders into a `String` taken from a small
+//! per-thread pool and returns it wrapped in a [`PooledString`], which puts
+//! the buffer back when dropped. Once the pool has warmed up, a server
+//! thread rendering one response after another reuses the same few buffers
+//! instead of allocating a new one per response.
+//!
+//! Requires the `pool` feature, which implies `std`.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use ironhtml::{Element, Render};
+//!
+//! for name in ["Ada", "Grace"] {
+//!     let html = Element::new("p").text(name).render_pooled();
+//!     assert!(html.starts_with("<p>"));
+//!     // `html` goes back to the pool here.
+//! }
+//! ```
+//!
+//! Unlike [`Renderer`](crate::Renderer), nothing has to be threaded
+//! through the code: each thread has its own pool, and a `PooledString`
+//! can be held, sent to another thread, or turned into a plain `String`
+//! with [`PooledString::into_string`].
+
+use core::fmt;
+use core::ops::Deref;
+use std::cell::RefCell;
+use std::string::String;
+use std::vec::Vec;
+
+use crate::Render;
+
+/// How many idle buffers each thread keeps.
+const MAX_POOLED: usize = 8;
+
+/// Buffers that grew beyond this many bytes are freed rather than pooled,
+/// so one huge page does not pin its memory for the life of the thread.
+const MAX_POOLED_CAPACITY: usize = 1 << 20;
+
+std::thread_local! {
+    static POOL: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// A rendered string whose buffer returns to the current thread's pool on
+/// drop.
+///
+/// Dereferences to `str`. The buffer goes back to the pool of whichever
+/// thread drops it.
+pub struct PooledString {
+    buf: String,
+}
+
+impl PooledString {
+    /// Render `value` into a buffer from the pool.
+    pub(crate) fn render(value: &(impl Render + ?Sized)) -> Self {
+        let mut buf = POOL
+            .try_with(|pool| pool.borrow_mut().pop())
+            .ok()
+            .flatten()
+            .unwrap_or_default();
+        value.render_to_buffer(&mut buf);
+        Self { buf }
+    }
+
+    /// Take the rendered `String` out, so its buffer is not returned to
+    /// the pool.
+    #[must_use]
+    pub fn into_string(mut self) -> String {
+        core::mem::take(&mut self.buf)
+    }
+
+    /// The rendered HTML.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.buf
+    }
+}
+
+impl Drop for PooledString {
+    fn drop(&mut self) {
+        let mut buf = core::mem::take(&mut self.buf);
+        if buf.capacity() == 0 || buf.capacity() > MAX_POOLED_CAPACITY {
+            return;
+        }
+        buf.clear();
+        // The pool may already be gone if this runs during thread teardown,
+        // in which case the buffer is simply freed.
+        let _ = POOL.try_with(|pool| {
+            if let Ok(mut pool) = pool.try_borrow_mut() {
+                if pool.len() < MAX_POOLED {
+                    pool.push(buf);
+                }
+            }
+        });
+    }
+}
+
+impl Deref for PooledString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.buf
+    }
+}
+
+impl AsRef<str> for PooledString {
+    fn as_ref(&self) -> &str {
+        &self.buf
+    }
+}
+
+impl fmt::Display for PooledString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.buf)
+    }
+}
+
+impl fmt::Debug for PooledString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.buf, f)
+    }
+}
+
+impl PartialEq<str> for PooledString {
+    fn eq(&self, other: &str) -> bool {
+        self.buf == other
+    }
+}
+
+impl PartialEq<&str> for PooledString {
+    fn eq(&self, other: &&str) -> bool {
+        self.buf == *other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Element, Render};
+
+    #[test]
+    fn test_buffer_is_reused() {
+        let page = Element::new("p").text("x".repeat(1000));
+        let first = page.render_pooled();
+        assert_eq!(first, page.render().as_str());
+        let ptr = first.as_ptr();
+        drop(first);
+
+        // The same allocation comes back, already big enough.
+        let second = Element::new("b").render_pooled();
+        assert_eq!(second, "<b></b>");
+        assert_eq!(second.as_ptr(), ptr);
+    }
+
+    #[test]
+    fn test_into_string_leaves_pool() {
+        let owned = Element::new("i").render_pooled().into_string();
+        assert_eq!(owned, "<i></i>");
+
+        // Buffers in use at the same time are distinct.
+        let a = Element::new("a").render_pooled();
+        let b = Element::new("b").render_pooled();
+        assert_ne!(a.as_ptr(), b.as_ptr());
+        assert_ne!(a.as_ptr(), owned.as_ptr());
+    }
+}
@@ -37,6 +37,9 @@
 //! assert!(html.contains(r#"<a href="/">Home</a>"#));
 //! ```
 //!
+//! The [`el`] module has a lowercase constructor for every element, such as
+//! `el::div()`, for building without turbofish syntax.
+//!
 //! ## Compile-Time Safety
 //!
 //! Invalid nesting produces a compilation error:
@@ -54,19 +57,41 @@ use alloc::borrow::Cow;
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use core::marker::PhantomData;
-use ironhtml_attributes::AttributeValue;
-use ironhtml_elements::{CanContain, HtmlElement, Text};
+use core::num::NonZeroU32;
+use ironhtml_attributes::{
+    AriaLive, AttributeValue, Capture, Decoding, Download, Enctype, EventHandler, HttpEquiv,
+    InlineStyle, InputType, Loading, MetaName, Method, RelTokens, Role, ScriptType, Shape, SrcSet,
+    WindowEventHandler, Wrap,
+};
+use ironhtml_elements::{
+    Area, Blockquote, Body, CanContain, Caption, Col, Colgroup, Custom, Datalist, Del, Details,
+    Dialog, Fieldset, Form, Head, Html, HtmlElement, Iframe, Img, Input, Ins, Label, Legend, Link,
+    ListedElement, Meta, Meter, NonVoidElement, Optgroup, Option_, Progress, Script, Select,
+    Source, Style, Summary, Table, Td, Text, Textarea, Th, Time, A, Q,
+};
+
+use crate::datetime::DatetimeValue;
+use crate::{
+    escape_raw_text, escape_to, write_text_to, NodeView, Render, RenderOptions, Syntax, TreeNode,
+};
 
-use crate::escape_html;
+pub mod el;
+#[doc(hidden)]
+pub mod interpolate;
 
 /// A node in the typed HTML tree.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum TypedNode {
-    /// An element with tag, attributes, and children.
+    /// An element with tag, attributes, key, and children.
+    ///
+    /// Non-exhaustive: match it with `..`, and build elements with
+    /// [`Element`] rather than this variant.
+    #[non_exhaustive]
     Element {
         tag: &'static str,
         is_void: bool,
         attrs: Vec<(Cow<'static, str>, String)>,
+        key: Option<String>,
         children: Vec<Self>,
     },
     /// Escaped text content.
@@ -84,30 +109,286 @@ impl TypedNode {
         output
     }
 
+    /// Render this node to a string using the given options.
+    #[must_use]
+    pub fn render_with(&self, options: &RenderOptions) -> String {
+        let mut output = String::new();
+        self.render_to_with(&mut output, options);
+        output
+    }
+
     /// Render this node to an existing string buffer.
     pub fn render_to(&self, output: &mut String) {
+        self.render_to_with(output, &RenderOptions::default());
+    }
+
+    /// Render this node to an existing string buffer using the given options.
+    pub fn render_to_with(&self, output: &mut String, options: &RenderOptions) {
         match self {
             Self::Element {
                 tag,
                 is_void,
                 attrs,
+                key,
                 children,
-            } => {
-                crate::render_element_to(
+            } if options.pretty => {
+                crate::render_pretty_element_to(
                     output,
+                    options,
                     tag,
                     *is_void,
                     attrs,
-                    |out| {
-                        for child in children {
-                            child.render_to(out);
-                        }
-                    },
-                    !children.is_empty(),
+                    key.as_deref(),
+                    children,
+                    0,
+                    None,
                 );
             }
-            Self::Text(text) => output.push_str(&escape_html(text)),
-            Self::Raw(html) => output.push_str(html),
+            _ => {
+                let _ = self.write_with(output, options);
+            }
+        }
+    }
+
+    /// Write this node to `sink` tag by tag, without first rendering the
+    /// whole tree to a string. Pretty rendering needs to measure lines, so
+    /// it still goes through a string.
+    fn write_with<W: core::fmt::Write + ?Sized>(
+        &self,
+        sink: &mut W,
+        options: &RenderOptions,
+    ) -> core::fmt::Result {
+        match self {
+            Self::Element { .. } if options.pretty => sink.write_str(&self.render_with(options)),
+            Self::Element {
+                tag,
+                is_void,
+                attrs,
+                key,
+                children,
+            } => crate::render_element_to(
+                sink,
+                options,
+                tag,
+                *is_void,
+                attrs,
+                key.as_deref(),
+                |sink| {
+                    children.iter().try_for_each(|child| match child {
+                        Self::Text(text) => write_text_to(sink, tag, text, options),
+                        _ => child.write_with(sink, options),
+                    })
+                },
+                !children.is_empty(),
+            ),
+            Self::Text(text) => escape_to(sink, text, false, options),
+            Self::Raw(html) => sink.write_str(html),
+        }
+    }
+
+    /// Pretty-print this node, annotating likely mistakes.
+    ///
+    /// See [`Element::render_debug_annotated`].
+    #[must_use]
+    pub fn render_debug_annotated(&self) -> String {
+        let mut output = String::new();
+        crate::render_debug_annotated_to(&mut output, core::slice::from_ref(self));
+        output
+    }
+}
+
+impl TreeNode for TypedNode {
+    type AttrName = Cow<'static, str>;
+
+    fn view(&self) -> NodeView<'_, Self, Cow<'static, str>> {
+        match self {
+            Self::Element {
+                tag,
+                is_void,
+                attrs,
+                key,
+                children,
+            } => NodeView::Element {
+                tag,
+                is_void: *is_void,
+                attrs,
+                key: key.as_deref(),
+                children,
+            },
+            Self::Text(text) => NodeView::Text(text),
+            Self::Raw(html) => NodeView::Raw(html),
+        }
+    }
+
+    fn render_flat_to(&self, output: &mut String, options: &RenderOptions) {
+        self.render_to_with(output, options);
+    }
+
+    fn text_mut(&mut self) -> Option<&mut String> {
+        match self {
+            Self::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    fn from_text(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+/// A value that can be added as a child of an element of type `P`.
+///
+/// Implemented for [`Element<C>`] whenever `P: CanContain<C>`, so children
+/// added through [`Element::prepend_child`] and [`Element::insert_child`]
+/// are checked against the content model just like [`Element::child`].
+pub trait IntoChild<P> {
+    /// Append the nodes this value represents to `nodes`.
+    fn push_into(self, nodes: &mut Vec<TypedNode>);
+}
+
+impl<P, C> IntoChild<P> for Element<C>
+where
+    P: CanContain<C>,
+    C: HtmlElement,
+{
+    fn push_into(self, nodes: &mut Vec<TypedNode>) {
+        nodes.push(self.into_node());
+    }
+}
+
+/// A borrowed element is added as a copy, checked against the content
+/// model like one passed by value.
+impl<P, C> IntoChild<P> for &Element<C>
+where
+    P: CanContain<C>,
+    C: HtmlElement,
+{
+    fn push_into(self, nodes: &mut Vec<TypedNode>) {
+        nodes.push(self.clone().into_node());
+    }
+}
+
+/// A user type that renders itself, added to a typed tree as its HTML.
+///
+/// Implement [`Render`] for the type, then opt in with an empty
+/// `impl Component for MyWidget {}`. A reference to it can then be passed
+/// to [`Element::append_child`], used as a computed child (`{ &widget }`)
+/// in [`html!`](crate::html), or interpolated as `#widget`.
+///
+/// The output is inserted as-is into any parent that is not a void
+/// element; it is not checked against the parent's content model. The
+/// crate's own elements are not components, so they stay checked with
+/// [`CanContain`].
+///
+/// ## Example
+///
+/// ```rust
+/// use core::fmt::{self, Write};
+/// use ironhtml::typed::{Component, Element};
+/// use ironhtml::Render;
+/// use ironhtml_elements::P;
+///
+/// struct Badge(u32);
+///
+/// impl Render for Badge {
+///     fn render_into(&self, sink: &mut impl Write) -> fmt::Result {
+///         write!(sink, r#"<span class="badge">{}</span>"#, self.0)
+///     }
+/// }
+///
+/// impl Component for Badge {}
+///
+/// let p = Element::<P>::new().text("Inbox ").append_child(&Badge(3));
+/// assert_eq!(p.render(), r#"<p>Inbox <span class="badge">3</span></p>"#);
+/// ```
+pub trait Component: Render {}
+
+impl<T: Component + ?Sized> Component for &T {}
+
+/// A reference to a [`Component`] is added as its rendered HTML.
+impl<P, T> IntoChild<P> for &T
+where
+    P: NonVoidElement,
+    T: Component + ?Sized,
+{
+    fn push_into(self, nodes: &mut Vec<TypedNode>) {
+        nodes.push(render_component(self));
+    }
+}
+
+/// Render a component to a raw node.
+fn render_component<T: Component + ?Sized>(component: &T) -> TypedNode {
+    let mut html = String::new();
+    component.render_to_buffer(&mut html);
+    TypedNode::Raw(html)
+}
+
+/// A `Vec` of children is added in order, flattened into the parent: no
+/// wrapper element is produced, and an empty `Vec` adds nothing.
+impl<P, T> IntoChild<P> for Vec<T>
+where
+    T: IntoChild<P>,
+{
+    fn push_into(self, nodes: &mut Vec<TypedNode>) {
+        for child in self {
+            child.push_into(nodes);
+        }
+    }
+}
+
+/// A mapped iterator, such as `items.iter().map(|item| ...)`, adds each
+/// child it yields, like a `Vec` but without collecting first.
+///
+/// The iterator is lazy until the call that adds it (`append_child`, or a
+/// `{ ... }` child in `html!`), which runs it to completion right away, so
+/// captured variables are only borrowed for that call. Other iterators can
+/// be `.collect()`ed into a `Vec` first.
+///
+/// ## Example
+///
+/// ```rust
+/// use ironhtml::typed::Element;
+/// use ironhtml_elements::{Li, Ul};
+///
+/// let names = ["Ada", "Grace"];
+/// let list = Element::<Ul>::new()
+///     .append_child(names.iter().map(|name| Element::<Li>::new().text(*name)));
+/// assert_eq!(list.render(), "<ul><li>Ada</li><li>Grace</li></ul>");
+/// ```
+impl<P, I, F, T> IntoChild<P> for core::iter::Map<I, F>
+where
+    I: Iterator,
+    F: FnMut(I::Item) -> T,
+    T: IntoChild<P>,
+{
+    fn push_into(self, nodes: &mut Vec<TypedNode>) {
+        for child in self {
+            child.push_into(nodes);
+        }
+    }
+}
+
+/// Like `Map`, for `items.iter().filter_map(...)`, which skips the items
+/// that map to `None`.
+impl<P, I, F, T> IntoChild<P> for core::iter::FilterMap<I, F>
+where
+    I: Iterator,
+    F: FnMut(I::Item) -> Option<T>,
+    T: IntoChild<P>,
+{
+    fn push_into(self, nodes: &mut Vec<TypedNode>) {
+        for child in self {
+            child.push_into(nodes);
+        }
+    }
+}
+
+/// Appends each child in order, checked like
+/// [`extend_children`](Element::extend_children).
+impl<E: HtmlElement, C: IntoChild<E>> Extend<C> for Element<E> {
+    fn extend<I: IntoIterator<Item = C>>(&mut self, iter: I) {
+        for child in iter {
+            child.push_into(&mut self.children);
         }
     }
 }
@@ -118,13 +399,64 @@ impl TypedNode {
 /// - The tag name (via `E::TAG`)
 /// - Whether it's a void element (via `E::VOID`)
 /// - Which children are allowed (via `CanContain<Child>` implementations)
-#[derive(Debug, Clone)]
+///
+/// Rendering is deterministic: attributes are written in the order they
+/// were first set, and repeated `.class()` calls extend the `class`
+/// attribute in place, so the same builder calls always give byte-identical
+/// output, which keeps snapshot tests stable. For a canonical order that
+/// does not depend on the builder calls, render with
+/// [`RenderOptions::sort_attributes`].
 pub struct Element<E: HtmlElement> {
+    tag: &'static str,
     attrs: Vec<(Cow<'static, str>, String)>,
+    key: Option<String>,
     children: Vec<TypedNode>,
     _marker: PhantomData<E>,
 }
 
+impl<E: HtmlElement> Clone for Element<E> {
+    fn clone(&self) -> Self {
+        Self {
+            tag: self.tag,
+            attrs: self.attrs.clone(),
+            key: self.key.clone(),
+            children: self.children.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<E: HtmlElement> core::fmt::Debug for Element<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Element")
+            .field("tag", &self.tag)
+            .field("attrs", &self.attrs)
+            .field("key", &self.key)
+            .field("children", &self.children)
+            .finish()
+    }
+}
+
+impl<E: HtmlElement> PartialEq for Element<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.tag == other.tag
+            && self.attrs == other.attrs
+            && self.key == other.key
+            && self.children == other.children
+    }
+}
+
+impl<E: HtmlElement> Eq for Element<E> {}
+
+impl<E: HtmlElement> core::hash::Hash for Element<E> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.tag.hash(state);
+        self.attrs.hash(state);
+        self.key.hash(state);
+        self.children.hash(state);
+    }
+}
+
 impl<E: HtmlElement> Default for Element<E> {
     fn default() -> Self {
         Self::new()
@@ -135,522 +467,5045 @@ impl<E: HtmlElement> Element<E> {
     /// Create a new empty element.
     #[must_use]
     pub const fn new() -> Self {
+        const {
+            assert!(
+                !E::TAG.is_empty(),
+                "a custom element needs a name; use Element::<Custom>::named"
+            );
+        }
         Self {
+            tag: E::TAG,
             attrs: Vec::new(),
+            key: None,
             children: Vec::new(),
             _marker: PhantomData,
         }
     }
 
     /// Add an attribute with a string value.
+    ///
+    /// `class`, `rel`, `headers`, and `aria-describedby` hold token lists:
+    /// setting one again appends the new tokens to the existing attribute,
+    /// in order, skipping duplicates. So
+    /// `.class("a").classes(["b", "c"]).class("d").class("a")` renders
+    /// `class="a b c d"`, whether the tokens come from `.class()`,
+    /// `.attr()`, or a typed setter such as [`Element::<Td>::headers`].
     #[must_use]
     pub fn attr(mut self, name: impl Into<Cow<'static, str>>, value: impl Into<String>) -> Self {
-        self.attrs.push((name.into(), value.into()));
+        self.set_attr(name, value);
         self
     }
 
     /// Add an attribute with a type-safe value.
+    ///
+    /// For a boolean attribute, presence alone means true, so a `bool`
+    /// value of `true` renders just the name and `false` adds nothing;
+    /// `disabled="false"` would still disable the element. Other values,
+    /// including the strings `"true"` and `"false"`, are written as given.
+    /// The boolean attributes are `allowfullscreen`, `async`, `autofocus`,
+    /// `autoplay`, `checked`, `controls`, `default`, `defer`, `disabled`,
+    /// `formnovalidate`, `hidden`, `inert`, `ismap`, `itemscope`, `loop`,
+    /// `multiple`, `muted`, `nomodule`, `novalidate`, `open`,
+    /// `playsinline`, `readonly`, `required`, `reversed`, and `selected`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_elements::Input;
+    ///
+    /// let input = Element::<Input>::new()
+    ///     .attr_value("required", &true)
+    ///     .attr_value("disabled", &false);
+    /// assert_eq!(input.render(), "<input required />");
+    /// ```
     #[must_use]
     pub fn attr_value<V: AttributeValue>(
         mut self,
         name: impl Into<Cow<'static, str>>,
         value: &V,
     ) -> Self {
-        self.attrs.push((name.into(), value.to_attr_value().into()));
+        let name = name.into();
+        if crate::is_boolean_attr(&name) {
+            match value.as_bool() {
+                Some(true) => return self.flag(name),
+                Some(false) => return self,
+                None => {}
+            }
+        }
+        self.set_attr(name, value.to_attr_value());
         self
     }
 
-    /// Add a boolean attribute (no value, e.g., `disabled`, `checked`).
+    /// Add an attribute only if `condition` is true.
+    ///
+    /// The value is added as by [`attr_value`](Self::attr_value), so `true`
+    /// for a boolean attribute such as `disabled` renders just its name.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_elements::Button;
+    ///
+    /// let saving = true;
+    /// let button = Element::<Button>::new()
+    ///     .class("btn")
+    ///     .attr_if(saving, "disabled", true)
+    ///     .attr_if(!saving, "title", "Save");
+    /// assert_eq!(button.render(), r#"<button class="btn" disabled></button>"#);
+    /// ```
+    #[must_use]
+    #[allow(clippy::needless_pass_by_value)] // by value so literals need no `&`
+    pub fn attr_if<V: AttributeValue>(
+        self,
+        condition: bool,
+        name: impl Into<Cow<'static, str>>,
+        value: V,
+    ) -> Self {
+        if condition {
+            self.attr_value(name, &value)
+        } else {
+            self
+        }
+    }
+
+    /// Add an attribute only if `value` is `Some`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_elements::Input;
+    ///
+    /// let placeholder: Option<&'static str> = None;
+    /// let max_length = Some(40_u32);
+    /// let input = Element::<Input>::new()
+    ///     .attr_opt("placeholder", placeholder)
+    ///     .attr_opt("maxlength", max_length);
+    /// assert_eq!(input.render(), r#"<input maxlength="40" />"#);
+    /// ```
     #[must_use]
-    pub fn bool_attr(mut self, name: impl Into<Cow<'static, str>>) -> Self {
-        self.attrs.push((name.into(), String::new()));
-        self
+    pub fn attr_opt<V: AttributeValue>(
+        self,
+        name: impl Into<Cow<'static, str>>,
+        value: Option<V>,
+    ) -> Self {
+        match value {
+            Some(value) => self.attr_value(name, &value),
+            None => self,
+        }
     }
 
-    /// Add a class. Multiple calls append to the class list.
+    /// Add attributes from name-value pairs, such as a
+    /// `Vec<(String, String)>` built at runtime. The `html!` macro calls
+    /// this for an `..expr` spread.
+    ///
+    /// Values are escaped when rendered, like any attribute value. If a
+    /// name is already set, the last value wins and replaces it in place,
+    /// except that token-list attributes such as `class` merge their tokens
+    /// as with [`attr`](Self::attr). An empty value renders as a boolean
+    /// attribute.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if a name is empty or contains whitespace, a
+    /// control character, a quote, `>`, `/`, or `=`, since it could not be
+    /// written into the tag safely. Release builds skip such a pair.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_elements::Div;
+    ///
+    /// let extra = vec![
+    ///     ("data-id".to_string(), "7".to_string()),
+    ///     ("title".to_string(), "\"Hi\"".to_string()),
+    ///     ("data-id".to_string(), "8".to_string()),
+    /// ];
+    /// let div = Element::<Div>::new().class("card").attrs(extra);
+    /// assert_eq!(
+    ///     div.render(),
+    ///     r#"<div class="card" data-id="8" title="&quot;Hi&quot;"></div>"#
+    /// );
+    /// ```
     #[must_use]
-    pub fn class(mut self, class: impl Into<String>) -> Self {
-        let class = class.into();
-        if let Some(pos) = self.attrs.iter().position(|(k, _)| k == "class") {
-            self.attrs[pos].1.push(' ');
-            self.attrs[pos].1.push_str(&class);
-        } else {
-            self.attrs.push((Cow::Borrowed("class"), class));
+    pub fn attrs<I, K, V>(mut self, attrs: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<Cow<'static, str>>,
+        V: Into<String>,
+    {
+        for (name, value) in attrs {
+            let name = name.into();
+            let valid = is_attr_name(&name);
+            debug_assert!(valid, "invalid attribute name {name:?}");
+            if !valid {
+                continue;
+            }
+            if crate::is_token_list_attr(&name) {
+                self.set_attr(name, value);
+            } else if let Some(pos) = self.attrs.iter().position(|(k, _)| *k == name) {
+                self.attrs[pos].1 = value.into();
+            } else {
+                self.attrs.push((name, value.into()));
+            }
         }
         self
     }
 
-    /// Add an id attribute.
+    /// Add a boolean attribute (no value, e.g., `disabled`, `checked`),
+    /// rendered as just its name: `<input required />`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_elements::Input;
+    ///
+    /// let input = Element::<Input>::new().flag("required");
+    /// assert_eq!(input.render(), "<input required />");
+    /// ```
     #[must_use]
-    pub fn id(self, id: impl Into<String>) -> Self {
-        self.attr("id", id)
+    pub fn flag(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.attrs.push((name.into(), String::new()));
+        self
     }
 
-    /// Add a data-* attribute.
+    /// Add a boolean attribute. Same as [`flag`](Self::flag).
     #[must_use]
-    pub fn data(self, name: &str, value: impl Into<String>) -> Self {
-        let attr_name = alloc::format!("data-{name}");
-        self.attr(attr_name, value)
+    pub fn bool_attr(self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.flag(name)
     }
 
-    /// Add a child element.
+    /// Set the key that identifies this element among its siblings, so
+    /// client-side code can match rendered items to their data across
+    /// reorders.
     ///
-    /// The child type must be allowed by the parent's content model.
-    /// This is enforced at compile time via the `CanContain` trait.
+    /// The key is not an attribute: it is left out of the HTML unless
+    /// rendered with [`RenderOptions::keys`], which writes it as
+    /// `data-ih-key`, and serialized as `"key"` with the `serde` feature.
+    /// Setting it again replaces it. The `html!` macro sets it with
+    /// `li.key(id)`.
+    ///
+    /// [`hydration_key`](Self::hydration_key) sets the same key and also
+    /// makes it an attribute, so it renders without `RenderOptions::keys`.
+    /// Use it when client-side code reads the key from every render.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml::RenderOptions;
+    /// use ironhtml_elements::Li;
+    ///
+    /// let item = Element::<Li>::new().key(7).text("Ada");
+    /// assert_eq!(item.render(), "<li>Ada</li>");
+    /// assert_eq!(
+    ///     item.render_with(&RenderOptions::new().keys(true)),
+    ///     r#"<li data-ih-key="7">Ada</li>"#
+    /// );
+    /// ```
     #[must_use]
-    pub fn child<C, F>(mut self, f: F) -> Self
-    where
-        E: CanContain<C>,
-        C: HtmlElement,
-        F: FnOnce(Element<C>) -> Element<C>,
-    {
-        let child = f(Element::<C>::new());
-        self.children.push(child.into_node());
+    pub fn key(mut self, key: impl core::fmt::Display) -> Self {
+        let key = key.to_string();
+        if let Some((_, value)) = self.attrs.iter_mut().find(|(n, _)| n == crate::KEY_ATTR) {
+            value.clone_from(&key);
+        }
+        self.key = Some(key);
         self
     }
 
-    /// Add text content.
+    /// Set the key, as with [`key`](Self::key), and always render it as a
+    /// `data-ih-key` attribute for client-side hydration.
     ///
-    /// Only available for elements that can contain text (via `CanContain<Text>`).
+    /// There is one key per element: this and `key` replace each other's
+    /// value, and rendering with [`RenderOptions::keys`] does not write
+    /// the attribute twice. The `html!` macro sets it for every item of a
+    /// keyed loop (`for item in #items use key = item.id { ... }`).
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml::RenderOptions;
+    /// use ironhtml_elements::Li;
+    ///
+    /// let item = Element::<Li>::new().hydration_key(7).text("Ada");
+    /// assert_eq!(item.render(), r#"<li data-ih-key="7">Ada</li>"#);
+    /// assert_eq!(item.clone().key(8).render(), r#"<li data-ih-key="8">Ada</li>"#);
+    /// assert_eq!(
+    ///     item.render_with(&RenderOptions::new().keys(true)),
+    ///     r#"<li data-ih-key="7">Ada</li>"#
+    /// );
+    /// ```
     #[must_use]
-    pub fn text(mut self, content: impl Into<String>) -> Self
-    where
-        E: CanContain<Text>,
-    {
-        self.children.push(TypedNode::Text(content.into()));
-        self
+    pub fn hydration_key(self, key: impl core::fmt::Display) -> Self {
+        let key = key.to_string();
+        self.replace_attr(crate::KEY_ATTR, key.clone()).key(key)
     }
 
-    /// Add raw HTML content (not escaped).
+    /// Add a class. Multiple calls append to the class list.
     ///
-    /// Use with caution - this bypasses XSS protection.
+    /// `class` may hold several space-separated classes; use
+    /// [`class_token`](Self::class_token) to insist on exactly one.
     #[must_use]
-    pub fn raw(mut self, html: impl Into<String>) -> Self
-    where
-        E: CanContain<Text>,
-    {
-        self.children.push(TypedNode::Raw(html.into()));
+    pub fn class(mut self, class: impl Into<String>) -> Self {
+        self.set_class(class);
         self
     }
 
-    /// Add multiple children from an iterator.
+    /// Add a single class.
+    ///
+    /// In debug builds, panics if `class` contains whitespace, which
+    /// usually means several classes were passed by mistake; use
+    /// [`classes`](Self::classes) to add several at once.
     #[must_use]
-    pub fn children<C, I, F>(mut self, items: I, f: F) -> Self
-    where
-        E: CanContain<C>,
-        C: HtmlElement,
-        I: IntoIterator,
-        F: Fn(I::Item, Element<C>) -> Element<C>,
-    {
-        for item in items {
-            let child = f(item, Element::<C>::new());
-            self.children.push(child.into_node());
+    pub fn class_token(self, class: impl Into<String>) -> Self {
+        let class = class.into();
+        crate::debug_check_class(&class);
+        self.class(class)
+    }
+
+    /// Set an attribute, overwriting its value in place if already present.
+    fn replace_attr(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        let value = value.into();
+        if let Some(pos) = self.attrs.iter().position(|(k, _)| k == name) {
+            self.attrs[pos].1 = value;
+        } else {
+            self.attrs.push((Cow::Borrowed(name), value));
         }
         self
     }
 
-    /// Conditionally add content.
-    #[must_use]
-    pub fn when<F>(self, condition: bool, f: F) -> Self
-    where
-        F: FnOnce(Self) -> Self,
-    {
-        if condition {
-            f(self)
+    /// Set the `cite` URL shared by the quotation and edit elements.
+    fn cite_attr(self, url: String) -> Self {
+        debug_assert!(!url.trim().is_empty(), "cite URL cannot be empty");
+        self.replace_attr("cite", url)
+    }
+
+    /// Set a numeric attribute, leaving it off if `value` is NaN or
+    /// infinite, which have no valid HTML representation.
+    fn float_attr(self, name: &'static str, value: f64) -> Self {
+        if value.is_finite() {
+            self.attr(name, value.to_string())
         } else {
             self
         }
     }
 
-    /// Conditionally add content with else branch.
+    /// Add several classes, one token per item.
+    ///
+    /// In debug builds, panics if an item contains whitespace.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_elements::Div;
+    ///
+    /// let div = Element::<Div>::new().classes(["card", "shadow"]);
+    /// assert_eq!(div.render(), r#"<div class="card shadow"></div>"#);
+    /// ```
     #[must_use]
-    pub fn when_else<F, G>(self, condition: bool, if_true: F, if_false: G) -> Self
+    pub fn classes<I>(mut self, classes: I) -> Self
     where
-        F: FnOnce(Self) -> Self,
-        G: FnOnce(Self) -> Self,
+        I: IntoIterator,
+        I::Item: AsRef<str>,
     {
+        for class in classes {
+            let class = class.as_ref();
+            crate::debug_check_class(class);
+            self = self.class(class);
+        }
+        self
+    }
+
+    /// Add a class only if `condition` is true.
+    ///
+    /// Like [`class`](Self::class), a class already present is not added
+    /// again, and skipped classes leave no extra whitespace.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_elements::Button;
+    ///
+    /// let (primary, disabled) = (true, false);
+    /// let button = Element::<Button>::new()
+    ///     .class("btn")
+    ///     .class_if(primary, "btn-primary")
+    ///     .class_if(disabled, "disabled")
+    ///     .class_if(primary, "btn");
+    /// assert_eq!(button.render(), r#"<button class="btn btn-primary"></button>"#);
+    /// ```
+    #[must_use]
+    pub fn class_if(self, condition: bool, class: impl Into<String>) -> Self {
         if condition {
-            if_true(self)
+            self.class(class)
         } else {
-            if_false(self)
+            self
         }
     }
 
-    /// Convert this element into a renderable node.
+    /// Add an id attribute.
+    ///
+    /// In debug builds, panics if `id` contains whitespace, which HTML
+    /// forbids.
     #[must_use]
-    pub fn into_node(self) -> TypedNode {
-        TypedNode::Element {
-            tag: E::TAG,
-            is_void: E::VOID,
-            attrs: self.attrs,
-            children: self.children,
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.set_id(id);
+        self
+    }
+
+    /// Add an attribute in place. The `&mut` counterpart of
+    /// [`attr`](Self::attr), for setting attributes in a loop or behind
+    /// conditions without rebinding.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_elements::Input;
+    ///
+    /// let mut input = Element::<Input>::new();
+    /// for (name, value) in [("type", "text"), ("name", "q")] {
+    ///     input.set_attr(name, value);
+    /// }
+    /// input.set_class("search").set_id("q");
+    /// assert_eq!(
+    ///     input.render(),
+    ///     r#"<input type="text" name="q" class="search" id="q" />"#
+    /// );
+    /// ```
+    pub fn set_attr(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        value: impl Into<String>,
+    ) -> &mut Self {
+        let name = name.into();
+        let value = value.into();
+        if !crate::is_token_list_attr(&name) {
+            self.attrs.push((name, value));
+        } else if let Some(pos) = self.attrs.iter().position(|(k, _)| *k == name) {
+            crate::merge_tokens(&mut self.attrs[pos].1, &value);
+        } else {
+            let mut tokens = String::new();
+            crate::merge_tokens(&mut tokens, &value);
+            self.attrs.push((name, tokens));
         }
+        self
     }
 
-    /// Render this element to a string.
+    /// Add a class in place. The `&mut` counterpart of [`class`](Self::class).
+    pub fn set_class(&mut self, class: impl Into<String>) -> &mut Self {
+        self.set_attr("class", class.into())
+    }
+
+    /// Add an id in place. The `&mut` counterpart of [`id`](Self::id).
+    pub fn set_id(&mut self, id: impl Into<String>) -> &mut Self {
+        let id = id.into();
+        crate::debug_check_id(&id);
+        self.set_attr("id", id)
+    }
+
+    /// Add a `data-*` attribute, read by scripts as `element.dataset`.
+    ///
+    /// `name` is the part after `data-`. HTML requires it to be
+    /// XML-compatible and free of ASCII uppercase letters: the browser
+    /// lowercases them anyway, so `dataset` lookups written for the original
+    /// casing would miss. Write `"user-id"` for `dataset.userId`.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `name` is empty, contains an ASCII
+    /// uppercase letter, or contains a character not allowed in an XML name,
+    /// such as whitespace or `:`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_elements::Button;
+    ///
+    /// let button = Element::<Button>::new().data("user-id", "42");
+    /// assert_eq!(button.render(), r#"<button data-user-id="42"></button>"#);
+    /// ```
     #[must_use]
-    pub fn render(&self) -> String {
-        let mut output = String::new();
-        self.render_to(&mut output);
-        output
+    pub fn data(self, name: &str, value: impl Into<String>) -> Self {
+        debug_assert!(
+            is_custom_attr_suffix(name),
+            "invalid data attribute name {name:?}: use no uppercase ASCII letters, whitespace, or ':'"
+        );
+        let attr_name = alloc::format!("data-{name}");
+        self.attr(attr_name, value)
     }
 
-    /// Render this element to an existing string buffer.
-    pub fn render_to(&self, output: &mut String) {
-        crate::render_element_to(
-            output,
-            E::TAG,
-            E::VOID,
-            &self.attrs,
-            |out| {
-                for child in &self.children {
-                    child.render_to(out);
-                }
-            },
-            !self.children.is_empty(),
+    /// Add an `aria-*` attribute, such as `aria-label` or `aria-expanded`.
+    ///
+    /// `name` is the part after `aria-`. ARIA attribute names are all
+    /// lowercase, as in `"describedby"`.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `name` is empty, starts with `aria-`, or
+    /// breaks the same rules as [`data`](Self::data).
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_elements::Button;
+    ///
+    /// let button = Element::<Button>::new()
+    ///     .aria("expanded", false)
+    ///     .aria("controls", "menu");
+    /// assert_eq!(
+    ///     button.render(),
+    ///     r#"<button aria-expanded="false" aria-controls="menu"></button>"#
+    /// );
+    /// ```
+    #[must_use]
+    #[allow(clippy::needless_pass_by_value)] // by value so literals need no `&`
+    pub fn aria<V: AttributeValue>(self, name: &str, value: V) -> Self {
+        debug_assert!(
+            is_custom_attr_suffix(name) && !name.starts_with("aria-"),
+            "invalid ARIA attribute name {name:?}: leave out the aria- prefix"
         );
+        let attr_name = alloc::format!("aria-{name}");
+        self.attr(attr_name, value.to_attr_value())
     }
-}
 
-/// A typed HTML document builder.
-#[derive(Debug, Clone, Default)]
-pub struct Document {
-    nodes: Vec<TypedNode>,
-}
+    /// Set the ARIA `role`.
+    #[must_use]
+    pub fn role(self, role: Role) -> Self {
+        self.attr("role", role.to_attr_value())
+    }
 
-impl Document {
-    /// Create a new empty document.
+    /// Make the element a live region, announced by screen readers when its
+    /// content changes.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_attributes::{AriaLive, Role};
+    /// use ironhtml_elements::Div;
+    ///
+    /// let status = Element::<Div>::new()
+    ///     .role(Role::Status)
+    ///     .aria_live(AriaLive::Polite)
+    ///     .text("Saved");
+    /// assert_eq!(
+    ///     status.render(),
+    ///     r#"<div role="status" aria-live="polite">Saved</div>"#
+    /// );
+    /// ```
     #[must_use]
-    pub const fn new() -> Self {
-        Self { nodes: Vec::new() }
+    pub fn aria_live(self, live: AriaLive) -> Self {
+        self.attr("aria-live", live.to_attr_value())
     }
 
-    /// Add the HTML5 doctype declaration.
+    /// Set `aria-busy`, which asks assistive technologies to hold off
+    /// announcing a live region until an update is complete.
     #[must_use]
-    pub fn doctype(mut self) -> Self {
-        self.nodes
-            .push(TypedNode::Raw("<!DOCTYPE html>".to_string()));
-        self
+    pub fn aria_busy(self, busy: bool) -> Self {
+        self.attr("aria-busy", busy.to_attr_value())
     }
 
-    /// Add a root element.
+    /// Set `aria-atomic`, which makes a live region announce its whole
+    /// content on change rather than only the changed part.
     #[must_use]
-    pub fn root<E, F>(mut self, f: F) -> Self
+    pub fn aria_atomic(self, atomic: bool) -> Self {
+        self.attr("aria-atomic", atomic.to_attr_value())
+    }
+
+    /// Set `tabindex`.
+    ///
+    /// `0` puts the element in the tab order at its place in the document,
+    /// and `-1` makes it focusable from script but skips it when tabbing.
+    /// Positive values move the element ahead of everything else in the tab
+    /// order; they are discouraged, as the resulting order rarely matches
+    /// the visual one and is hard to maintain.
+    #[must_use]
+    pub fn tabindex(self, index: i32) -> Self {
+        self.replace_attr(ironhtml_attributes::global::TABINDEX, index.to_string())
+    }
+
+    /// Set the inline `style`, replacing any `style` already set.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_attributes::InlineStyle;
+    /// use ironhtml_elements::Div;
+    ///
+    /// let style = InlineStyle::new().color("red").margin("0 auto");
+    /// let div = Element::<Div>::new().style(&style);
+    /// assert_eq!(
+    ///     div.render(),
+    ///     r#"<div style="color: red; margin: 0 auto"></div>"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn style(self, style: &InlineStyle) -> Self {
+        self.replace_attr(ironhtml_attributes::global::STYLE, style.to_attr_value())
+    }
+
+    /// Set the keyboard shortcut that focuses or activates the element.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `key` is whitespace.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_elements::Button;
+    ///
+    /// let save = Element::<Button>::new().accesskey('s').tabindex(-1).text("Save");
+    /// assert_eq!(
+    ///     save.render(),
+    ///     r#"<button accesskey="s" tabindex="-1">Save</button>"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn accesskey(self, key: char) -> Self {
+        self.accesskeys([key])
+    }
+
+    /// Set several alternative keyboard shortcuts, in order of preference;
+    /// the browser uses the first one the keyboard has.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if a key is whitespace or `keys` is empty.
+    #[must_use]
+    pub fn accesskeys(self, keys: impl IntoIterator<Item = char>) -> Self {
+        let mut value = String::new();
+        for key in keys {
+            debug_assert!(!key.is_whitespace(), "accesskey cannot be whitespace");
+            if !value.is_empty() {
+                value.push(' ');
+            }
+            value.push(key);
+        }
+        debug_assert!(!value.is_empty(), "accesskey needs at least one key");
+        self.replace_attr(ironhtml_attributes::global::ACCESSKEY, value)
+    }
+
+    /// Set an inline event handler, such as `onclick`, replacing any
+    /// handler already set for that event.
+    ///
+    /// The code is escaped like any attribute value, so quotes and `&` in
+    /// it cannot end the attribute early, and the browser sees the code
+    /// exactly as written.
+    ///
+    /// # Security
+    ///
+    /// Escaping keeps the code inside the attribute, but the code itself
+    /// runs as written: never build it from untrusted input, where a value
+    /// such as `'); steal(); ('` becomes part of the script. Pass data to
+    /// scripts through `data-*` attributes instead and read them from the
+    /// handler. Inline handlers are also blocked by a Content Security
+    /// Policy unless it allows `'unsafe-inline'` or the handler's hash, so
+    /// prefer `addEventListener` in a script where a CSP is in place.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_attributes::EventHandler;
+    /// use ironhtml_elements::Button;
+    ///
+    /// let button = Element::<Button>::new()
+    ///     .on(EventHandler::Click, r#"alert("Hi & bye")"#)
+    ///     .text("Greet");
+    /// assert_eq!(
+    ///     button.render(),
+    ///     r#"<button onclick="alert(&quot;Hi &amp; bye&quot;)">Greet</button>"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn on(self, event: EventHandler, js: impl Into<String>) -> Self {
+        self.replace_attr(event.attr_name(), js)
+    }
+
+    /// Add a child element.
+    ///
+    /// The child type must be allowed by the parent's content model.
+    /// This is enforced at compile time via the `CanContain` trait.
+    #[must_use]
+    pub fn child<C, F>(mut self, f: F) -> Self
     where
-        E: HtmlElement,
-        F: FnOnce(Element<E>) -> Element<E>,
+        E: CanContain<C>,
+        C: HtmlElement,
+        F: FnOnce(Element<C>) -> Element<C>,
     {
-        let elem = f(Element::<E>::new());
-        self.nodes.push(elem.into_node());
+        let child = f(Element::<C>::new());
+        self.children.push(child.into_node());
         self
     }
 
-    /// Add raw HTML at the document level.
+    /// Append a child built elsewhere, such as one returned by a function.
+    ///
+    /// The child must be allowed by the parent's content model; see
+    /// [`IntoChild`].
     #[must_use]
-    pub fn raw(mut self, html: impl Into<String>) -> Self {
-        self.nodes.push(TypedNode::Raw(html.into()));
+    pub fn append_child(mut self, child: impl IntoChild<E>) -> Self {
+        child.push_into(&mut self.children);
         self
     }
 
-    /// Build the final HTML string.
+    /// Append every child produced by an iterator, in order.
+    ///
+    /// Equivalent to calling [`append_child`](Self::append_child) for each
+    /// item; the children are added directly to this element, not wrapped.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_elements::{Li, Ul};
+    ///
+    /// let rows: Vec<Element<Li>> = ["a", "b"]
+    ///     .into_iter()
+    ///     .map(|s| Element::<Li>::new().text(s))
+    ///     .collect();
+    /// let list = Element::<Ul>::new().extend_children(rows);
+    /// assert_eq!(list.render(), "<ul><li>a</li><li>b</li></ul>");
+    /// ```
     #[must_use]
-    pub fn build(&self) -> String {
-        let mut output = String::new();
-        for node in &self.nodes {
-            node.render_to(&mut output);
+    pub fn extend_children<C, I>(mut self, children: I) -> Self
+    where
+        C: IntoChild<E>,
+        I: IntoIterator<Item = C>,
+    {
+        for child in children {
+            child.push_into(&mut self.children);
         }
-        output
+        self
     }
 
-    /// Render the document to a string (alias for `build`).
+    /// Insert a child before all existing children.
+    ///
+    /// Useful for transforms such as adding a `<caption>` to a table that
+    /// has already been built. Every existing child is shifted one position,
+    /// so this is O(n) in the number of children; prefer appending when
+    /// building in order.
     #[must_use]
-    pub fn render(&self) -> String {
-        self.build()
+    pub fn prepend_child(self, child: impl IntoChild<E>) -> Self {
+        self.insert_child(0, child)
     }
 
-    /// Write the document to a file.
+    /// Insert a child at `index`, shifting later children to the right.
     ///
-    /// Requires the `std` feature.
+    /// Like [`Vec::insert`], this is O(n) in the number of children after
+    /// `index`.
     ///
-    /// # Errors
+    /// # Panics
     ///
-    /// Returns an error if the file cannot be written.
+    /// Panics if `index` is greater than the current number of children.
+    #[must_use]
+    pub fn insert_child(mut self, index: usize, child: impl IntoChild<E>) -> Self {
+        let mut nodes = Vec::new();
+        child.push_into(&mut nodes);
+        self.children.splice(index..index, nodes);
+        self
+    }
+
+    /// Add text content.
+    ///
+    /// Only available for elements that can contain text (via `CanContain<Text>`).
+    /// Text added right after other text, such as a literal followed by an
+    /// interpolated value in `html!`, joins the same node; the rendered
+    /// output is unchanged.
+    ///
+    /// Text in a raw text element ([`HtmlElement::RAW_TEXT`]: `<script>`,
+    /// `<style>`) is written unescaped, as [`Element::<Script>::inline`]
+    /// does, so `a < b` stays intact.
+    #[must_use]
+    pub fn text(mut self, content: impl Into<String>) -> Self
+    where
+        E: CanContain<Text>,
+    {
+        crate::push_text(&mut self.children, content.into());
+        self
+    }
+
+    /// Add any [`Display`](core::fmt::Display) value as text, such as a
+    /// number or a date. This is what `#expr` in [`html!`](crate::html)
+    /// calls.
+    ///
+    /// Escaping happens after formatting, on the string the `Display` impl
+    /// produced, exactly as for [`text`](Self::text): a value that formats
+    /// as `<b>` shows the characters `<b>`, not bold text. The value is
+    /// formatted straight into the element's text, without an intermediate
+    /// `String`.
     ///
     /// ## Example
     ///
     /// ```rust
-    /// use ironhtml::typed::Document;
-    /// use ironhtml_elements::{Html, Head, Body, Title, P};
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_elements::Td;
     ///
-    /// let doc = Document::new()
-    ///     .doctype()
-    ///     .root::<Html, _>(|html| {
-    ///         html.child::<Head, _>(|h| h.child::<Title, _>(|t| t.text("Hello")))
-    ///             .child::<Body, _>(|b| b.child::<P, _>(|p| p.text("Hello, World!")))
-    ///     });
+    /// let cell = Element::<Td>::new().text_display(42).text(" < ").text_display(1.5);
+    /// assert_eq!(cell.render(), "<td>42 &lt; 1.5</td>");
+    /// ```
+    #[must_use]
+    pub fn text_display(mut self, value: impl core::fmt::Display) -> Self
+    where
+        E: CanContain<Text>,
+    {
+        crate::push_text(&mut self.children, value);
+        self
+    }
+
+    /// Add raw HTML content, written to the output exactly as given.
     ///
-    /// // Write to a temp file
-    /// let temp_path = std::env::temp_dir().join("ironhtml_doctest.html");
-    /// doc.write_to_file(&temp_path).expect("Failed to write file");
+    /// This is the escape hatch for markup produced elsewhere, such as
+    /// rendered Markdown; in `html!`, write `@raw expr`. Any element that
+    /// accepts text accepts it, and the content is not checked against the
+    /// element's content model.
     ///
-    /// // Verify the file was written correctly
-    /// let content = std::fs::read_to_string(&temp_path).unwrap();
-    /// assert!(content.contains("<!DOCTYPE html>"));
-    /// assert!(content.contains("<title>Hello</title>"));
+    /// # Security
     ///
-    /// // Clean up
-    /// std::fs::remove_file(&temp_path).ok();
+    /// Nothing is escaped, so this bypasses the XSS protection the rest of
+    /// the builder gives. Sanitizing the content is the caller's
+    /// responsibility: pass only markup you generated yourself or ran
+    /// through an HTML sanitizer, never user input. Use
+    /// [`text`](Self::text) for anything that should display as text.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_elements::Div;
+    ///
+    /// let rendered_markdown = "<p>Hello <em>world</em></p>";
+    /// let post = Element::<Div>::new().raw_html(rendered_markdown);
+    /// assert_eq!(post.render(), "<div><p>Hello <em>world</em></p></div>");
     /// ```
-    #[cfg(feature = "std")]
-    pub fn write_to_file(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
-        std::fs::write(path, self.build())
+    #[must_use]
+    #[doc(alias = "PreEscaped", alias = "dangerously_set_inner_html")]
+    pub fn raw_html(mut self, html: impl Into<String>) -> Self
+    where
+        E: CanContain<Text>,
+    {
+        self.children.push(TypedNode::Raw(html.into()));
+        self
+    }
+
+    /// Add raw HTML content. Same as [`raw_html`](Self::raw_html), whose
+    /// security notes apply.
+    #[must_use]
+    pub fn raw(self, html: impl Into<String>) -> Self
+    where
+        E: CanContain<Text>,
+    {
+        self.raw_html(html)
+    }
+
+    /// Add multiple children from an iterator.
+    #[must_use]
+    pub fn children<C, I, F>(mut self, items: I, f: F) -> Self
+    where
+        E: CanContain<C>,
+        C: HtmlElement,
+        I: IntoIterator,
+        F: Fn(I::Item, Element<C>) -> Element<C>,
+    {
+        for item in items {
+            let child = f(item, Element::<C>::new());
+            self.children.push(child.into_node());
+        }
+        self
+    }
+
+    /// Add multiple children from an iterator, or apply `empty` instead if
+    /// the iterator yields nothing.
+    ///
+    /// The iterator is consumed once, so one-shot iterators work too. The
+    /// `html!` macro uses this for `for ... { } else { }`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_elements::{Li, Ul};
+    ///
+    /// let items: Vec<&str> = Vec::new();
+    /// let ul = Element::<Ul>::new().children_or_else(
+    ///     items,
+    ///     |item, li: Element<Li>| li.text(item),
+    ///     |ul| ul.child::<Li, _>(|li| li.text("No results")),
+    /// );
+    /// assert_eq!(ul.render(), "<ul><li>No results</li></ul>");
+    /// ```
+    #[must_use]
+    pub fn children_or_else<C, I, F, G>(self, items: I, f: F, empty: G) -> Self
+    where
+        E: CanContain<C>,
+        C: HtmlElement,
+        I: IntoIterator,
+        F: Fn(I::Item, Element<C>) -> Element<C>,
+        G: FnOnce(Self) -> Self,
+    {
+        let mut items = items.into_iter().peekable();
+        if items.peek().is_none() {
+            empty(self)
+        } else {
+            self.children(items, f)
+        }
+    }
+
+    /// Conditionally add content.
+    #[must_use]
+    pub fn when<F>(self, condition: bool, f: F) -> Self
+    where
+        F: FnOnce(Self) -> Self,
+    {
+        if condition {
+            f(self)
+        } else {
+            self
+        }
+    }
+
+    /// Conditionally add content with else branch.
+    #[must_use]
+    pub fn when_else<F, G>(self, condition: bool, if_true: F, if_false: G) -> Self
+    where
+        F: FnOnce(Self) -> Self,
+        G: FnOnce(Self) -> Self,
+    {
+        if condition {
+            if_true(self)
+        } else {
+            if_false(self)
+        }
+    }
+
+    /// Drop this element's tag and attributes, keeping its children as a
+    /// [`Fragment`] checked against the same parent.
+    #[must_use]
+    pub fn into_fragment(self) -> Fragment<E> {
+        Fragment {
+            children: self.children,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The element's tag name: `E::TAG`, or the name given to
+    /// [`CustomElement::named`].
+    #[must_use]
+    pub const fn tag(&self) -> &'static str {
+        self.tag
+    }
+
+    /// Convert this element into a renderable node.
+    #[must_use]
+    pub fn into_node(self) -> TypedNode {
+        TypedNode::Element {
+            tag: self.tag,
+            is_void: E::VOID,
+            attrs: self.attrs,
+            key: self.key,
+            children: self.children,
+        }
+    }
+
+    /// A hash of this element's structure: tag, attributes, and children.
+    ///
+    /// Equal trees hash equal, in every process, so the hash can key a
+    /// cache of rendered fragments. It covers the tree, not the rendered
+    /// string: `.text("ab")` and `.text("a").raw("b")` render the same but
+    /// hash differently. `Element` also implements `Hash` and `Eq` for use
+    /// as a `HashMap` key directly.
+    #[must_use]
+    pub fn content_hash(&self) -> u64 {
+        crate::content_hash_of(self)
+    }
+
+    /// The number of nodes in this tree: this element, its descendant
+    /// elements, and their text and raw HTML nodes. A component added as a
+    /// child is one raw node.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_elements::{Li, Ul};
+    ///
+    /// let list = Element::<Ul>::new()
+    ///     .child::<Li, _>(|li| li.text("One"))
+    ///     .child::<Li, _>(|li| li.text("Two"));
+    /// assert_eq!(list.node_count(), 5);
+    /// assert_eq!(list.max_depth(), 3);
+    /// ```
+    #[must_use]
+    pub fn node_count(&self) -> usize {
+        1 + crate::count_nodes(&self.children)
+    }
+
+    /// The number of nodes on the longest path from this element down to
+    /// a leaf, counting both ends: an empty element has depth 1, and
+    /// `<p>text</p>` has depth 2.
+    #[must_use]
+    pub fn max_depth(&self) -> usize {
+        1 + crate::nesting_depth(&self.children)
+    }
+
+    /// A rough size in bytes of the rendered HTML, computed in one pass
+    /// over the tree.
+    ///
+    /// [`render`](Self::render) uses it to allocate its string once instead
+    /// of growing it as it goes. It is only a hint: escaped text and
+    /// pretty-printing can make the output longer.
+    #[must_use]
+    pub fn estimated_size(&self) -> usize {
+        crate::estimated_size(self.tag, &self.attrs, &self.children)
+    }
+
+    /// Render this element to a string.
+    ///
+    /// With the `validate-ids` feature, debug builds panic if two elements
+    /// in the tree have the same `id`, naming the id. Release builds never
+    /// check.
+    #[must_use]
+    pub fn render(&self) -> String {
+        let mut output = String::with_capacity(self.estimated_size());
+        self.render_to(&mut output);
+        output
+    }
+
+    /// Render this element to a string using the given options.
+    #[must_use]
+    pub fn render_with(&self, options: &RenderOptions) -> String {
+        let mut output = String::with_capacity(self.estimated_size());
+        self.render_to_with(&mut output, options);
+        output
+    }
+
+    /// Render this element to an existing string buffer.
+    pub fn render_to(&self, output: &mut String) {
+        self.render_to_with(output, &RenderOptions::default());
+    }
+
+    /// Render this element with block-level children indented on their own
+    /// lines, for reading and snapshot tests. See [`RenderOptions::pretty`].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_elements::{Li, Ul};
+    ///
+    /// let list = Element::<Ul>::new()
+    ///     .child::<Li, _>(|li| li.text("One"))
+    ///     .child::<Li, _>(|li| li.text("Two"));
+    /// assert_eq!(list.render_pretty(), "<ul>\n  <li>One</li>\n  <li>Two</li>\n</ul>");
+    /// ```
+    ///
+    /// With [`insta`](https://docs.rs/insta), pass the output to
+    /// `insta::assert_snapshot!(list.render_pretty())` to keep golden files
+    /// that diff line by line.
+    #[must_use]
+    pub fn render_pretty(&self) -> String {
+        self.render_with(&RenderOptions::new().pretty(true))
+    }
+
+    /// Render this element as well-formed XHTML, giving every attribute a
+    /// quoted value. See [`Syntax::Xhtml`].
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_elements::{Br, Input, P};
+    ///
+    /// let p = Element::<P>::new()
+    ///     .child::<Input, _>(|i| i.bool_attr("disabled"))
+    ///     .child::<Br, _>(|br| br);
+    /// assert_eq!(p.render_xhtml(), r#"<p><input disabled="" /><br /></p>"#);
+    /// ```
+    #[must_use]
+    pub fn render_xhtml(&self) -> String {
+        self.render_with(&RenderOptions::new().syntax(Syntax::Xhtml))
+    }
+
+    /// Render this element to an existing string buffer using the given options.
+    pub fn render_to_with(&self, output: &mut String, options: &RenderOptions) {
+        crate::validate_ids(&self.attrs, &self.children);
+        if options.pretty {
+            crate::render_pretty_element_to(
+                output,
+                options,
+                self.tag,
+                E::VOID,
+                &self.attrs,
+                self.key.as_deref(),
+                &self.children,
+                0,
+                None,
+            );
+            return;
+        }
+        let _ = self.write_with(output, options);
+    }
+
+    /// Write this element to `sink` tag by tag. See [`TypedNode::write_with`].
+    fn write_with<W: core::fmt::Write + ?Sized>(
+        &self,
+        sink: &mut W,
+        options: &RenderOptions,
+    ) -> core::fmt::Result {
+        if options.pretty {
+            return sink.write_str(&self.render_with(options));
+        }
+        crate::render_element_to(
+            sink,
+            options,
+            self.tag,
+            E::VOID,
+            &self.attrs,
+            self.key.as_deref(),
+            |sink| {
+                self.children.iter().try_for_each(|child| match child {
+                    TypedNode::Text(text) if E::RAW_TEXT => {
+                        sink.write_str(&escape_raw_text(text, self.tag))
+                    }
+                    _ => child.write_with(sink, options),
+                })
+            },
+            !self.children.is_empty(),
+        )
+    }
+
+    /// Pretty-print this element for debugging: the output of
+    /// [`render_pretty`](Self::render_pretty), with a
+    /// `<!-- WARN: ... -->` line before each line holding an element with a
+    /// likely mistake: an `<img>` without `alt`, or an `id` already used
+    /// earlier in the tree.
+    ///
+    /// The output is meant for reading, not serving: the comments are part
+    /// of the markup.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_elements::{Div, Img};
+    ///
+    /// let div = Element::<Div>::new().child::<Img, _>(|i| i.attr("src", "a.png"));
+    /// assert_eq!(
+    ///     div.render_debug_annotated(),
+    ///     "<!-- WARN: <img> is missing an 'alt' attribute -->\n<div><img src=\"a.png\" /></div>"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn render_debug_annotated(&self) -> String {
+        let mut output = String::new();
+        crate::render_pretty_element_to(
+            &mut output,
+            &RenderOptions::new().pretty(true),
+            self.tag,
+            E::VOID,
+            &self.attrs,
+            self.key.as_deref(),
+            &self.children,
+            0,
+            Some(&mut crate::DebugAnnotations::default()),
+        );
+        output
+    }
+}
+
+impl Element<Area> {
+    /// Set the region's `shape` and the matching `coords`, replacing any
+    /// already set.
+    ///
+    /// [`Shape::Default`] covers the whole image and removes `coords`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_attributes::Shape;
+    /// use ironhtml_elements::Area;
+    ///
+    /// let area = Element::<Area>::new()
+    ///     .shape(Shape::Rect([0, 0, 100, 50]))
+    ///     .attr("href", "/top")
+    ///     .attr("alt", "Top");
+    /// assert_eq!(
+    ///     area.render(),
+    ///     r#"<area shape="rect" coords="0,0,100,50" href="/top" alt="Top" />"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn shape(self, shape: Shape<'_>) -> Self {
+        let mut area = self.replace_attr(ironhtml_attributes::area::SHAPE, shape.to_attr_value());
+        if let Some(coords) = shape.coords() {
+            area.replace_attr(ironhtml_attributes::area::COORDS, coords)
+        } else {
+            area.attrs
+                .retain(|(name, _)| name != ironhtml_attributes::area::COORDS);
+            area
+        }
+    }
+
+    /// Set the URLs the browser notifies when the region is followed.
+    ///
+    /// Works like `ping` on `Element<A>`.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if a URL is empty or contains whitespace.
+    #[must_use]
+    pub fn ping<'a>(self, urls: impl IntoIterator<Item = &'a str>) -> Self {
+        self.replace_attr(ironhtml_attributes::area::PING, join_urls(urls))
+    }
+
+    /// Set the region's link relationship, replacing any `rel` already set.
+    ///
+    /// Works like `rel` on `Element<A>`.
+    #[must_use]
+    pub fn rel(self, rel: impl Into<RelTokens>) -> Self {
+        self.replace_attr(ironhtml_attributes::area::REL, rel.into().to_attr_value())
+    }
+}
+
+impl Element<Html> {
+    /// Set the document language.
+    ///
+    /// `lang` must have the shape of a BCP 47 language tag: one or more
+    /// `-`-separated subtags of 1 to 8 ASCII letters or digits, such as
+    /// `en`, `pt-BR`, or `zh-Hant-TW`. Only the shape is checked, not the
+    /// subtag registry.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `lang` is not shaped like a language tag.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_elements::Html;
+    ///
+    /// let html = Element::<Html>::new().lang("en");
+    /// assert_eq!(html.render(), r#"<html lang="en"></html>"#);
+    /// ```
+    #[must_use]
+    pub fn lang(self, lang: &str) -> Self {
+        debug_check_lang_tag(lang);
+        self.attr(ironhtml_attributes::global::LANG, lang)
+    }
+
+    /// Set the document language on both `lang` and `xml:lang`.
+    ///
+    /// XHTML documents served as XML read `xml:lang`, while HTML parsers read
+    /// `lang`; setting both from one value keeps them in agreement, as the
+    /// spec requires. See [`lang`](Self::lang) for the accepted shape.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `lang` is not shaped like a language tag.
+    #[must_use]
+    pub fn lang_xhtml(self, lang: &str) -> Self {
+        self.lang(lang).attr("xml:lang", lang)
+    }
+
+    /// Render this element as a complete page, prefixed with
+    /// `<!DOCTYPE html>`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_elements::Html;
+    ///
+    /// let html = Element::<Html>::new().lang("en");
+    /// assert_eq!(
+    ///     html.render_document(),
+    ///     r#"<!DOCTYPE html><html lang="en"></html>"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn render_document(&self) -> String {
+        self.render_document_with(&Doctype::Html5)
+    }
+
+    /// Render this element as a complete page, prefixed with the given
+    /// doctype, for output that must follow a legacy standard.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::{Doctype, Element};
+    /// use ironhtml_elements::Html;
+    ///
+    /// let html = Element::<Html>::new().lang("en");
+    /// let page = html.render_document_with(&Doctype::Html401Strict);
+    /// assert!(page.starts_with(r#"<!DOCTYPE HTML PUBLIC "-//W3C//DTD HTML 4.01//EN""#));
+    /// assert!(page.ends_with(r#"strict.dtd"><html lang="en"></html>"#));
+    /// ```
+    #[must_use]
+    pub fn render_document_with(&self, doctype: &Doctype) -> String {
+        let mut output = String::from(doctype.as_str());
+        self.render_to(&mut output);
+        output
+    }
+}
+
+impl Element<A> {
+    /// Mark the link as a download, optionally suggesting a filename.
+    ///
+    /// [`Download::Default`] renders a bare `download` attribute;
+    /// [`Download::Filename`] renders `download="name"`, escaped like any
+    /// other attribute value.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_attributes::Download;
+    /// use ironhtml_elements::A;
+    ///
+    /// let link = Element::<A>::new()
+    ///     .attr("href", "/report")
+    ///     .download(Download::Filename("report.pdf".into()));
+    /// assert_eq!(
+    ///     link.render(),
+    ///     r#"<a href="/report" download="report.pdf"></a>"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn download(self, download: Download) -> Self {
+        match download {
+            Download::Default => self.bool_attr(ironhtml_attributes::anchor::DOWNLOAD),
+            Download::Filename(name) => self.attr(ironhtml_attributes::anchor::DOWNLOAD, name),
+        }
+    }
+
+    /// Set the URLs the browser notifies, with a POST request, when the
+    /// link is followed. They are joined into a space-separated `ping`
+    /// list.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if a URL is empty or contains whitespace.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_elements::A;
+    ///
+    /// let link = Element::<A>::new()
+    ///     .attr("href", "/docs")
+    ///     .ping(["/track?l=docs", "https://stats.example.com/hit"]);
+    /// assert_eq!(
+    ///     link.render(),
+    ///     r#"<a href="/docs" ping="/track?l=docs https://stats.example.com/hit"></a>"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn ping<'a>(self, urls: impl IntoIterator<Item = &'a str>) -> Self {
+        self.replace_attr(ironhtml_attributes::anchor::PING, join_urls(urls))
+    }
+
+    /// Set the link relationship, replacing any `rel` already set.
+    ///
+    /// Takes one [`Rel`](ironhtml_attributes::Rel) or a [`RelTokens`] set of several.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_attributes::{Rel, RelTokens};
+    /// use ironhtml_elements::A;
+    ///
+    /// let link = Element::<A>::new()
+    ///     .attr("href", "/page/2")
+    ///     .rel(RelTokens::new().with(Rel::Next).with(Rel::Nofollow));
+    /// assert_eq!(link.render(), r#"<a href="/page/2" rel="next nofollow"></a>"#);
+    /// ```
+    #[must_use]
+    pub fn rel(self, rel: impl Into<RelTokens>) -> Self {
+        self.replace_attr(ironhtml_attributes::anchor::REL, rel.into().to_attr_value())
+    }
+
+    /// Link to `href` in a new tab, safely: sets `target="_blank"` and adds
+    /// `noopener` and `noreferrer` to `rel`, keeping any `rel` tokens
+    /// already set.
+    ///
+    /// Without `noopener`, the opened page gets a `window.opener` handle
+    /// back to this one and can navigate it elsewhere (reverse
+    /// tabnabbing). Setting `target` by hand leaves `rel` alone, for links
+    /// that need the opener.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_elements::A;
+    ///
+    /// let link = Element::<A>::new()
+    ///     .external("https://example.com")
+    ///     .text("Example");
+    /// assert_eq!(
+    ///     link.render(),
+    ///     r#"<a href="https://example.com" target="_blank" rel="noopener noreferrer">Example</a>"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn external(self, href: impl Into<String>) -> Self {
+        let mut rel: Vec<&str> = self
+            .attrs
+            .iter()
+            .find(|(name, _)| name == ironhtml_attributes::anchor::REL)
+            .map_or(Vec::new(), |(_, value)| {
+                value.split_ascii_whitespace().collect()
+            });
+        for token in ["noopener", "noreferrer"] {
+            if !rel.contains(&token) {
+                rel.push(token);
+            }
+        }
+        let rel = rel.join(" ");
+        self.replace_attr(ironhtml_attributes::anchor::HREF, href)
+            .replace_attr(ironhtml_attributes::anchor::TARGET, "_blank")
+            .replace_attr(ironhtml_attributes::anchor::REL, rel)
+    }
+}
+
+impl Element<Link> {
+    /// Set the relationship of the linked resource, such as
+    /// [`Rel::Stylesheet`](ironhtml_attributes::Rel::Stylesheet), replacing any `rel` already set.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_attributes::Rel;
+    /// use ironhtml_elements::Link;
+    ///
+    /// let css = Element::<Link>::new().rel(Rel::Stylesheet).attr("href", "/app.css");
+    /// assert_eq!(css.render(), r#"<link rel="stylesheet" href="/app.css" />"#);
+    /// ```
+    #[must_use]
+    pub fn rel(self, rel: impl Into<RelTokens>) -> Self {
+        self.replace_attr(ironhtml_attributes::link::REL, rel.into().to_attr_value())
+    }
+}
+
+/// Whether `name` can follow a `data-` or `aria-` prefix: non-empty, no
+/// ASCII uppercase letters, and only characters allowed in an XML name,
+/// without `:`.
+fn is_custom_attr_suffix(name: &str) -> bool {
+    !name.is_empty()
+        && name.chars().all(|c| {
+            c.is_ascii_lowercase()
+                || c.is_ascii_digit()
+                || matches!(c, '-' | '.' | '_' | '\u{B7}')
+                || (!c.is_ascii() && !c.is_whitespace() && !c.is_control())
+        })
+}
+
+/// Panic in debug builds unless `lang` looks like a BCP 47 language tag.
+fn debug_check_lang_tag(lang: &str) {
+    debug_assert!(
+        lang.split('-').all(|subtag| {
+            (1..=8).contains(&subtag.len()) && subtag.bytes().all(|b| b.is_ascii_alphanumeric())
+        }),
+        "invalid language tag {lang:?}"
+    );
+}
+
+/// Whether `name` can be written as an attribute name: at least one
+/// character and no whitespace, controls, quotes, `>`, `/`, or `=`.
+fn is_attr_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.chars().any(|c| {
+            c.is_whitespace() || c.is_control() || matches!(c, '"' | '\'' | '>' | '/' | '=')
+        })
+}
+
+impl Render for TypedNode {
+    fn render_into(&self, sink: &mut impl core::fmt::Write) -> core::fmt::Result {
+        self.write_with(sink, &RenderOptions::default())
+    }
+
+    fn render_to_buffer(&self, buf: &mut String) {
+        self.render_to(buf);
+    }
+}
+
+impl<E: HtmlElement> Render for Element<E> {
+    fn render_into(&self, sink: &mut impl core::fmt::Write) -> core::fmt::Result {
+        crate::validate_ids(&self.attrs, &self.children);
+        self.write_with(sink, &RenderOptions::default())
+    }
+
+    fn render_to_buffer(&self, buf: &mut String) {
+        self.render_to(buf);
+    }
+}
+
+impl Render for Document {
+    fn render_into(&self, sink: &mut impl core::fmt::Write) -> core::fmt::Result {
+        self.write_with(sink, &RenderOptions::default())
+    }
+
+    fn render_to_buffer(&self, buf: &mut String) {
+        for node in &self.nodes {
+            node.render_to(buf);
+        }
+    }
+}
+
+/// Formats as the rendered HTML, escaped exactly as by
+/// [`Element::render`], so elements work with `format!`, `write!`, and any
+/// API that takes `impl Display`. The alternate flag (`{:#}`) renders
+/// pretty, as [`Element::render_pretty`] does.
+///
+/// ## Example
+///
+/// ```rust
+/// use ironhtml::typed::Element;
+/// use ironhtml_elements::P;
+///
+/// let p = Element::<P>::new().text("Fish & chips");
+/// assert_eq!(format!("{p}"), "<p>Fish &amp; chips</p>");
+/// ```
+impl<E: HtmlElement> core::fmt::Display for Element<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        crate::validate_ids(&self.attrs, &self.children);
+        self.write_with(f, &RenderOptions::new().pretty(f.alternate()))
+    }
+}
+
+/// Formats as the rendered HTML; see the `Display` impl of [`Element`].
+impl core::fmt::Display for TypedNode {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.write_with(f, &RenderOptions::new().pretty(f.alternate()))
+    }
+}
+
+/// Formats as the rendered HTML; see the `Display` impl of [`Element`].
+impl core::fmt::Display for Document {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.write_with(f, &RenderOptions::new().pretty(f.alternate()))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for TypedNode {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::json::serialize_node(serializer, self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<E: HtmlElement> serde::Serialize for Element<E> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::json::serialize_element(
+            serializer,
+            self.tag,
+            &self.attrs,
+            self.key.as_deref(),
+            &self.children,
+        )
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Document {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::json::serialize_nodes(serializer, &self.nodes)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<E: HtmlElement> Element<E> {
+    /// Serialize this tree to JSON, for hydrating it on the client. See
+    /// [`crate::Element::to_json`] for the format. Requires the `serde`
+    /// feature.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_elements::{Br, P};
+    ///
+    /// let p = Element::<P>::new().text("a").child::<Br, _>(|br| br);
+    /// assert_eq!(
+    ///     p.to_json(),
+    ///     r#"{"tag":"p","attrs":{},"children":["a",{"tag":"br","attrs":{},"children":[]}]}"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        crate::json::to_json(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Document {
+    /// Serialize the document's root nodes to a JSON array. The doctype,
+    /// if any, is a `{"raw": ...}` entry. See [`crate::Element::to_json`].
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        crate::json::to_json(self)
+    }
+}
+
+/// Sibling nodes without a wrapper element, like a React fragment.
+///
+/// A component that returns several top-level siblings builds them into a
+/// `Fragment<P>`, where `P` is the element they are meant to go in: its
+/// children are checked against `P`'s content model exactly as with
+/// [`Element<P>`], and the fragment can only be added to a `P`; add
+/// `&fragment` to insert it anywhere else, unchecked, like any [`Render`]
+/// value. It renders its children one after another, with no tag of its
+/// own.
+///
+/// In [`html!`](crate::html), `frag { ... }` builds a fragment of flow
+/// content (`Fragment<Div>`), and `frag<ul> { ... }` names the parent.
+///
+/// ## Example
+///
+/// ```rust
+/// use ironhtml::typed::{Element, Fragment};
+/// use ironhtml_elements::{Li, Ul};
+///
+/// fn items() -> Fragment<Ul> {
+///     Fragment::new()
+///         .child::<Li, _>(|li| li.text("One"))
+///         .child::<Li, _>(|li| li.text("Two"))
+/// }
+///
+/// assert_eq!(items().render(), "<li>One</li><li>Two</li>");
+/// let list = Element::<Ul>::new().append_child(items());
+/// assert_eq!(list.render(), "<ul><li>One</li><li>Two</li></ul>");
+/// ```
+#[derive(Debug)]
+pub struct Fragment<P: HtmlElement> {
+    children: Vec<TypedNode>,
+    _marker: PhantomData<P>,
+}
+
+impl<P: HtmlElement> Clone for Fragment<P> {
+    fn clone(&self) -> Self {
+        Self {
+            children: self.children.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<P: HtmlElement> Default for Fragment<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P: HtmlElement> Fragment<P> {
+    /// Create an empty fragment.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            children: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Apply `f` to an element holding this fragment's children.
+    fn map(self, f: impl FnOnce(Element<P>) -> Element<P>) -> Self {
+        let parent = Element {
+            tag: P::TAG,
+            attrs: Vec::new(),
+            key: None,
+            children: self.children,
+            _marker: PhantomData,
+        };
+        f(parent).into_fragment()
+    }
+
+    /// Add a child element. See [`Element::child`].
+    #[must_use]
+    pub fn child<C, F>(self, f: F) -> Self
+    where
+        P: CanContain<C>,
+        C: HtmlElement,
+        F: FnOnce(Element<C>) -> Element<C>,
+    {
+        self.map(|parent| parent.child(f))
+    }
+
+    /// Add an already-built child. See [`Element::append_child`].
+    #[must_use]
+    pub fn append_child(self, child: impl IntoChild<P>) -> Self {
+        self.map(|parent| parent.append_child(child))
+    }
+
+    /// Add escaped text. See [`Element::text`].
+    #[must_use]
+    pub fn text(self, content: impl Into<String>) -> Self
+    where
+        P: CanContain<Text>,
+    {
+        self.map(|parent| parent.text(content))
+    }
+
+    /// Add raw HTML, which is not escaped. See [`Element::raw_html`].
+    #[must_use]
+    pub fn raw_html(self, html: impl Into<String>) -> Self
+    where
+        P: CanContain<Text>,
+    {
+        self.map(|parent| parent.raw_html(html))
+    }
+
+    /// Add raw HTML. Same as [`raw_html`](Self::raw_html).
+    #[must_use]
+    pub fn raw(self, html: impl Into<String>) -> Self
+    where
+        P: CanContain<Text>,
+    {
+        self.raw_html(html)
+    }
+
+    /// The fragment's nodes, in order.
+    #[must_use]
+    pub fn children(&self) -> &[TypedNode] {
+        &self.children
+    }
+
+    /// Render the fragment to a string.
+    #[must_use]
+    pub fn render(&self) -> String {
+        self.render_with(&RenderOptions::default())
+    }
+
+    /// Render the fragment to a string using the given options. With
+    /// [`RenderOptions::pretty`], each top-level node starts on its own
+    /// line, as in [`Document`].
+    #[must_use]
+    pub fn render_with(&self, options: &RenderOptions) -> String {
+        let mut output = String::new();
+        self.render_to_with(&mut output, options);
+        output
+    }
+
+    /// Render the fragment to an existing string buffer.
+    pub fn render_to(&self, output: &mut String) {
+        self.render_to_with(output, &RenderOptions::default());
+    }
+
+    /// Render the fragment to an existing string buffer using the given
+    /// options.
+    pub fn render_to_with(&self, output: &mut String, options: &RenderOptions) {
+        crate::validate_ids::<_, String>(&[], &self.children);
+        for (i, node) in self.children.iter().enumerate() {
+            if options.pretty && i > 0 {
+                output.push('\n');
+            }
+            node.render_to_with(output, options);
+        }
+    }
+
+    /// Render the fragment with block-level elements indented on their own
+    /// lines. See [`Element::render_pretty`].
+    #[must_use]
+    pub fn render_pretty(&self) -> String {
+        self.render_with(&RenderOptions::new().pretty(true))
+    }
+}
+
+/// Collects children into a fragment for parent `P`, each checked against
+/// `P`'s content model, so `items.map(row).collect::<Fragment<Ul>>()`
+/// builds a reusable list body.
+impl<P: HtmlElement, C: IntoChild<P>> FromIterator<C> for Fragment<P> {
+    fn from_iter<I: IntoIterator<Item = C>>(iter: I) -> Self {
+        let mut fragment = Self::new();
+        fragment.extend(iter);
+        fragment
+    }
+}
+
+impl<P: HtmlElement, C: IntoChild<P>> Extend<C> for Fragment<P> {
+    fn extend<I: IntoIterator<Item = C>>(&mut self, iter: I) {
+        for child in iter {
+            child.push_into(&mut self.children);
+        }
+    }
+}
+
+/// A fragment is added to its parent as its children, in order.
+impl<P: HtmlElement> IntoChild<P> for Fragment<P> {
+    fn push_into(self, nodes: &mut Vec<TypedNode>) {
+        nodes.extend(self.children);
+    }
+}
+
+/// A borrowed fragment adds copies of its children.
+impl<P: HtmlElement> IntoChild<P> for &Fragment<P> {
+    fn push_into(self, nodes: &mut Vec<TypedNode>) {
+        nodes.extend_from_slice(&self.children);
+    }
+}
+
+impl<P: HtmlElement> Render for Fragment<P> {
+    fn render_into(&self, sink: &mut impl core::fmt::Write) -> core::fmt::Result {
+        crate::validate_ids::<_, String>(&[], &self.children);
+        let options = RenderOptions::default();
+        self.children
+            .iter()
+            .try_for_each(|node| node.write_with(sink, &options))
+    }
+
+    fn render_to_buffer(&self, buf: &mut String) {
+        self.render_to(buf);
+    }
+}
+
+/// Formats as the rendered HTML; see the `Display` impl of [`Element`].
+impl<P: HtmlElement> core::fmt::Display for Fragment<P> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if f.alternate() {
+            return f.write_str(&self.render_pretty());
+        }
+        self.render_into(f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<P: HtmlElement> serde::Serialize for Fragment<P> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::json::serialize_nodes(serializer, &self.children)
+    }
+}
+
+/// An autonomous custom element (a web component), such as
+/// `<my-widget>`, whose tag is given as a string.
+///
+/// This is an [`Element`] of the [`Custom`] type, so it has the whole
+/// builder and render API. Custom elements have no content model the
+/// builder knows about, so their children are not checked: any element,
+/// text, or component can be added. The element itself is flow and
+/// phrasing content, and can be added to any parent that accepts phrasing
+/// content.
+///
+/// In [`html!`](crate::html), `element("my-widget") { ... }` builds one.
+///
+/// ## Example
+///
+/// ```rust
+/// use ironhtml::typed::{CustomElement, Element};
+/// use ironhtml_elements::{Div, Li};
+///
+/// let button = CustomElement::named("ion-button")
+///     .attr("expand", "block")
+///     .text("Save");
+/// let page = Element::<Div>::new().append_child(button);
+/// assert_eq!(
+///     page.render(),
+///     r#"<div><ion-button expand="block">Save</ion-button></div>"#
+/// );
+///
+/// // Children are unchecked: a custom list may hold `<li>`s directly.
+/// let list = CustomElement::named("my-list").child::<Li, _>(|li| li.text("One"));
+/// assert_eq!(list.render(), "<my-list><li>One</li></my-list>");
+/// ```
+///
+/// A custom element always needs a name, so `new()` is rejected when the
+/// program is built:
+///
+/// ```rust,compile_fail
+/// use ironhtml::typed::Element;
+/// use ironhtml_elements::Custom;
+///
+/// let unnamed = Element::<Custom>::new();
+/// ```
+pub type CustomElement = Element<Custom>;
+
+impl Element<Custom> {
+    /// Create an empty custom element named `tag`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tag` is not a valid custom element name (see
+    /// [`is_custom_element_name`](ironhtml_elements::is_custom_element_name)).
+    /// The tag is written to the output unescaped, so this check always
+    /// runs, not only in debug builds.
+    #[must_use]
+    pub fn named(tag: &'static str) -> Self {
+        assert!(
+            ironhtml_elements::is_custom_element_name(tag),
+            "invalid custom element name {tag:?}"
+        );
+        Self {
+            tag,
+            attrs: Vec::new(),
+            key: None,
+            children: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// A document type declaration, for [`Document::with_doctype`].
+///
+/// Void elements always render self-closed (`<br />`), which is valid in
+/// both HTML and XHTML, so an XHTML doctype only needs the `xmlns`
+/// attribute on `<html>` to produce a well-formed XHTML document.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Doctype {
+    /// `<!DOCTYPE html>`, for HTML5 documents.
+    #[default]
+    Html5,
+    /// XHTML 1.0 Strict.
+    XhtmlStrict,
+    /// XHTML 1.0 Transitional.
+    XhtmlTransitional,
+    /// HTML 4.01 Strict, for legacy consumers.
+    Html401Strict,
+    /// HTML 4.01 Transitional, for legacy consumers.
+    Html401Transitional,
+    /// A complete declaration, such as `<!DOCTYPE svg>`, written as-is
+    /// without escaping.
+    Custom(Cow<'static, str>),
+}
+
+impl Doctype {
+    /// The declaration as written to the document.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Html5 => "<!DOCTYPE html>",
+            Self::XhtmlStrict => concat!(
+                r#"<!DOCTYPE html PUBLIC "-//W3C//DTD XHTML 1.0 Strict//EN" "#,
+                r#""http://www.w3.org/TR/xhtml1/DTD/xhtml1-strict.dtd">"#
+            ),
+            Self::XhtmlTransitional => concat!(
+                r#"<!DOCTYPE html PUBLIC "-//W3C//DTD XHTML 1.0 Transitional//EN" "#,
+                r#""http://www.w3.org/TR/xhtml1/DTD/xhtml1-transitional.dtd">"#
+            ),
+            Self::Html401Strict => concat!(
+                r#"<!DOCTYPE HTML PUBLIC "-//W3C//DTD HTML 4.01//EN" "#,
+                r#""http://www.w3.org/TR/html4/strict.dtd">"#
+            ),
+            Self::Html401Transitional => concat!(
+                r#"<!DOCTYPE HTML PUBLIC "-//W3C//DTD HTML 4.01 Transitional//EN" "#,
+                r#""http://www.w3.org/TR/html4/loose.dtd">"#
+            ),
+            Self::Custom(declaration) => declaration,
+        }
+    }
+}
+
+/// A typed HTML document builder.
+#[derive(Debug, Clone, Default)]
+pub struct Document {
+    nodes: Vec<TypedNode>,
+}
+
+impl Document {
+    /// Create a new empty document.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { nodes: Vec::new() }
+    }
+
+    /// Add the HTML5 doctype declaration.
+    ///
+    /// Shorthand for `with_doctype(Doctype::Html5)`.
+    #[must_use]
+    pub fn doctype(self) -> Self {
+        self.with_doctype(Doctype::Html5)
+    }
+
+    /// Add a doctype declaration of the given kind.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::{Doctype, Document};
+    /// use ironhtml_elements::Html;
+    ///
+    /// let doc = Document::new()
+    ///     .with_doctype(Doctype::XhtmlStrict)
+    ///     .root::<Html, _>(|html| html.attr("xmlns", "http://www.w3.org/1999/xhtml"));
+    /// assert!(doc.build().starts_with(
+    ///     r#"<!DOCTYPE html PUBLIC "-//W3C//DTD XHTML 1.0 Strict//EN" "#
+    /// ));
+    /// ```
+    #[must_use]
+    pub fn with_doctype(mut self, doctype: Doctype) -> Self {
+        let declaration = match doctype {
+            Doctype::Custom(declaration) => declaration.into_owned(),
+            other => other.as_str().to_string(),
+        };
+        self.nodes.push(TypedNode::Raw(declaration));
+        self
+    }
+
+    /// Add a root element.
+    #[must_use]
+    pub fn root<E, F>(mut self, f: F) -> Self
+    where
+        E: HtmlElement,
+        F: FnOnce(Element<E>) -> Element<E>,
+    {
+        let elem = f(Element::<E>::new());
+        self.nodes.push(elem.into_node());
+        self
+    }
+
+    /// Add raw HTML at the document level.
+    #[must_use]
+    pub fn raw(mut self, html: impl Into<String>) -> Self {
+        self.nodes.push(TypedNode::Raw(html.into()));
+        self
+    }
+
+    /// Build the final HTML string.
+    #[must_use]
+    pub fn build(&self) -> String {
+        self.build_with(&RenderOptions::default())
+    }
+
+    /// Build the final HTML string using the given options.
+    #[must_use]
+    pub fn build_with(&self, options: &RenderOptions) -> String {
+        let mut output = String::new();
+        let _ = self.write_with(&mut output, options);
+        output
+    }
+
+    /// Write the root nodes to `sink`, each on its own line when pretty.
+    /// `build_with` and `Display` both go through here, so `{:#}` matches
+    /// [`Document::render_pretty`].
+    fn write_with<W: core::fmt::Write + ?Sized>(
+        &self,
+        sink: &mut W,
+        options: &RenderOptions,
+    ) -> core::fmt::Result {
+        crate::validate_ids::<_, String>(&[], &self.nodes);
+        for (i, node) in self.nodes.iter().enumerate() {
+            if options.pretty && i > 0 {
+                sink.write_char('\n')?;
+            }
+            node.write_with(sink, options)?;
+        }
+        Ok(())
+    }
+
+    /// Render the document to a string (alias for `build`).
+    #[must_use]
+    pub fn render(&self) -> String {
+        self.build()
+    }
+
+    /// Render the document with block-level elements indented on their own
+    /// lines. See [`Element::render_pretty`].
+    #[must_use]
+    pub fn render_pretty(&self) -> String {
+        self.build_with(&RenderOptions::new().pretty(true))
+    }
+
+    /// Pretty-print the document for debugging, annotating likely
+    /// mistakes. See [`Element::render_debug_annotated`].
+    #[must_use]
+    pub fn render_debug_annotated(&self) -> String {
+        let mut output = String::new();
+        crate::render_debug_annotated_to(&mut output, &self.nodes);
+        output
+    }
+
+    /// Render the document as well-formed XHTML. See [`Syntax::Xhtml`].
+    #[must_use]
+    pub fn render_xhtml(&self) -> String {
+        self.build_with(&RenderOptions::new().syntax(Syntax::Xhtml))
+    }
+
+    /// Write the document to a file.
+    ///
+    /// Requires the `std` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Document;
+    /// use ironhtml_elements::{Html, Head, Body, Title, P};
+    ///
+    /// let doc = Document::new()
+    ///     .doctype()
+    ///     .root::<Html, _>(|html| {
+    ///         html.child::<Head, _>(|h| h.child::<Title, _>(|t| t.text("Hello")))
+    ///             .child::<Body, _>(|b| b.child::<P, _>(|p| p.text("Hello, World!")))
+    ///     });
+    ///
+    /// // Write to a temp file
+    /// let temp_path = std::env::temp_dir().join("ironhtml_doctest.html");
+    /// doc.write_to_file(&temp_path).expect("Failed to write file");
+    ///
+    /// // Verify the file was written correctly
+    /// let content = std::fs::read_to_string(&temp_path).unwrap();
+    /// assert!(content.contains("<!DOCTYPE html>"));
+    /// assert!(content.contains("<title>Hello</title>"));
+    ///
+    /// // Clean up
+    /// std::fs::remove_file(&temp_path).ok();
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn write_to_file(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.build())
+    }
+}
+
+impl Element<Iframe> {
+    /// Embed a whole HTML document as the frame's content.
+    ///
+    /// Pass the document as ordinary HTML; like every attribute value it is
+    /// escaped when rendered, so quotes and `&` in it cannot end the
+    /// attribute, and the browser decodes it back to the original markup.
+    /// Any escaping inside the document itself (`&amp;` in its text) is
+    /// kept, so it ends up escaped twice in the output.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_elements::Iframe;
+    ///
+    /// let frame = Element::<Iframe>::new().srcdoc(r#"<p class="x">Hi</p>"#);
+    /// assert_eq!(
+    ///     frame.render(),
+    ///     r#"<iframe srcdoc="&lt;p class=&quot;x&quot;&gt;Hi&lt;/p&gt;"></iframe>"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn srcdoc(self, html: impl Into<String>) -> Self {
+        self.attr(ironhtml_attributes::iframe::SRCDOC, html)
+    }
+
+    /// Render `doc`, such as a [`Document`] or [`Element`], and embed the
+    /// result with [`srcdoc`](Self::srcdoc).
+    #[must_use]
+    pub fn srcdoc_element(self, doc: &(impl Render + ?Sized)) -> Self {
+        let mut html = String::new();
+        doc.render_to_buffer(&mut html);
+        self.srcdoc(html)
+    }
+}
+
+impl Element<Img> {
+    /// Set `loading`, replacing any value already set.
+    #[must_use]
+    pub fn loading(self, loading: Loading) -> Self {
+        self.replace_attr(ironhtml_attributes::img::LOADING, loading.to_attr_value())
+    }
+
+    /// Set `decoding`, replacing any value already set.
+    #[must_use]
+    pub fn decoding(self, decoding: Decoding) -> Self {
+        self.replace_attr(ironhtml_attributes::img::DECODING, decoding.to_attr_value())
+    }
+
+    /// Set the candidate files for responsive loading, replacing any
+    /// `srcset` already set. Pair width descriptors with `sizes`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_attributes::{Descriptor, SrcSet};
+    /// use ironhtml_elements::Img;
+    ///
+    /// let srcset = SrcSet::new()
+    ///     .candidate("a.jpg", Descriptor::Density(1.0))
+    ///     .candidate("a@2x.jpg", Descriptor::Density(2.0));
+    /// let img = Element::<Img>::new().attr("src", "a.jpg").srcset(&srcset);
+    /// assert_eq!(
+    ///     img.render(),
+    ///     r#"<img src="a.jpg" srcset="a.jpg 1x, a@2x.jpg 2x" />"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn srcset(self, srcset: &SrcSet) -> Self {
+        self.replace_attr(ironhtml_attributes::img::SRCSET, srcset.to_attr_value())
+    }
+}
+
+impl Element<Source> {
+    /// Set the candidate files for this `<picture>` source, replacing any
+    /// `srcset` already set. See [`Element::<Img>::srcset`].
+    #[must_use]
+    pub fn srcset(self, srcset: &SrcSet) -> Self {
+        self.replace_attr(ironhtml_attributes::source::SRCSET, srcset.to_attr_value())
+    }
+}
+
+impl Element<Input> {
+    /// Set the file types a file input accepts, as a comma-separated
+    /// `accept` list of MIME types (`image/*`) and extensions (`.pdf`).
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if a type is empty or contains a comma.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_attributes::InputType;
+    /// use ironhtml_elements::Input;
+    ///
+    /// let upload = Element::<Input>::new()
+    ///     .attr_value("type", &InputType::File)
+    ///     .accept(["image/*", ".pdf"])
+    ///     .multiple(true);
+    /// assert_eq!(
+    ///     upload.render(),
+    ///     r#"<input type="file" accept="image/*,.pdf" multiple />"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn accept<'a>(self, types: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut accept = String::new();
+        for ty in types {
+            let ty = ty.trim();
+            debug_assert!(
+                !ty.is_empty() && !ty.contains(','),
+                "invalid accept type {ty:?}"
+            );
+            if !accept.is_empty() {
+                accept.push(',');
+            }
+            accept.push_str(ty);
+        }
+        self.attr(ironhtml_attributes::input::ACCEPT, accept)
+    }
+
+    /// Allow choosing more than one file or email address. `false` leaves
+    /// the attribute off.
+    #[must_use]
+    pub fn multiple(self, multiple: bool) -> Self {
+        self.when(multiple, |e| {
+            e.bool_attr(ironhtml_attributes::input::MULTIPLE)
+        })
+    }
+
+    /// Ask a file input to capture from the given camera.
+    #[must_use]
+    pub fn capture(self, capture: Capture) -> Self {
+        self.attr_value(ironhtml_attributes::input::CAPTURE, &capture)
+    }
+}
+
+/// Numeric setters on `<meter>` and `<progress>` take `f64`. HTML has no
+/// spelling for NaN or infinity, so a non-finite value leaves the attribute
+/// off, and the browser falls back to its default, rather than rendering
+/// an invalid `value="NaN"`.
+impl Element<Meter> {
+    /// Set the current `value`. Omitted if not finite.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_elements::Meter;
+    ///
+    /// let disk = Element::<Meter>::new().min(0.0).max(1.0).value(0.25);
+    /// assert_eq!(disk.render(), r#"<meter min="0" max="1" value="0.25"></meter>"#);
+    ///
+    /// let unknown = Element::<Meter>::new().value(f64::NAN);
+    /// assert_eq!(unknown.render(), "<meter></meter>");
+    /// ```
+    #[must_use]
+    pub fn value(self, value: f64) -> Self {
+        self.float_attr("value", value)
+    }
+
+    /// Set the lower bound, `min`. Omitted if not finite.
+    #[must_use]
+    pub fn min(self, min: f64) -> Self {
+        self.float_attr("min", min)
+    }
+
+    /// Set the upper bound, `max`. Omitted if not finite.
+    #[must_use]
+    pub fn max(self, max: f64) -> Self {
+        self.float_attr("max", max)
+    }
+
+    /// Set the upper end of the low range, `low`. Omitted if not finite.
+    #[must_use]
+    pub fn low(self, low: f64) -> Self {
+        self.float_attr("low", low)
+    }
+
+    /// Set the lower end of the high range, `high`. Omitted if not finite.
+    #[must_use]
+    pub fn high(self, high: f64) -> Self {
+        self.float_attr("high", high)
+    }
+
+    /// Set the optimal value, `optimum`. Omitted if not finite.
+    #[must_use]
+    pub fn optimum(self, optimum: f64) -> Self {
+        self.float_attr("optimum", optimum)
+    }
+}
+
+impl Element<Progress> {
+    /// Set how much of the task is done, `value`. Omitted if not finite,
+    /// which leaves the progress bar indeterminate.
+    #[must_use]
+    pub fn value(self, value: f64) -> Self {
+        self.float_attr("value", value)
+    }
+
+    /// Set how much work the task needs in total, `max`. Omitted if not
+    /// finite.
+    #[must_use]
+    pub fn max(self, max: f64) -> Self {
+        self.float_attr("max", max)
+    }
+}
+
+impl Element<Script> {
+    /// Set the script `type`.
+    ///
+    /// [`ScriptType::JavaScript`] is the default, so it leaves the attribute
+    /// off rather than writing `type="text/javascript"`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_attributes::ScriptType;
+    /// use ironhtml_elements::Script;
+    ///
+    /// let script = Element::<Script>::new()
+    ///     .r#type(ScriptType::Module)
+    ///     .attr("src", "app.js");
+    /// assert_eq!(script.render(), r#"<script type="module" src="app.js"></script>"#);
+    /// ```
+    #[must_use]
+    pub fn r#type(self, script_type: ScriptType) -> Self {
+        if script_type == ScriptType::JavaScript {
+            self
+        } else {
+            self.attr("type", script_type.to_attr_value())
+        }
+    }
+
+    /// Set the inline script body.
+    ///
+    /// `<script>` is a raw text element: the browser does not decode
+    /// entities in it, so the content is written unescaped. This is what
+    /// JavaScript and JSON data blocks (JSON-LD, import maps, speculation
+    /// rules) need.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `content` contains `</script`, which
+    /// would end the element early; write it as `<\/script`, which means
+    /// the same thing inside JavaScript and JSON strings. Release builds
+    /// make that replacement themselves.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_attributes::ScriptType;
+    /// use ironhtml_elements::Script;
+    ///
+    /// let script = Element::<Script>::new()
+    ///     .r#type(ScriptType::JsonLd)
+    ///     .inline(r#"{"name": "Fish & Chips"}"#);
+    /// assert_eq!(
+    ///     script.render(),
+    ///     r#"<script type="application/ld+json">{"name": "Fish & Chips"}</script>"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn inline(mut self, content: impl AsRef<str>) -> Self {
+        self.children.push(TypedNode::Raw(escape_raw_text(
+            content.as_ref(),
+            Script::TAG,
+        )));
+        self
+    }
+}
+
+impl Element<Style> {
+    /// Set the media query the styles apply to, e.g. `print` or
+    /// `(max-width: 600px)`.
+    #[must_use]
+    pub fn media(self, query: impl Into<String>) -> Self {
+        self.attr(ironhtml_attributes::style::MEDIA, query)
+    }
+
+    /// Set the `nonce` a Content Security Policy uses to allow this inline
+    /// stylesheet.
+    #[must_use]
+    pub fn nonce(self, nonce: impl Into<String>) -> Self {
+        self.attr(ironhtml_attributes::global::NONCE, nonce)
+    }
+
+    /// Set the stylesheet body.
+    ///
+    /// `<style>` is a raw text element, so the CSS is written unescaped and
+    /// selectors such as `a > b` survive intact.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `body` contains `</style`, which would
+    /// close the element early; escape it in the CSS, such as `\3C /style`
+    /// in a string. Release builds write it as `<\/style`.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_elements::Style;
+    ///
+    /// let style = Element::<Style>::new().media("screen").css("nav > a { color: red; }");
+    /// assert_eq!(
+    ///     style.render(),
+    ///     r#"<style media="screen">nav > a { color: red; }</style>"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn css(mut self, body: &str) -> Self {
+        self.children
+            .push(TypedNode::Raw(escape_raw_text(body, Style::TAG)));
+        self
+    }
+}
+
+impl Element<Time> {
+    /// Set the machine-readable `datetime` value.
+    ///
+    /// Accepts a string in one of the HTML date, time, or duration formats,
+    /// a [`core::time::Duration`], or, with the `chrono` or `time` feature,
+    /// those crates' date and time types. See [`crate::datetime`].
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if a string value is not a valid datetime
+    /// value. Panics if a typed value cannot be represented (see
+    /// [`DatetimeValue`]).
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_elements::Time;
+    ///
+    /// let t = Element::<Time>::new()
+    ///     .datetime("2024-03-15T14:30:00Z")
+    ///     .text("March 15, 2:30 PM UTC");
+    /// assert_eq!(
+    ///     t.render(),
+    ///     r#"<time datetime="2024-03-15T14:30:00Z">March 15, 2:30 PM UTC</time>"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn datetime(self, value: impl DatetimeValue) -> Self {
+        self.attr("datetime", value.to_datetime_string())
+    }
+}
+
+impl Element<Blockquote> {
+    /// Set the URL of the quotation's source, `cite`.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `url` is empty.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_elements::{Blockquote, P};
+    ///
+    /// let quote = Element::<Blockquote>::new()
+    ///     .cite("https://example.com/?a=1&b=2")
+    ///     .child::<P, _>(|p| p.text("Quoted"));
+    /// assert_eq!(
+    ///     quote.render(),
+    ///     r#"<blockquote cite="https://example.com/?a=1&amp;b=2"><p>Quoted</p></blockquote>"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn cite(self, url: impl Into<String>) -> Self {
+        self.cite_attr(url.into())
+    }
+}
+
+impl Element<Q> {
+    /// Set the URL of the quotation's source, `cite`.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `url` is empty.
+    #[must_use]
+    pub fn cite(self, url: impl Into<String>) -> Self {
+        self.cite_attr(url.into())
+    }
+}
+
+impl Element<Del> {
+    /// Set the URL of a document explaining the removal, `cite`.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `url` is empty.
+    #[must_use]
+    pub fn cite(self, url: impl Into<String>) -> Self {
+        self.cite_attr(url.into())
+    }
+}
+
+impl Element<Ins> {
+    /// Set the URL of a document explaining the addition, `cite`.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `url` is empty.
+    #[must_use]
+    pub fn cite(self, url: impl Into<String>) -> Self {
+        self.cite_attr(url.into())
+    }
+}
+
+impl Element<Select> {
+    /// Allow selecting more than one option. `false` leaves the attribute
+    /// off.
+    #[must_use]
+    pub fn multiple(self, multiple: bool) -> Self {
+        self.when(multiple, |e| {
+            e.bool_attr(ironhtml_attributes::select::MULTIPLE)
+        })
+    }
+
+    /// Set the number of visible rows.
+    #[must_use]
+    pub fn size(self, size: u32) -> Self {
+        self.attr_value(ironhtml_attributes::select::SIZE, &size)
+    }
+}
+
+impl Element<Option_> {
+    /// Set the value submitted with the form.
+    #[must_use]
+    pub fn value(self, value: &str) -> Self {
+        self.attr(ironhtml_attributes::option::VALUE, value)
+    }
+
+    /// Select this option initially. `false` leaves the attribute off.
+    #[must_use]
+    pub fn selected(self, selected: bool) -> Self {
+        self.when(selected, |e| {
+            e.bool_attr(ironhtml_attributes::option::SELECTED)
+        })
+    }
+
+    /// Make this option unselectable. `false` leaves the attribute off.
+    #[must_use]
+    pub fn disabled(self, disabled: bool) -> Self {
+        self.when(disabled, |e| {
+            e.bool_attr(ironhtml_attributes::option::DISABLED)
+        })
+    }
+
+    /// Set the label shown instead of the option's text.
+    #[must_use]
+    pub fn label(self, label: &str) -> Self {
+        self.attr(ironhtml_attributes::option::LABEL, label)
+    }
+}
+
+impl Element<Optgroup> {
+    /// Disable every option in the group. `false` leaves the attribute off.
+    #[must_use]
+    pub fn disabled(self, disabled: bool) -> Self {
+        self.when(disabled, |e| {
+            e.bool_attr(ironhtml_attributes::option::DISABLED)
+        })
+    }
+
+    /// Set the group's label. Required by the spec.
+    #[must_use]
+    pub fn label(self, label: &str) -> Self {
+        self.attr(ironhtml_attributes::option::LABEL, label)
+    }
+}
+
+impl Element<Body> {
+    /// Set a window event handler, such as `onhashchange`, replacing any
+    /// handler already set for that event.
+    ///
+    /// The same escaping and security notes as
+    /// [`on`](Element::on) apply.
+    #[must_use]
+    pub fn on_window(self, event: WindowEventHandler, js: impl Into<String>) -> Self {
+        self.replace_attr(event.attr_name(), js)
+    }
+}
+
+impl Element<Fieldset> {
+    /// Disable the group. `false` leaves the attribute off.
+    ///
+    /// Every form control inside the fieldset is then disabled too, whether
+    /// or not it has its own `disabled` attribute, except controls inside
+    /// the fieldset's first `<legend>`. Disabled controls are not submitted.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_elements::{Fieldset, Input, Legend};
+    ///
+    /// let shipping = Element::<Fieldset>::new()
+    ///     .disabled(true)
+    ///     .child::<Legend, _>(|l| l.text("Shipping"))
+    ///     .child::<Input, _>(|i| i.attr("name", "address"));
+    /// assert_eq!(
+    ///     shipping.render(),
+    ///     r#"<fieldset disabled><legend>Shipping</legend><input name="address" /></fieldset>"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn disabled(self, disabled: bool) -> Self {
+        self.when(disabled, |e| e.bool_attr("disabled"))
+    }
+}
+
+impl Element<Textarea> {
+    /// Set the initial text, replacing any content already added.
+    ///
+    /// A textarea has no `value` attribute: its initial value is its text
+    /// content, escaped like any other text.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_elements::Textarea;
+    ///
+    /// let notes = Element::<Textarea>::new()
+    ///     .rows(4)
+    ///     .cols(40)
+    ///     .value("a < b");
+    /// assert_eq!(
+    ///     notes.render(),
+    ///     r#"<textarea rows="4" cols="40">a &lt; b</textarea>"#
+    /// );
+    /// ```
+    #[must_use]
+    pub fn value(mut self, value: impl Into<String>) -> Self {
+        self.children.clear();
+        self.text(value)
+    }
+
+    /// Set the number of visible text lines.
+    #[must_use]
+    pub fn rows(self, rows: u32) -> Self {
+        self.attr_value(ironhtml_attributes::textarea::ROWS, &rows)
+    }
+
+    /// Set the visible width in average character widths.
+    #[must_use]
+    pub fn cols(self, cols: u32) -> Self {
+        self.attr_value(ironhtml_attributes::textarea::COLS, &cols)
+    }
+
+    /// Set the maximum number of characters the user can enter.
+    #[must_use]
+    pub fn maxlength(self, maxlength: u32) -> Self {
+        self.attr_value(ironhtml_attributes::textarea::MAXLENGTH, &maxlength)
+    }
+
+    /// Set the minimum number of characters needed to pass validation.
+    #[must_use]
+    pub fn minlength(self, minlength: u32) -> Self {
+        self.attr_value(ironhtml_attributes::textarea::MINLENGTH, &minlength)
+    }
+
+    /// Set the hint shown while the textarea is empty.
+    #[must_use]
+    pub fn placeholder(self, placeholder: &str) -> Self {
+        self.attr(ironhtml_attributes::textarea::PLACEHOLDER, placeholder)
+    }
+
+    /// Set how submitted text is wrapped.
+    #[must_use]
+    pub fn wrap(self, wrap: Wrap) -> Self {
+        self.attr_value(ironhtml_attributes::textarea::WRAP, &wrap)
+    }
+
+    /// Make the textarea read-only. `false` leaves the attribute off.
+    #[must_use]
+    pub fn readonly(self, readonly: bool) -> Self {
+        self.when(readonly, |e| {
+            e.bool_attr(ironhtml_attributes::textarea::READONLY)
+        })
+    }
+
+    /// Require a value before the form can be submitted. `false` leaves
+    /// the attribute off.
+    #[must_use]
+    pub fn required(self, required: bool) -> Self {
+        self.when(required, |e| {
+            e.bool_attr(ironhtml_attributes::textarea::REQUIRED)
+        })
+    }
+}
+
+impl Element<Table> {
+    /// Set the table's `<caption>`, placing it first whatever has already
+    /// been added, since a caption anywhere else is invalid. Calling it
+    /// again replaces the caption rather than adding a second one.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_elements::{Caption, Table, Td, Tr};
+    ///
+    /// let table = Element::<Table>::new()
+    ///     .child::<Tr, _>(|tr| tr.child::<Td, _>(|td| td.text("1")))
+    ///     .caption(Element::<Caption>::new().text("Totals"));
+    /// assert_eq!(
+    ///     table.render(),
+    ///     "<table><caption>Totals</caption><tr><td>1</td></tr></table>"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn caption(mut self, caption: Element<Caption>) -> Self {
+        match self.children.first_mut() {
+            Some(first @ TypedNode::Element { tag: "caption", .. }) => {
+                *first = caption.into_node();
+                self
+            }
+            _ => self.prepend_child(caption),
+        }
+    }
+}
+
+impl Element<Td> {
+    /// Associate this cell with header cells by their `id`s.
+    ///
+    /// The ids are joined into a space-separated `headers` list, so each id
+    /// must not itself contain whitespace.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_elements::Td;
+    ///
+    /// let cell = Element::<Td>::new().headers(["q1", "north"]).text("42");
+    /// assert_eq!(cell.render(), r#"<td headers="q1 north">42</td>"#);
+    /// ```
+    #[must_use]
+    pub fn headers<'a>(self, ids: impl IntoIterator<Item = &'a str>) -> Self {
+        self.attr(ironhtml_attributes::tablecell::HEADERS, join_ids(ids))
+    }
+
+    /// Set the number of columns this cell spans, replacing any `colspan`
+    /// already set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `columns` is 0 or greater than 1000, the range the spec
+    /// allows.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_elements::Td;
+    ///
+    /// let cell = Element::<Td>::new().colspan(3).rowspan(2).text("Total");
+    /// assert_eq!(cell.render(), r#"<td colspan="3" rowspan="2">Total</td>"#);
+    /// ```
+    #[must_use]
+    pub fn colspan(self, columns: u32) -> Self {
+        check_colspan(columns);
+        self.replace_attr(
+            ironhtml_attributes::tablecell::COLSPAN,
+            columns.to_attr_value(),
+        )
+    }
+
+    /// Set the number of rows this cell spans, replacing any `rowspan`
+    /// already set.
+    ///
+    /// Unlike `colspan`, 0 is allowed: the cell then extends to the last
+    /// row of its row group.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rows` is greater than 65534, the most the spec allows.
+    #[must_use]
+    pub fn rowspan(self, rows: u32) -> Self {
+        check_rowspan(rows);
+        self.replace_attr(
+            ironhtml_attributes::tablecell::ROWSPAN,
+            rows.to_attr_value(),
+        )
+    }
+}
+
+impl Element<Th> {
+    /// Associate this header with other header cells by their `id`s.
+    ///
+    /// Works like `headers` on `Element<Td>`.
+    #[must_use]
+    pub fn headers<'a>(self, ids: impl IntoIterator<Item = &'a str>) -> Self {
+        self.attr(ironhtml_attributes::tablecell::HEADERS, join_ids(ids))
+    }
+
+    /// Set a short label for this header, used when it is announced
+    /// alongside data cells.
+    #[must_use]
+    pub fn abbr(self, abbr: &str) -> Self {
+        self.attr(ironhtml_attributes::tablecell::ABBR, abbr)
+    }
+
+    /// Set the number of columns this header spans. Works like `colspan`
+    /// on `Element<Td>`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `columns` is 0 or greater than 1000.
+    #[must_use]
+    pub fn colspan(self, columns: u32) -> Self {
+        check_colspan(columns);
+        self.replace_attr(
+            ironhtml_attributes::tablecell::COLSPAN,
+            columns.to_attr_value(),
+        )
+    }
+
+    /// Set the number of rows this header spans. Works like `rowspan` on
+    /// `Element<Td>`, including 0 for the rest of the row group.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rows` is greater than 65534.
+    #[must_use]
+    pub fn rowspan(self, rows: u32) -> Self {
+        check_rowspan(rows);
+        self.replace_attr(
+            ironhtml_attributes::tablecell::ROWSPAN,
+            rows.to_attr_value(),
+        )
+    }
+}
+
+/// Panic unless `columns` is a valid cell `colspan`, 1 to 1000.
+fn check_colspan(columns: u32) {
+    assert!(
+        (1..=1000).contains(&columns),
+        "colspan must be between 1 and 1000, got {columns}"
+    );
+}
+
+/// Panic unless `rows` is a valid cell `rowspan`, 0 to 65534.
+fn check_rowspan(rows: u32) {
+    assert!(rows <= 65534, "rowspan must be at most 65534, got {rows}");
+}
+
+impl<E: ListedElement> Element<E> {
+    /// Associate this control with the `<form>` whose id is `form_id`, so
+    /// it is submitted with that form even when placed outside it.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if `form_id` is empty or contains whitespace.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use ironhtml::typed::Element;
+    /// use ironhtml_elements::Button;
+    ///
+    /// let button = Element::<Button>::new().form("checkout").text("Pay");
+    /// assert_eq!(button.render(), r#"<button form="checkout">Pay</button>"#);
+    /// ```
+    #[must_use]
+    pub fn form(self, form_id: impl Into<String>) -> Self {
+        let form_id = form_id.into();
+        debug_assert!(
+            !form_id.is_empty() && !form_id.contains(|c: char| c.is_ascii_whitespace()),
+            "form id {form_id:?} must be a non-empty token without whitespace"
+        );
+        self.attr("form", form_id)
+    }
+}
+
+/// Join URLs into a space-separated list such as `ping`.
+fn join_urls<'a>(urls: impl IntoIterator<Item = &'a str>) -> String {
+    let mut joined = String::new();
+    for url in urls {
+        debug_assert!(
+            !url.is_empty() && !url.contains(char::is_whitespace),
+            "invalid URL in list: {url:?}"
+        );
+        if !joined.is_empty() {
+            joined.push(' ');
+        }
+        joined.push_str(url);
+    }
+    joined
+}
+
+/// Join ids into a space-separated token list.
+fn join_ids<'a>(ids: impl IntoIterator<Item = &'a str>) -> String {
+    let mut joined = String::new();
+    for id in ids {
+        if !joined.is_empty() {
+            joined.push(' ');
+        }
+        joined.push_str(id);
+    }
+    joined
+}
+
+// =============================================================================
+// Structural helpers
+// =============================================================================
+//
+// Some content-model rules depend on attributes, child order, or cardinality.
+// `CanContain` is a binary parent/child trait and cannot express them, so the
+// helpers below build the element in one call in a shape that is always valid.
+
+/// Create a complete document: the doctype, then `<html>` containing exactly
+/// one `<head>` followed by one `<body>`.
+///
+/// Taking `head` and `body` as separate typed arguments makes a root with a
+/// missing, repeated, or misordered `<head>`/`<body>` impossible to build.
+///
+/// ## Example
+///
+/// ```rust
+/// use ironhtml::typed::{document, Element};
+/// use ironhtml_elements::{Body, Head, Title, H1};
+///
+/// let doc = document(
+///     Element::<Head>::new().child::<Title, _>(|t| t.text("Hi")),
+///     Element::<Body>::new().child::<H1, _>(|h| h.text("Hello")),
+/// );
+/// assert_eq!(
+///     doc.render(),
+///     "<!DOCTYPE html><html><head><title>Hi</title></head><body><h1>Hello</h1></body></html>"
+/// );
+/// ```
+#[must_use]
+pub fn document(head: Element<Head>, body: Element<Body>) -> Document {
+    document_with_root(Element::<Html>::new(), head, body)
+}
+
+/// Like [`document`], with attributes such as `lang` taken from `root`.
+///
+/// # Panics
+///
+/// Panics if `root` already has children.
+///
+/// ## Example
+///
+/// ```rust
+/// use ironhtml::typed::{document_with_root, Element};
+/// use ironhtml_elements::{Body, Head, Html};
+///
+/// let doc = document_with_root(
+///     Element::<Html>::new().lang("en"),
+///     Element::<Head>::new(),
+///     Element::<Body>::new(),
+/// );
+/// assert_eq!(
+///     doc.render(),
+///     r#"<!DOCTYPE html><html lang="en"><head></head><body></body></html>"#
+/// );
+/// ```
+#[must_use]
+pub fn document_with_root(
+    root: Element<Html>,
+    head: Element<Head>,
+    body: Element<Body>,
+) -> Document {
+    assert!(
+        root.children.is_empty(),
+        "document root must not have children; pass them in head and body"
+    );
+    let root = root.append_child(head).append_child(body);
+    Document::new().doctype().root::<Html, _>(|_| root)
+}
+
+/// Create a `<details>` disclosure widget whose first child is `summary`.
+///
+/// A `<details>` must start with exactly one `<summary>`, which
+/// `CanContain` cannot express; building it here puts the summary first.
+/// `content` may be any child `<details>` accepts, including a `Vec`.
+///
+/// ## Example
+///
+/// ```rust
+/// use ironhtml::typed::{details, Element};
+/// use ironhtml_elements::{Summary, P};
+///
+/// let faq = details(
+///     Element::<Summary>::new().text("Is it free?"),
+///     Element::<P>::new().text("Yes."),
+/// );
+/// assert_eq!(
+///     faq.render(),
+///     "<details><summary>Is it free?</summary><p>Yes.</p></details>"
+/// );
+/// ```
+#[must_use]
+pub fn details(summary: Element<Summary>, content: impl IntoChild<Details>) -> Element<Details> {
+    Element::<Details>::new()
+        .append_child(summary)
+        .append_child(content)
+}
+
+/// Create a `<dialog>` holding `content`, with the `open` attribute set so
+/// it is shown when the page loads.
+///
+/// `open` shows the dialog in place, without a backdrop. Modal behavior,
+/// where the rest of the page becomes inert and Escape closes the dialog,
+/// only comes from calling `showModal()` in JavaScript; leave `open` off
+/// (build the element with `Element::<Dialog>::new()`) for a dialog that
+/// script will open that way. A `<form method="dialog">` inside closes the
+/// dialog when submitted, with no script.
+///
+/// ## Example
+///
+/// ```rust
+/// use ironhtml::typed::{dialog_modal, Element};
+/// use ironhtml_elements::{Button, Form, P};
+///
+/// let notice = dialog_modal(Element::<P>::new().text("Saved."))
+///     .child::<Form, _>(|f| f.attr("method", "dialog").child::<Button, _>(|b| b.text("OK")));
+/// assert_eq!(
+///     notice.render(),
+///     r#"<dialog open><p>Saved.</p><form method="dialog"><button>OK</button></form></dialog>"#
+/// );
+/// ```
+#[must_use]
+pub fn dialog_modal(content: impl IntoChild<Dialog>) -> Element<Dialog> {
+    Element::<Dialog>::new()
+        .bool_attr("open")
+        .append_child(content)
+}
+
+/// Create a `<table>` with an optional `<caption>` first, followed by
+/// `content` (row groups, rows, or a `Vec` of them).
+///
+/// ## Example
+///
+/// ```rust
+/// use ironhtml::typed::{table, Element};
+/// use ironhtml_elements::{Caption, Tbody, Td, Tr};
+///
+/// let t = table(
+///     Some(Element::<Caption>::new().text("Scores")),
+///     Element::<Tbody>::new().child::<Tr, _>(|tr| tr.child::<Td, _>(|td| td.text("9"))),
+/// );
+/// assert_eq!(
+///     t.render(),
+///     "<table><caption>Scores</caption><tbody><tr><td>9</td></tr></tbody></table>"
+/// );
+/// ```
+#[must_use]
+pub fn table(caption: Option<Element<Caption>>, content: impl IntoChild<Table>) -> Element<Table> {
+    let table = Element::<Table>::new().append_child(content);
+    match caption {
+        Some(caption) => table.caption(caption),
+        None => table,
+    }
+}
+
+/// Create a `<colgroup>` spanning `span` columns, with no `<col>` children.
+///
+/// A `<colgroup>` either has a `span` attribute and is empty, or has `<col>`
+/// children and no `span` — never both. Since `Colgroup: CanContain<Col>`
+/// holds unconditionally, an `Element<Colgroup>` cannot enforce this, so
+/// this returns a [`SpannedColgroup`], which takes attributes but no
+/// children. Use [`colgroup_cols`] for the other form.
+///
+/// ## Example
+///
+/// ```rust
+/// use core::num::NonZeroU32;
+/// use ironhtml::typed::{colgroup_span, Element};
+/// use ironhtml_elements::Table;
+///
+/// let span = NonZeroU32::new(2).unwrap();
+/// let table = Element::<Table>::new().append_child(colgroup_span(span));
+/// assert_eq!(table.render(), r#"<table><colgroup span="2"></colgroup></table>"#);
+/// ```
+#[must_use]
+pub fn colgroup_span(span: NonZeroU32) -> SpannedColgroup {
+    SpannedColgroup(Element::<Colgroup>::new().attr("span", span.to_string()))
+}
+
+/// A `<colgroup>` with a `span` attribute, made by [`colgroup_span`].
+///
+/// It can be given attributes but, unlike `Element<Colgroup>`, no `<col>`
+/// children.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SpannedColgroup(Element<Colgroup>);
+
+impl SpannedColgroup {
+    /// Add a class. Multiple calls append to the class list.
+    #[must_use]
+    pub fn class(self, class: impl Into<String>) -> Self {
+        Self(self.0.class(class))
+    }
+
+    /// Set the `id`.
+    #[must_use]
+    pub fn id(self, id: impl Into<String>) -> Self {
+        Self(self.0.id(id))
+    }
+
+    /// Add an attribute.
+    #[must_use]
+    pub fn attr(self, name: impl Into<Cow<'static, str>>, value: impl Into<String>) -> Self {
+        Self(self.0.attr(name, value))
+    }
+
+    /// Render to an HTML string.
+    #[must_use]
+    pub fn render(&self) -> String {
+        self.0.render()
+    }
+}
+
+impl Render for SpannedColgroup {
+    fn render_into(&self, sink: &mut impl core::fmt::Write) -> core::fmt::Result {
+        self.0.render_into(sink)
+    }
+}
+
+impl<P: CanContain<Colgroup>> IntoChild<P> for SpannedColgroup {
+    fn push_into(self, nodes: &mut Vec<TypedNode>) {
+        nodes.push(self.0.into_node());
+    }
+}
+
+/// Create a `<colgroup>` from a list of `<col>` elements, with no `span`.
+///
+/// See [`colgroup_span`] for why the two forms are separate constructors.
+///
+/// ## Example
+///
+/// ```rust
+/// use ironhtml::typed::{colgroup_cols, Element};
+/// use ironhtml_elements::Col;
+///
+/// let group = colgroup_cols([
+///     Element::<Col>::new().class("name"),
+///     Element::<Col>::new().attr("span", "2"),
+/// ]);
+/// assert_eq!(
+///     group.render(),
+///     r#"<colgroup><col class="name" /><col span="2" /></colgroup>"#
+/// );
+/// ```
+#[must_use]
+pub fn colgroup_cols<I>(cols: I) -> Element<Colgroup>
+where
+    I: IntoIterator<Item = Element<Col>>,
+{
+    let mut group = Element::<Colgroup>::new();
+    group
+        .children
+        .extend(cols.into_iter().map(Element::into_node));
+    group
+}
+
+/// Link an `<input>` to a new `<datalist>` of suggested values.
+///
+/// Sets the input's `list` attribute and the datalist's `id` from the same
+/// `id`, so the two can't drift apart, and adds one `<option value>` per
+/// suggestion. Both elements are returned so they can be placed as siblings.
+///
+/// ## Example
+///
+/// ```rust
+/// use ironhtml::typed::{input_with_datalist, Element};
+/// use ironhtml_elements::{Datalist, Form, Input};
+///
+/// let (input, list) = input_with_datalist(
+///     "browsers",
+///     Element::<Input>::new().attr("name", "browser"),
+///     ["Firefox", "Chrome"],
+/// );
+/// let form = Element::<Form>::new()
+///     .child::<Input, _>(|_| input)
+///     .child::<Datalist, _>(|_| list);
+///
+/// assert_eq!(
+///     form.render(),
+///     r#"<form><input name="browser" list="browsers" /><datalist id="browsers"><option value="Firefox"></option><option value="Chrome"></option></datalist></form>"#
+/// );
+/// ```
+#[must_use]
+pub fn input_with_datalist<'a, I>(
+    id: &str,
+    input: Element<Input>,
+    options: I,
+) -> (Element<Input>, Element<Datalist>)
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let input = input.attr("list", id.to_string());
+    let list = options
+        .into_iter()
+        .fold(Element::<Datalist>::new().id(id), |list, value| {
+            list.child::<Option_, _>(|o| o.attr("value", value.to_string()))
+        });
+    (input, list)
+}
+
+/// Create a responsive `<img>` that loads lazily and decodes off the main
+/// thread.
+///
+/// `alt` is required: pass `""` for a purely decorative image. `srcset`
+/// lists candidate files with width descriptors, and `sizes` tells the
+/// browser how wide the image will be displayed so it can pick one. The
+/// `loading="lazy"` and `decoding="async"` defaults suit images below the
+/// fold; override them with [`Element::loading`] and [`Element::decoding`],
+/// e.g. `Loading::Eager` for a hero image.
+///
+/// ## Example
+///
+/// ```rust
+/// use ironhtml::typed::responsive_img;
+/// use ironhtml_attributes::Loading;
+///
+/// let img = responsive_img(
+///     "cat-800.jpg",
+///     "A cat asleep on a keyboard",
+///     "cat-400.jpg 400w, cat-800.jpg 800w",
+///     "(max-width: 600px) 400px, 800px",
+/// )
+/// .loading(Loading::Eager);
+/// assert_eq!(
+///     img.render(),
+///     r#"<img src="cat-800.jpg" alt="A cat asleep on a keyboard" srcset="cat-400.jpg 400w, cat-800.jpg 800w" sizes="(max-width: 600px) 400px, 800px" loading="eager" decoding="async" />"#
+/// );
+/// ```
+#[must_use]
+pub fn responsive_img(
+    src: impl Into<String>,
+    alt: impl Into<String>,
+    srcset: impl Into<String>,
+    sizes: impl Into<String>,
+) -> Element<Img> {
+    Element::<Img>::new()
+        .attr(ironhtml_attributes::img::SRC, src)
+        .attr(ironhtml_attributes::img::ALT, alt)
+        .attr(ironhtml_attributes::img::SRCSET, srcset)
+        .attr(ironhtml_attributes::img::SIZES, sizes)
+        .loading(Loading::Lazy)
+        .decoding(Decoding::Async)
+}
+
+/// Create a `<form>` that can upload files.
+///
+/// A form containing `<input type="file">` must be submitted with
+/// `method="post"` and `enctype="multipart/form-data"`; otherwise the browser
+/// sends only the file names. This sets both.
+///
+/// ## Example
+///
+/// ```rust
+/// use ironhtml::typed::form_with_files;
+/// use ironhtml_elements::Input;
+///
+/// let form = form_with_files("/upload")
+///     .child::<Input, _>(|i| i.attr("type", "file").attr("name", "doc"));
+/// assert_eq!(
+///     form.render(),
+///     r#"<form action="/upload" method="post" enctype="multipart/form-data"><input type="file" name="doc" /></form>"#
+/// );
+/// ```
+#[must_use]
+pub fn form_with_files(action: impl Into<String>) -> Element<Form> {
+    Element::<Form>::new()
+        .attr(ironhtml_attributes::form::ACTION, action)
+        .attr_value(ironhtml_attributes::form::METHOD, &Method::Post)
+        .attr_value(ironhtml_attributes::form::ENCTYPE, &Enctype::Multipart)
+}
+
+/// Create a `<fieldset>` of checkboxes sharing one `name`.
+///
+/// Each option is `(value, label, checked)` and becomes an
+/// `<input type="checkbox">` wrapped in its `<label>`. Inputs get the ids
+/// `{id_prefix}-0`, `{id_prefix}-1`, ..., and each label points at its
+/// input with `for`. The `<legend>` names the group for assistive
+/// technologies.
+///
+/// The ids are kept apart from `name`, since two groups on one page may
+/// submit the same name. `id_prefix` must not contain whitespace, and must
+/// differ from every other group's prefix on the page so the ids stay
+/// unique.
+///
+/// ## Example
+///
+/// ```rust
+/// use ironhtml::typed::checkbox_group;
+///
+/// let group = checkbox_group("Toppings", "topping", "pizza-topping", [("ham", "Ham", true)]);
+/// assert_eq!(
+///     group.render(),
+///     r#"<fieldset><legend>Toppings</legend><label for="pizza-topping-0"><input type="checkbox" id="pizza-topping-0" name="topping" value="ham" checked />Ham</label></fieldset>"#
+/// );
+/// ```
+#[must_use]
+pub fn checkbox_group<'a, I>(
+    legend: &str,
+    name: &str,
+    id_prefix: &str,
+    options: I,
+) -> Element<Fieldset>
+where
+    I: IntoIterator<Item = (&'a str, &'a str, bool)>,
+{
+    choice_group(InputType::Checkbox, legend, name, id_prefix, options)
+}
+
+/// Create a `<fieldset>` of radio buttons sharing one `name`, so at most
+/// one can be selected.
+///
+/// Built like [`checkbox_group`]; `checked` should be set on one option
+/// at most.
+#[must_use]
+pub fn radio_group<'a, I>(
+    legend: &str,
+    name: &str,
+    id_prefix: &str,
+    options: I,
+) -> Element<Fieldset>
+where
+    I: IntoIterator<Item = (&'a str, &'a str, bool)>,
+{
+    choice_group(InputType::Radio, legend, name, id_prefix, options)
+}
+
+fn choice_group<'a, I>(
+    input_type: InputType,
+    legend: &str,
+    name: &str,
+    id_prefix: &str,
+    options: I,
+) -> Element<Fieldset>
+where
+    I: IntoIterator<Item = (&'a str, &'a str, bool)>,
+{
+    let fieldset = Element::<Fieldset>::new().child::<Legend, _>(|l| l.text(legend));
+    options
+        .into_iter()
+        .enumerate()
+        .fold(fieldset, |fieldset, (i, (value, label, checked))| {
+            let id = alloc::format!("{id_prefix}-{i}");
+            fieldset.child::<Label, _>(|l| {
+                l.attr("for", id.clone())
+                    .child::<Input, _>(|input| {
+                        input
+                            .attr_value("type", &input_type)
+                            .id(id)
+                            .attr("name", name.to_string())
+                            .attr("value", value.to_string())
+                            .when(checked, |input| input.bool_attr("checked"))
+                    })
+                    .text(label)
+            })
+        })
+}
+
+/// Create a `<meta name content>` element for a standard metadata name.
+///
+/// ## Example
+///
+/// ```rust
+/// use ironhtml::typed::meta_name;
+/// use ironhtml_attributes::MetaName;
+///
+/// let meta = meta_name(MetaName::Viewport, "width=device-width, initial-scale=1");
+/// assert_eq!(
+///     meta.render(),
+///     r#"<meta name="viewport" content="width=device-width, initial-scale=1" />"#
+/// );
+/// ```
+#[must_use]
+pub fn meta_name(name: MetaName, content: impl Into<String>) -> Element<Meta> {
+    Element::<Meta>::new()
+        .attr_value(ironhtml_attributes::meta::NAME, &name)
+        .attr(ironhtml_attributes::meta::CONTENT, content)
+}
+
+/// Create a `<meta http-equiv content>` pragma directive.
+///
+/// ## Example
+///
+/// ```rust
+/// use ironhtml::typed::meta_http_equiv;
+/// use ironhtml_attributes::HttpEquiv;
+///
+/// let meta = meta_http_equiv(HttpEquiv::Refresh, "30");
+/// assert_eq!(meta.render(), r#"<meta http-equiv="refresh" content="30" />"#);
+/// ```
+#[must_use]
+pub fn meta_http_equiv(equiv: HttpEquiv, content: impl Into<String>) -> Element<Meta> {
+    Element::<Meta>::new()
+        .attr_value(ironhtml_attributes::meta::HTTPEQUIV, &equiv)
+        .attr(ironhtml_attributes::meta::CONTENT, content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{format, vec};
+    use ironhtml_elements::*;
+
+    #[test]
+    fn test_simple_element() {
+        let html = Element::<Div>::new()
+            .class("container")
+            .text("Hello")
+            .render();
+        assert_eq!(html, r#"<div class="container">Hello</div>"#);
+    }
+
+    #[test]
+    fn test_nested_elements() {
+        let html = Element::<Table>::new()
+            .class("table")
+            .child::<Tr, _>(|tr| {
+                tr.child::<Td, _>(|td| td.text("Cell 1"))
+                    .child::<Td, _>(|td| td.text("Cell 2"))
+            })
+            .render();
+
+        assert_eq!(
+            html,
+            r#"<table class="table"><tr><td>Cell 1</td><td>Cell 2</td></tr></table>"#
+        );
+    }
+
+    #[test]
+    fn test_list() {
+        let items = ["Apple", "Banana", "Cherry"];
+        let html = Element::<Ul>::new()
+            .children(items, |item, li: Element<Li>| li.text(item))
+            .render();
+
+        assert_eq!(
+            html,
+            r"<ul><li>Apple</li><li>Banana</li><li>Cherry</li></ul>"
+        );
+    }
+
+    #[test]
+    fn test_void_element() {
+        let html = Element::<Img>::new()
+            .attr("src", "image.jpg")
+            .attr("alt", "An image")
+            .render();
+
+        assert_eq!(html, r#"<img src="image.jpg" alt="An image" />"#);
+    }
+
+    #[test]
+    fn test_document() {
+        let html = Document::new()
+            .doctype()
+            .root::<Html, _>(|html| {
+                html.attr("lang", "en")
+                    .child::<Head, _>(|head| {
+                        head.child::<Meta, _>(|meta| meta.attr("charset", "UTF-8"))
+                            .child::<Title, _>(|title| title.text("Hello"))
+                    })
+                    .child::<Body, _>(|body| body.child::<H1, _>(|h1| h1.text("Hello, World!")))
+            })
+            .build();
+
+        assert_eq!(
+            html,
+            r#"<!DOCTYPE html><html lang="en"><head><meta charset="UTF-8" /><title>Hello</title></head><body><h1>Hello, World!</h1></body></html>"#
+        );
+    }
+
+    #[test]
+    fn test_class_chaining() {
+        let html = Element::<Div>::new()
+            .class("btn")
+            .class("btn-primary")
+            .class("active")
+            .render();
+
+        assert_eq!(html, r#"<div class="btn btn-primary active"></div>"#);
+    }
+
+    #[test]
+    fn test_data_attributes() {
+        let html = Element::<Div>::new()
+            .data("id", "123")
+            .data("action", "submit")
+            .render();
+
+        assert_eq!(html, r#"<div data-id="123" data-action="submit"></div>"#);
+    }
+
+    #[test]
+    fn test_conditional() {
+        let show = true;
+        let html = Element::<Div>::new()
+            .when(show, |e| e.child::<Span, _>(|s| s.text("Visible")))
+            .render();
+
+        assert_eq!(html, r"<div><span>Visible</span></div>");
+
+        let hide = false;
+        let html = Element::<Div>::new()
+            .when(hide, |e| e.child::<Span, _>(|s| s.text("Hidden")))
+            .render();
+
+        assert_eq!(html, r"<div></div>");
+    }
+
+    #[test]
+    fn test_escape_text() {
+        let html = Element::<Div>::new()
+            .text("<script>alert('xss')</script>")
+            .render();
+
+        assert_eq!(
+            html,
+            r"<div>&lt;script&gt;alert('xss')&lt;/script&gt;</div>"
+        );
+    }
+
+    #[test]
+    fn test_escape_attr() {
+        let html = Element::<Div>::new()
+            .attr("data-value", "say \"hello\"")
+            .render();
+
+        assert_eq!(html, r#"<div data-value="say &quot;hello&quot;"></div>"#);
+    }
+
+    #[test]
+    fn test_escape_policy() {
+        use crate::EscapePolicy;
+
+        let el = Element::<P>::new().attr("title", "café").text("café");
+        assert_eq!(el.render(), r#"<p title="café">café</p>"#);
+
+        let options = RenderOptions::new().escape(EscapePolicy::AsciiEntities);
+        assert_eq!(
+            el.render_with(&options),
+            r#"<p title="caf&#xE9;">caf&#xE9;</p>"#
+        );
+    }
+
+    #[test]
+    fn test_type_safe_attribute_value() {
+        use ironhtml_attributes::{InputType, Loading};
+
+        let html = Element::<Input>::new()
+            .attr_value(ironhtml_attributes::input::TYPE, &InputType::Email)
+            .attr("name", "email")
+            .render();
+
+        assert_eq!(html, r#"<input type="email" name="email" />"#);
+
+        let html = Element::<Img>::new()
+            .attr("src", "large.jpg")
+            .attr_value(ironhtml_attributes::img::LOADING, &Loading::Lazy)
+            .render();
+
+        assert_eq!(html, r#"<img src="large.jpg" loading="lazy" />"#);
+    }
+
+    #[test]
+    fn test_form() {
+        use ironhtml_attributes::Method;
+
+        let html = Element::<Form>::new()
+            .attr("action", "/submit")
+            .attr_value(ironhtml_attributes::form::METHOD, &Method::Post)
+            .child::<Input, _>(|i| {
+                i.attr("type", "text")
+                    .attr("name", "username")
+                    .attr("placeholder", "Username")
+            })
+            .child::<Input, _>(|i| i.attr("type", "password").attr("name", "password"))
+            .child::<Button, _>(|b| b.attr("type", "submit").text("Login"))
+            .render();
+
+        assert!(html.contains(r#"<form action="/submit" method="post">"#));
+        assert!(html.contains(r#"<input type="text" name="username""#));
+        assert!(html.contains(r#"<button type="submit">Login</button>"#));
+    }
+
+    #[test]
+    fn test_anchor_link() {
+        use ironhtml_attributes::Target;
+
+        let html = Element::<A>::new()
+            .attr("href", "https://example.com")
+            .attr_value(ironhtml_attributes::anchor::TARGET, &Target::Blank)
+            .attr("rel", "noopener noreferrer")
+            .text("External Link")
+            .render();
+
+        assert_eq!(
+            html,
+            r#"<a href="https://example.com" target="_blank" rel="noopener noreferrer">External Link</a>"#
+        );
+    }
+
+    #[test]
+    fn test_select_options() {
+        let html = Element::<Select>::new()
+            .attr("name", "country")
+            .child::<Option_, _>(|o| o.attr("value", "us").text("United States"))
+            .child::<Option_, _>(|o| o.attr("value", "uk").text("United Kingdom"))
+            .child::<Option_, _>(|o| o.attr("value", "ca").text("Canada"))
+            .render();
+
+        assert!(html.contains(r#"<select name="country">"#));
+        assert!(html.contains(r#"<option value="us">United States</option>"#));
+        assert!(html.contains(r#"<option value="uk">United Kingdom</option>"#));
+    }
+
+    #[test]
+    fn test_definition_list() {
+        let html = Element::<Dl>::new()
+            .child::<Dt, _>(|dt| dt.text("HTML"))
+            .child::<Dd, _>(|dd| dd.text("HyperText Markup Language"))
+            .child::<Dt, _>(|dt| dt.text("CSS"))
+            .child::<Dd, _>(|dd| dd.text("Cascading Style Sheets"))
+            .render();
+
+        assert!(html.contains("<dl>"));
+        assert!(html.contains("<dt>HTML</dt>"));
+        assert!(html.contains("<dd>HyperText Markup Language</dd>"));
+    }
+
+    #[test]
+    fn test_complex_table() {
+        let html = Element::<Table>::new()
+            .class("table")
+            .child::<Thead, _>(|thead| {
+                thead.child::<Tr, _>(|tr| {
+                    tr.child::<Th, _>(|th| th.text("Name"))
+                        .child::<Th, _>(|th| th.text("Age"))
+                })
+            })
+            .child::<Tbody, _>(|tbody| {
+                tbody
+                    .child::<Tr, _>(|tr| {
+                        tr.child::<Td, _>(|td| td.text("Alice"))
+                            .child::<Td, _>(|td| td.text("30"))
+                    })
+                    .child::<Tr, _>(|tr| {
+                        tr.child::<Td, _>(|td| td.text("Bob"))
+                            .child::<Td, _>(|td| td.text("25"))
+                    })
+            })
+            .render();
+
+        assert!(html.contains("<thead>"));
+        assert!(html.contains("<th>Name</th>"));
+        assert!(html.contains("<tbody>"));
+        assert!(html.contains("<td>Alice</td>"));
+    }
+
+    #[test]
+    fn test_extend_children() {
+        let rows = || -> Vec<Element<Li>> {
+            ["One", "Two", "Three"]
+                .into_iter()
+                .map(|s| Element::<Li>::new().text(s))
+                .collect()
+        };
+        let list = Element::<Ul>::new()
+            .child::<Li, _>(|li| li.text("Zero"))
+            .extend_children(rows());
+        assert_eq!(
+            list.render(),
+            "<ul><li>Zero</li><li>One</li><li>Two</li><li>Three</li></ul>"
+        );
+
+        let appended = Element::<Ul>::new().append_child(rows());
+        assert_eq!(
+            appended.render(),
+            "<ul><li>One</li><li>Two</li><li>Three</li></ul>"
+        );
+    }
+
+    #[test]
+    fn test_prepend_child() {
+        let table = Element::<Table>::new()
+            .child::<Tr, _>(|tr| tr.child::<Td, _>(|td| td.text("Cell")))
+            .prepend_child(Element::<Caption>::new().text("Totals"));
+
+        assert_eq!(
+            table.render(),
+            "<table><caption>Totals</caption><tr><td>Cell</td></tr></table>"
+        );
+    }
+
+    #[test]
+    fn test_insert_child() {
+        let list = Element::<Ul>::new()
+            .child::<Li, _>(|li| li.text("One"))
+            .child::<Li, _>(|li| li.text("Three"))
+            .insert_child(1, Element::<Li>::new().text("Two"));
+
+        assert_eq!(
+            list.render(),
+            "<ul><li>One</li><li>Two</li><li>Three</li></ul>"
+        );
+
+        let list = list.insert_child(3, Element::<Li>::new().text("Four"));
+        assert!(list.render().ends_with("<li>Four</li></ul>"));
+    }
+
+    #[test]
+    fn test_colgroup_span() {
+        let span = NonZeroU32::new(3).unwrap();
+        let html = Element::<Table>::new()
+            .append_child(colgroup_span(span).class("numbers"))
+            .render();
+
+        assert_eq!(
+            html,
+            r#"<table><colgroup span="3" class="numbers"></colgroup></table>"#
+        );
+        let debug = alloc::format!("{:?}", colgroup_span(span));
+        assert!(debug.starts_with(r#"SpannedColgroup(Element { tag: "colgroup""#));
+    }
+
+    #[test]
+    fn test_colgroup_cols() {
+        let html = colgroup_cols([
+            Element::<Col>::new(),
+            Element::<Col>::new().attr("span", "2").class("numbers"),
+        ])
+        .render();
+
+        assert_eq!(
+            html,
+            r#"<colgroup><col /><col span="2" class="numbers" /></colgroup>"#
+        );
+        assert!(!html.contains("<colgroup span"));
+    }
+
+    #[test]
+    fn test_input_with_datalist() {
+        let (input, list) = input_with_datalist(
+            "colors",
+            Element::<Input>::new().attr("name", "color"),
+            ["red", "green", "blue"],
+        );
+        let input = input.render();
+        let list = list.render();
+
+        assert_eq!(input, r#"<input name="color" list="colors" />"#);
+        assert!(list.starts_with(r#"<datalist id="colors">"#));
+        assert!(list.contains(r#"<option value="red"></option>"#));
+        assert!(list.contains(r#"<option value="green"></option>"#));
+        assert!(list.contains(r#"<option value="blue"></option>"#));
+    }
+
+    #[test]
+    fn test_rel_tokens() {
+        use ironhtml_attributes::{anchor, link, Rel, RelTokens};
+
+        let a = Element::<A>::new()
+            .attr(anchor::HREF, "https://example.com")
+            .attr_value(
+                anchor::REL,
+                &RelTokens::new().with(Rel::Noopener).with(Rel::Noreferrer),
+            )
+            .text("Out");
+        assert_eq!(
+            a.render(),
+            r#"<a href="https://example.com" rel="noopener noreferrer">Out</a>"#
+        );
+
+        let css = Element::<Link>::new().attr_value(link::REL, &RelTokens::from(Rel::Stylesheet));
+        assert_eq!(css.render(), r#"<link rel="stylesheet" />"#);
+    }
+
+    #[test]
+    fn test_rel_setters_replace() {
+        use ironhtml_attributes::{Rel, RelTokens};
+
+        let a = Element::<A>::new().rel(Rel::Nofollow).rel(Rel::Next);
+        assert_eq!(a.render(), r#"<a rel="next"></a>"#);
+        let area = Element::<Area>::new().rel(RelTokens::new().with(Rel::Noopener));
+        assert_eq!(area.render(), r#"<area rel="noopener" />"#);
+        let link = Element::<Link>::new().rel(Rel::Icon);
+        assert_eq!(link.render(), r#"<link rel="icon" />"#);
+    }
+
+    #[test]
+    fn test_form_with_files() {
+        let form = form_with_files("/upload").child::<Input, _>(|i| i.attr("type", "file"));
+        assert_eq!(
+            form.render(),
+            r#"<form action="/upload" method="post" enctype="multipart/form-data"><input type="file" /></form>"#
+        );
+    }
+
+    #[test]
+    fn test_download() {
+        let bare = Element::<A>::new()
+            .attr("href", "/file")
+            .download(Download::Default);
+        assert_eq!(bare.render(), r#"<a href="/file" download></a>"#);
+
+        let named = Element::<A>::new()
+            .attr("href", "/file")
+            .download(Download::Filename(r#"my "file".pdf"#.into()));
+        assert_eq!(
+            named.render(),
+            r#"<a href="/file" download="my &quot;file&quot;.pdf"></a>"#
+        );
+    }
+
+    #[test]
+    fn test_render_debug_annotated() {
+        let page = Element::<Div>::new()
+            .id("main")
+            .child::<Figure, _>(|f| {
+                f.child::<Img, _>(|i| i.attr("src", "logo.png"))
+                    .child::<Img, _>(|i| i.attr("src", "ok.png").attr("alt", "OK"))
+            })
+            .child::<P, _>(|p| p.id("main").text("Hi"));
+        assert_eq!(
+            page.render_debug_annotated(),
+            "<div id=\"main\">\n\
+             \x20 <!-- WARN: <img> is missing an 'alt' attribute -->\n\
+             \x20 <figure><img src=\"logo.png\" /><img src=\"ok.png\" alt=\"OK\" /></figure>\n\
+             \x20 <!-- WARN: duplicate id 'main' on <p> -->\n\
+             \x20 <p id=\"main\">Hi</p>\n\
+             </div>"
+        );
+        let doc = Document::new().root::<Div, _>(|div| div.child::<P, _>(|p| p.text("ok")));
+        assert_eq!(doc.render_debug_annotated(), doc.render_pretty());
+    }
+
+    #[test]
+    fn test_table_cell_headers() {
+        let td = Element::<Td>::new().headers(["region", "q<1>"]).text("10");
+        assert_eq!(td.render(), r#"<td headers="region q&lt;1&gt;">10</td>"#);
+
+        let th = Element::<Th>::new()
+            .id("north")
+            .headers(["region"])
+            .abbr("N")
+            .text("North");
+        assert_eq!(
+            th.render(),
+            r#"<th id="north" headers="region" abbr="N">North</th>"#
+        );
+    }
+
+    struct Badge {
+        count: u32,
+    }
+
+    impl Render for Badge {
+        fn render_into(&self, sink: &mut impl core::fmt::Write) -> core::fmt::Result {
+            write!(sink, r#"<span class="badge">{}</span>"#, self.count)
+        }
+    }
+
+    impl Component for Badge {}
+
+    #[test]
+    fn test_render_trait_child() {
+        let badge = Badge { count: 3 };
+        let p = Element::<P>::new().text("Inbox ").append_child(&badge);
+        assert_eq!(p.render(), r#"<p>Inbox <span class="badge">3</span></p>"#);
+
+        let mut out = String::new();
+        p.render_into(&mut out).unwrap();
+        assert_eq!(out, p.render());
+    }
+
+    #[test]
+    fn test_time_datetime() {
+        let date = Element::<Time>::new().datetime("2024-03-15").text("Mar 15");
+        assert_eq!(
+            date.render(),
+            r#"<time datetime="2024-03-15">Mar 15</time>"#
+        );
+
+        let stamp = Element::<Time>::new()
+            .datetime("2024-03-15T14:30:05.250+02:00")
+            .text("Later");
+        assert_eq!(
+            stamp.render(),
+            r#"<time datetime="2024-03-15T14:30:05.250+02:00">Later</time>"#
+        );
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "invalid datetime value")]
+    fn test_time_datetime_invalid() {
+        let _ = Element::<Time>::new().datetime("15/03/2024");
+    }
+
+    #[test]
+    fn test_renderer_typed() {
+        let mut renderer = crate::Renderer::with_capacity(64);
+        let list = Element::<Ul>::new().child::<Li, _>(|li| li.text("One"));
+        assert_eq!(renderer.render_reset(&list), "<ul><li>One</li></ul>");
+        let doc = Document::new().doctype();
+        assert_eq!(renderer.render_reset(&doc), "<!DOCTYPE html>");
+        assert_eq!(renderer.capacity(), 64);
+    }
+
+    #[test]
+    fn test_select_setters() {
+        let select = Element::<Select>::new()
+            .multiple(true)
+            .size(4)
+            .child::<Optgroup, _>(|g| {
+                g.label("Fruit")
+                    .child::<Option_, _>(|o| o.value("apple").selected(true).text("Apple"))
+                    .child::<Option_, _>(|o| {
+                        o.value("pear")
+                            .selected(true)
+                            .disabled(true)
+                            .label("Pear")
+                            .text("Pear (sold out)")
+                    })
+            })
+            .child::<Optgroup, _>(|g| g.label("Old").disabled(true))
+            .child::<Option_, _>(|o| o.value("none").selected(false).disabled(false));
+        assert_eq!(
+            select.render(),
+            concat!(
+                r#"<select multiple size="4">"#,
+                r#"<optgroup label="Fruit">"#,
+                r#"<option value="apple" selected>Apple</option>"#,
+                r#"<option value="pear" selected disabled label="Pear">Pear (sold out)</option>"#,
+                "</optgroup>",
+                r#"<optgroup label="Old" disabled></optgroup>"#,
+                r#"<option value="none"></option>"#,
+                "</select>"
+            )
+        );
+    }
+
+    #[test]
+    fn test_document_helper() {
+        let doc = document(
+            Element::<Head>::new()
+                .child::<Meta, _>(|m| m.attr("charset", "utf-8"))
+                .child::<Title, _>(|t| t.text("Page")),
+            Element::<Body>::new().child::<P, _>(|p| p.text("Body")),
+        );
+        assert_eq!(
+            doc.render(),
+            concat!(
+                "<!DOCTYPE html><html>",
+                r#"<head><meta charset="utf-8" /><title>Page</title></head>"#,
+                "<body><p>Body</p></body>",
+                "</html>"
+            )
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "document root must not have children")]
+    fn test_document_with_root_children() {
+        let root = Element::<Html>::new().child::<Body, _>(|b| b);
+        let _ = document_with_root(root, Element::<Head>::new(), Element::<Body>::new());
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "id \"a b\" must not contain whitespace")]
+    fn test_id_with_whitespace() {
+        let _ = Element::<Div>::new().id("a b");
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "use .classes")]
+    fn test_class_token_with_whitespace() {
+        let _ = Element::<Div>::new().class_token("a b");
+    }
+
+    #[test]
+    fn test_script_type_module() {
+        let script = Element::<Script>::new()
+            .r#type(ScriptType::Module)
+            .inline("import { a } from './a.js'; if (a < 1 && a > 0) {}");
+        assert_eq!(
+            script.render(),
+            r#"<script type="module">import { a } from './a.js'; if (a < 1 && a > 0) {}</script>"#
+        );
+
+        let classic = Element::<Script>::new().r#type(ScriptType::JavaScript);
+        assert_eq!(classic.render(), "<script></script>");
+    }
+
+    #[test]
+    fn test_script_json_ld_unescaped() {
+        let script = Element::<Script>::new()
+            .r#type(ScriptType::JsonLd)
+            .inline(r#"{"@type": "Organization", "name": "A & B <Ltd>"}"#);
+        assert_eq!(
+            script.render(),
+            r#"<script type="application/ld+json">{"@type": "Organization", "name": "A & B <Ltd>"}</script>"#
+        );
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "<script> text must not contain `</script`")]
+    fn test_script_inline_rejects_end_tag() {
+        let _ = Element::<Script>::new().inline(r#"{"y": "</SCRIPT"}"#);
+    }
+
+    #[cfg(not(debug_assertions))]
+    #[test]
+    fn test_script_inline_cannot_close_element() {
+        let script = Element::<Script>::new()
+            .r#type(ScriptType::ImportMap)
+            .inline(r#"{"x": "</script><b>", "y": "</SCRIPT"}"#);
+        assert_eq!(
+            script.render(),
+            r#"<script type="importmap">{"x": "<\/script><b>", "y": "<\/SCRIPT"}</script>"#
+        );
+    }
+
+    #[test]
+    fn test_polite_live_region() {
+        let status = Element::<Div>::new()
+            .role(Role::Status)
+            .aria_live(AriaLive::Polite)
+            .aria_atomic(true)
+            .aria_busy(false)
+            .text("3 results");
+        assert_eq!(
+            status.render(),
+            r#"<div role="status" aria-live="polite" aria-atomic="true" aria-busy="false">3 results</div>"#
+        );
+
+        let output = Element::<Output>::new().aria_live(AriaLive::Assertive);
+        assert_eq!(
+            output.render(),
+            r#"<output aria-live="assertive"></output>"#
+        );
+    }
+
+    #[test]
+    fn test_attribute_order_is_insertion_order() {
+        let build = || {
+            Element::<Div>::new()
+                .data("z", "1")
+                .class("a")
+                .id("main")
+                .class("b")
+                .attr("aria-label", "x")
+        };
+        assert_eq!(
+            build().render(),
+            r#"<div data-z="1" class="a b" id="main" aria-label="x"></div>"#
+        );
+        assert_eq!(build().render(), build().render());
+    }
+
+    #[test]
+    fn test_document_render_pretty() {
+        let doc = Document::new().doctype().root::<Html, _>(|html| {
+            html.child::<Head, _>(|h| h.child::<Title, _>(|t| t.text("T")))
+                .child::<Body, _>(|b| b.child::<P, _>(|p| p.text("Hi")))
+        });
+        assert_eq!(
+            doc.render_pretty(),
+            "<!DOCTYPE html>\n<html>\n  <head>\n    <title>T</title>\n  </head>\n  \
+             <body>\n    <p>Hi</p>\n  </body>\n</html>"
+        );
+    }
+
+    #[test]
+    fn test_control_associated_with_form_by_id() {
+        let body = Element::<Body>::new()
+            .child::<Form, _>(|f| f.id("signup").attr("action", "/signup"))
+            .child::<Button, _>(|b| b.form("signup").text("Sign up"));
+        assert_eq!(
+            body.render(),
+            r#"<body><form id="signup" action="/signup"></form><button form="signup">Sign up</button></body>"#
+        );
+
+        let input = Element::<Input>::new().form("signup");
+        assert_eq!(input.render(), r#"<input form="signup" />"#);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "must be a non-empty token")]
+    fn test_form_id_rejects_whitespace() {
+        let _ = Element::<Select>::new().form("sign up");
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "must be a non-empty token")]
+    fn test_form_id_rejects_empty() {
+        let _ = Element::<Textarea>::new().form("");
+    }
+
+    #[test]
+    fn test_checkbox_group() {
+        let group = checkbox_group(
+            "Toppings",
+            "topping",
+            "topping",
+            [
+                ("ham", "Ham", true),
+                ("olives", "Olives", false),
+                ("pineapple", "Pineapple", true),
+            ],
+        );
+        let html = group.render();
+        assert!(html.starts_with("<fieldset><legend>Toppings</legend><label"));
+        assert!(html.ends_with("</label></fieldset>"));
+        assert_eq!(html.matches(r#"type="checkbox""#).count(), 3);
+        assert_eq!(html.matches(r#"name="topping""#).count(), 3);
+        assert_eq!(html.matches(" checked").count(), 2);
+        for i in 0..3 {
+            assert!(html.contains(&alloc::format!(
+                r#"<label for="topping-{i}"><input type="checkbox" id="topping-{i}""#
+            )));
+        }
+        assert!(html.contains(r#"value="olives" />Olives</label>"#));
+    }
+
+    #[test]
+    fn test_radio_group() {
+        let group = radio_group(
+            "Size",
+            "size",
+            "shirt-size",
+            [
+                ("s", "Small", false),
+                ("m", "Medium", true),
+                ("l", "Large", false),
+            ],
+        );
+        assert_eq!(
+            group.render(),
+            concat!(
+                "<fieldset><legend>Size</legend>",
+                r#"<label for="shirt-size-0"><input type="radio" id="shirt-size-0" name="size" value="s" />Small</label>"#,
+                r#"<label for="shirt-size-1"><input type="radio" id="shirt-size-1" name="size" value="m" checked />Medium</label>"#,
+                r#"<label for="shirt-size-2"><input type="radio" id="shirt-size-2" name="size" value="l" />Large</label>"#,
+                "</fieldset>"
+            )
+        );
+    }
+
+    #[test]
+    fn test_details_summary_first() {
+        let faq = details(
+            Element::<Summary>::new()
+                .child::<H3, _>(|h| h.text("Shipping"))
+                .child::<Small, _>(|s| s.text("(2 answers)")),
+            vec![
+                Element::<P>::new().text("Worldwide."),
+                Element::<P>::new().text("Free over $50."),
+            ],
+        )
+        .bool_attr("open");
+        assert_eq!(
+            faq.render(),
+            "<details open><summary><h3>Shipping</h3><small>(2 answers)</small></summary>\
+             <p>Worldwide.</p><p>Free over $50.</p></details>"
+        );
+    }
+
+    #[test]
+    fn test_dialog_modal_with_dialog_form() {
+        let confirm = dialog_modal(vec![
+            Element::<P>::new().text("Delete this file?"),
+            Element::<P>::new().text("This cannot be undone."),
+        ])
+        .child::<Form, _>(|f| {
+            f.attr("method", Method::Dialog.to_attr_value())
+                .child::<Button, _>(|b| b.attr("value", "cancel").text("Cancel"))
+                .child::<Button, _>(|b| b.attr("value", "delete").text("Delete"))
+        });
+        assert_eq!(
+            confirm.render(),
+            "<dialog open><p>Delete this file?</p><p>This cannot be undone.</p>\
+             <form method=\"dialog\"><button value=\"cancel\">Cancel</button>\
+             <button value=\"delete\">Delete</button></form></dialog>"
+        );
+    }
+
+    #[test]
+    fn test_responsive_img_defaults() {
+        let img = responsive_img("a.jpg", "", "a-1x.jpg 1x, a-2x.jpg 2x", "100vw");
+        assert_eq!(
+            img.render(),
+            r#"<img src="a.jpg" alt srcset="a-1x.jpg 1x, a-2x.jpg 2x" sizes="100vw" loading="lazy" decoding="async" />"#
+        );
+
+        let hero = responsive_img("h.jpg", "Hero", "h.jpg 1200w", "100vw")
+            .loading(Loading::Eager)
+            .decoding(Decoding::Sync);
+        let html = hero.render();
+        assert!(html.contains(r#"loading="eager" decoding="sync""#));
+        assert_eq!(html.matches("loading=").count(), 1);
+        assert_eq!(html.matches("decoding=").count(), 1);
+    }
+
+    #[test]
+    fn test_content_hash_tracks_structure() {
+        let card = |title: &str| {
+            Element::<Div>::new()
+                .class("card")
+                .child::<H2, _>(|h| h.text(title))
+        };
+        assert!(card("A") == card("A"));
+        assert_eq!(card("A").content_hash(), card("A").content_hash());
+        assert_ne!(card("A").content_hash(), card("B").content_hash());
+        assert_ne!(
+            card("A").content_hash(),
+            card("A").attr("data-x", "1").content_hash()
+        );
+        assert_eq!(card("A").into_node(), card("A").into_node());
+    }
+
+    #[test]
+    fn test_style_css_is_raw_text() {
+        let style = Element::<Style>::new()
+            .media("(max-width: 600px)")
+            .nonce("r4nd0m")
+            .css("a > b {} p::before { content: \"&\"; }");
+        assert_eq!(
+            style.render(),
+            r#"<style media="(max-width: 600px)" nonce="r4nd0m">a > b {} p::before { content: "&"; }</style>"#
+        );
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "<style> text must not contain `</style`")]
+    fn test_style_css_rejects_end_tag() {
+        let _ = Element::<Style>::new().css("/* </STYLE><script> */");
+    }
+
+    #[cfg(not(debug_assertions))]
+    #[test]
+    fn test_style_css_cannot_close_element() {
+        let tricky = Element::<Style>::new().css("/* </STYLE><script> */");
+        assert_eq!(tricky.render(), r"<style>/* <\/STYLE><script> */</style>");
+    }
+
+    #[test]
+    fn test_raw_text_elements_keep_text_unescaped() {
+        let script = Element::<Script>::new()
+            .text("if (a < b && c > d) { x = \"<\\/scr")
+            .text("ipt>\"; }");
+        assert_eq!(
+            script.render(),
+            r#"<script>if (a < b && c > d) { x = "<\/script>"; }</script>"#
+        );
+        let style = Element::<Style>::new().text("a > b { content: '&'; }");
+        assert_eq!(style.render(), "<style>a > b { content: '&'; }</style>");
+
+        // Inside a pretty-printed tree too, and other text is still escaped.
+        let head = Element::<Head>::new()
+            .child::<Style, _>(|s| s.text("p > a {}"))
+            .child::<Title, _>(|t| t.text("a < b"));
+        assert_eq!(
+            head.render_pretty(),
+            "<head>\n  <style>p > a {}</style>\n  <title>a &lt; b</title>\n</head>"
+        );
+    }
+
+    #[test]
+    fn test_whitespace_constants_match_tag_names() {
+        fn check<E: HtmlElement>() {
+            assert_eq!(crate::preserves_whitespace(E::TAG), E::PRESERVE_WHITESPACE);
+            assert_eq!(crate::is_raw_text(E::TAG), E::RAW_TEXT);
+        }
+        check::<Pre>();
+        check::<Textarea>();
+        check::<Script>();
+        check::<Style>();
+        check::<Div>();
+        check::<Title>();
+        check::<Code>();
+    }
+
+    #[test]
+    fn test_mut_setters_in_loop() {
+        struct Column {
+            name: &'static str,
+            sortable: bool,
+            numeric: bool,
+        }
+        let columns = [
+            Column {
+                name: "name",
+                sortable: true,
+                numeric: false,
+            },
+            Column {
+                name: "age",
+                sortable: true,
+                numeric: true,
+            },
+        ];
+
+        let mut table = Element::<Table>::new();
+        table.set_id("people");
+        for col in &columns {
+            if col.sortable {
+                table.set_attr(alloc::format!("data-sort-{}", col.name), "asc");
+            }
+            if col.numeric {
+                table.set_class(alloc::format!("has-{}", col.name));
+            }
+        }
+        table.set_class("striped");
+        assert_eq!(
+            table.render(),
+            r#"<table id="people" data-sort-name="asc" data-sort-age="asc" class="has-age striped"></table>"#
+        );
+    }
+
+    #[test]
+    fn test_non_finite_numbers_are_omitted() {
+        let meter = Element::<Meter>::new()
+            .min(0.0)
+            .max(f64::INFINITY)
+            .low(f64::NEG_INFINITY)
+            .value(f64::NAN)
+            .optimum(0.5);
+        assert_eq!(meter.render(), r#"<meter min="0" optimum="0.5"></meter>"#);
+
+        let progress = Element::<Progress>::new().value(f64::NAN).max(100.0);
+        assert_eq!(progress.render(), r#"<progress max="100"></progress>"#);
+
+        let progress = Element::<Progress>::new().value(42.5).max(100.0);
+        assert_eq!(
+            progress.render(),
+            r#"<progress value="42.5" max="100"></progress>"#
+        );
+    }
+
+    #[test]
+    fn test_iframe_srcdoc_escapes_embedded_html() {
+        let inner = Element::<P>::new()
+            .attr("title", "it's \"quoted\"")
+            .text("Tom & Jerry");
+        assert_eq!(
+            inner.render(),
+            r#"<p title="it&#x27;s &quot;quoted&quot;">Tom &amp; Jerry</p>"#
+        );
+
+        let frame = Element::<Iframe>::new()
+            .attr("sandbox", "")
+            .srcdoc_element(&inner);
+        assert_eq!(
+            frame.render(),
+            "<iframe sandbox srcdoc=\"&lt;p title=&quot;it&amp;#x27;s &amp;quot;quoted&amp;quot;&quot;&gt;\
+             Tom &amp;amp; Jerry&lt;/p&gt;\"></iframe>"
+        );
+
+        let breakout = Element::<Iframe>::new().srcdoc("\"><script>alert(1)</script>");
+        assert!(!breakout.render().contains("\"><script>"));
+    }
+
+    #[test]
+    fn test_token_attributes_merge_in_order() {
+        use ironhtml_attributes::{Rel, RelTokens};
+
+        let div = Element::<Div>::new()
+            .class("a")
+            .classes(["b", "c"])
+            .class("d")
+            .classes(["c", "a"]);
+        assert_eq!(div.render(), r#"<div class="a b c d"></div>"#);
+
+        let link = Element::<A>::new()
+            .attr_value("rel", &Rel::Noopener)
+            .attr_value(
+                "rel",
+                &RelTokens::new().with(Rel::Noreferrer).with(Rel::Noopener),
+            );
+        assert_eq!(link.render(), r#"<a rel="noopener noreferrer"></a>"#);
+
+        let cell = Element::<Td>::new()
+            .headers(["name", "q1"])
+            .headers(["q1", "total"]);
+        assert_eq!(cell.render(), r#"<td headers="name q1 total"></td>"#);
+
+        let input = Element::<Input>::new()
+            .attr("aria-describedby", "hint")
+            .attr("aria-describedby", "error hint");
+        assert_eq!(input.render(), r#"<input aria-describedby="hint error" />"#);
+    }
+
+    #[test]
+    fn test_table_caption_is_first() {
+        let rows = || {
+            vec![
+                Element::<Tr>::new().child::<Td, _>(|td| td.text("a")),
+                Element::<Tr>::new().child::<Td, _>(|td| td.text("b")),
+            ]
+        };
+        assert_eq!(
+            table(Some(Element::<Caption>::new().text("Letters")), rows()).render(),
+            "<table><caption>Letters</caption><tr><td>a</td></tr><tr><td>b</td></tr></table>"
+        );
+        assert_eq!(
+            table(None, rows()).render(),
+            "<table><tr><td>a</td></tr><tr><td>b</td></tr></table>"
+        );
+
+        let late = Element::<Table>::new()
+            .append_child(rows())
+            .caption(Element::<Caption>::new().text("Late"));
+        assert!(late
+            .render()
+            .starts_with("<table><caption>Late</caption><tr>"));
+    }
+
+    #[test]
+    fn test_table_caption_replaces_caption() {
+        let table = Element::<Table>::new()
+            .caption(Element::<Caption>::new().text("Draft"))
+            .child::<Tr, _>(|tr| tr.child::<Td, _>(|td| td.text("1")))
+            .caption(Element::<Caption>::new().text("Final"));
+        assert_eq!(
+            table.render(),
+            "<table><caption>Final</caption><tr><td>1</td></tr></table>"
+        );
+    }
+
+    #[test]
+    fn test_textarea_setters() {
+        let textarea = Element::<Textarea>::new()
+            .rows(3)
+            .cols(20)
+            .maxlength(100)
+            .minlength(2)
+            .placeholder("Say hi")
+            .wrap(Wrap::Hard)
+            .readonly(false)
+            .required(true)
+            .text("discarded")
+            .value("Hello\n<world>");
+        assert_eq!(
+            textarea.render(),
+            r#"<textarea rows="3" cols="20" maxlength="100" minlength="2" placeholder="Say hi" wrap="hard" required>Hello
+&lt;world&gt;</textarea>"#
+        );
+    }
+
+    #[test]
+    fn test_area_shape() {
+        let circle = Element::<Area>::new().shape(Shape::Circle(90, 58, 3));
+        assert_eq!(
+            circle.render(),
+            r#"<area shape="circle" coords="90,58,3" />"#
+        );
+
+        let poly = Element::<Area>::new().shape(Shape::Poly(&[(0, 0), (10, 0), (5, 10)]));
+        assert_eq!(
+            poly.render(),
+            r#"<area shape="poly" coords="0,0,10,0,5,10" />"#
+        );
+
+        // Changing the shape replaces both attributes in place.
+        let whole = Element::<Area>::new()
+            .shape(Shape::Rect([0, 0, 1, 1]))
+            .shape(Shape::Default);
+        assert_eq!(whole.render(), r#"<area shape="default" />"#);
+    }
+
+    #[test]
+    fn test_file_input() {
+        let upload = Element::<Input>::new()
+            .attr_value("type", &InputType::File)
+            .accept(["image/*", " .pdf "])
+            .multiple(true)
+            .capture(Capture::Environment);
+        assert_eq!(
+            upload.render(),
+            r#"<input type="file" accept="image/*,.pdf" multiple capture="environment" />"#
+        );
+        assert_eq!(
+            Element::<Input>::new().multiple(false).render(),
+            "<input />"
+        );
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "invalid accept type")]
+    fn test_accept_rejects_empty_type() {
+        let _ = Element::<Input>::new().accept(["image/png", ""]);
+    }
+
+    #[test]
+    fn test_tabindex_and_accesskey() {
+        let div = Element::<Div>::new().tabindex(-1);
+        assert_eq!(div.render(), r#"<div tabindex="-1"></div>"#);
+        assert_eq!(div.tabindex(0).render(), r#"<div tabindex="0"></div>"#);
+
+        let link = Element::<A>::new().accesskeys(['h', '1']).text("Home");
+        assert_eq!(link.render(), r#"<a accesskey="h 1">Home</a>"#);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "accesskey cannot be whitespace")]
+    fn test_accesskey_rejects_whitespace() {
+        let _ = Element::<A>::new().accesskey(' ');
+    }
+
+    #[test]
+    fn test_cite() {
+        let url = "/src?a=1&b=\"2\"";
+        let escaped = "/src?a=1&amp;b=&quot;2&quot;";
+        assert_eq!(
+            Element::<Blockquote>::new().cite(url).render(),
+            format!(r#"<blockquote cite="{escaped}"></blockquote>"#)
+        );
+        assert_eq!(
+            Element::<Q>::new().cite(url).text("Hi").render(),
+            format!(r#"<q cite="{escaped}">Hi</q>"#)
+        );
+        assert_eq!(
+            Element::<Del>::new().cite("/a").cite("/b").render(),
+            r#"<del cite="/b"></del>"#
+        );
+        assert_eq!(
+            Element::<Ins>::new().cite("/why").render(),
+            r#"<ins cite="/why"></ins>"#
+        );
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "cite URL cannot be empty")]
+    fn test_cite_rejects_empty_url() {
+        let _ = Element::<Q>::new().cite(" ");
+    }
+
+    #[test]
+    fn test_fieldset_disabled() {
+        assert_eq!(
+            Element::<Fieldset>::new().disabled(true).render(),
+            "<fieldset disabled></fieldset>"
+        );
+        assert_eq!(
+            Element::<Fieldset>::new().disabled(false).render(),
+            "<fieldset></fieldset>"
+        );
+    }
+
+    #[test]
+    fn test_doctype_variants() {
+        let build = |doctype| Document::new().with_doctype(doctype).build();
+        assert_eq!(build(Doctype::Html5), "<!DOCTYPE html>");
+        assert_eq!(Document::new().doctype().build(), "<!DOCTYPE html>");
+        assert_eq!(
+            build(Doctype::XhtmlStrict),
+            r#"<!DOCTYPE html PUBLIC "-//W3C//DTD XHTML 1.0 Strict//EN" "http://www.w3.org/TR/xhtml1/DTD/xhtml1-strict.dtd">"#
+        );
+        assert_eq!(
+            build(Doctype::XhtmlTransitional),
+            r#"<!DOCTYPE html PUBLIC "-//W3C//DTD XHTML 1.0 Transitional//EN" "http://www.w3.org/TR/xhtml1/DTD/xhtml1-transitional.dtd">"#
+        );
+        assert_eq!(
+            build(Doctype::Html401Strict),
+            r#"<!DOCTYPE HTML PUBLIC "-//W3C//DTD HTML 4.01//EN" "http://www.w3.org/TR/html4/strict.dtd">"#
+        );
+        assert_eq!(
+            build(Doctype::Html401Transitional),
+            r#"<!DOCTYPE HTML PUBLIC "-//W3C//DTD HTML 4.01 Transitional//EN" "http://www.w3.org/TR/html4/loose.dtd">"#
+        );
+        assert_eq!(
+            build(Doctype::Custom("<!DOCTYPE svg>".into())),
+            "<!DOCTYPE svg>"
+        );
+
+        // An XHTML page: voids are self-closed.
+        let xhtml = Document::new()
+            .with_doctype(Doctype::XhtmlStrict)
+            .root::<Html, _>(|html| {
+                html.attr("xmlns", "http://www.w3.org/1999/xhtml")
+                    .child::<Body, _>(|b| b.child::<P, _>(|p| p.text("a").child::<Br, _>(|br| br)))
+            })
+            .build();
+        assert!(xhtml.ends_with(
+            r#"<html xmlns="http://www.w3.org/1999/xhtml"><body><p>a<br /></p></body></html>"#
+        ));
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use ironhtml_elements::*;
+    #[test]
+    fn test_node_count_and_depth() {
+        let empty = Element::<Div>::new();
+        assert_eq!((empty.node_count(), empty.max_depth()), (1, 1));
+
+        // <div><p>a<strong>b</strong></p><hr /></div>
+        let tree = Element::<Div>::new()
+            .child::<P, _>(|p| p.text("a").child::<Strong, _>(|s| s.text("b")))
+            .child::<Hr, _>(|hr| hr);
+        assert_eq!(tree.node_count(), 6);
+        assert_eq!(tree.max_depth(), 4);
+    }
 
     #[test]
-    fn test_simple_element() {
-        let html = Element::<Div>::new()
-            .class("container")
-            .text("Hello")
-            .render();
-        assert_eq!(html, r#"<div class="container">Hello</div>"#);
+    fn test_ping() {
+        let link = Element::<A>::new().ping(["/a?x=1&y=2", "/b"]);
+        assert_eq!(link.render(), r#"<a ping="/a?x=1&amp;y=2 /b"></a>"#);
+
+        let area = Element::<Area>::new().shape(Shape::Default).ping(["/p"]);
+        assert_eq!(area.render(), r#"<area shape="default" ping="/p" />"#);
     }
 
+    #[cfg(debug_assertions)]
     #[test]
-    fn test_nested_elements() {
-        let html = Element::<Table>::new()
-            .class("table")
-            .child::<Tr, _>(|tr| {
-                tr.child::<Td, _>(|td| td.text("Cell 1"))
-                    .child::<Td, _>(|td| td.text("Cell 2"))
-            })
-            .render();
+    #[should_panic(expected = "invalid URL in list")]
+    fn test_ping_rejects_empty_url() {
+        let _ = Element::<A>::new().ping(["/a", ""]);
+    }
 
+    #[test]
+    fn test_event_handlers() {
+        let form = Element::<Form>::new()
+            .on(EventHandler::Submit, "return check(this)")
+            .on(
+                EventHandler::Submit,
+                "return confirm('Send?') && check(this)",
+            );
         assert_eq!(
-            html,
-            r#"<table class="table"><tr><td>Cell 1</td><td>Cell 2</td></tr></table>"#
+            form.render(),
+            r#"<form onsubmit="return confirm(&#x27;Send?&#x27;) &amp;&amp; check(this)"></form>"#
         );
-    }
 
-    #[test]
-    fn test_list() {
-        let items = ["Apple", "Banana", "Cherry"];
-        let html = Element::<Ul>::new()
-            .children(items, |item, li: Element<Li>| li.text(item))
-            .render();
+        let button = Element::<Button>::new().on(EventHandler::Click, r#"go("</button>")"#);
+        assert_eq!(
+            button.render(),
+            r#"<button onclick="go(&quot;&lt;/button&gt;&quot;)"></button>"#
+        );
 
+        let body = Element::<Body>::new()
+            .on(EventHandler::Load, "init()")
+            .on_window(WindowEventHandler::PopState, "route()");
         assert_eq!(
-            html,
-            r"<ul><li>Apple</li><li>Banana</li><li>Cherry</li></ul>"
+            body.render(),
+            r#"<body onload="init()" onpopstate="route()"></body>"#
         );
     }
 
     #[test]
-    fn test_void_element() {
-        let html = Element::<Img>::new()
-            .attr("src", "image.jpg")
-            .attr("alt", "An image")
-            .render();
-
-        assert_eq!(html, r#"<img src="image.jpg" alt="An image" />"#);
+    fn test_document_render_xhtml() {
+        let doc = Document::new()
+            .with_doctype(Doctype::XhtmlStrict)
+            .root::<Html, _>(|h| {
+                h.attr("xmlns", "http://www.w3.org/1999/xhtml")
+                    .child::<Body, _>(|b| {
+                        b.child::<Form, _>(|f| {
+                            f.child::<Input, _>(|i| i.bool_attr("required"))
+                                .child::<Textarea, _>(|t| t)
+                        })
+                    })
+            });
+        let xhtml = doc.render_xhtml();
+        assert!(xhtml.ends_with(
+            "<html xmlns=\"http://www.w3.org/1999/xhtml\"><body><form>\
+             <input required=\"\" /><textarea></textarea></form></body></html>"
+        ));
+        assert!(doc.render().contains("<input required />"));
     }
 
     #[test]
-    fn test_document() {
-        let html = Document::new()
-            .doctype()
-            .root::<Html, _>(|html| {
-                html.attr("lang", "en")
-                    .child::<Head, _>(|head| {
-                        head.child::<Meta, _>(|meta| meta.attr("charset", "UTF-8"))
-                            .child::<Title, _>(|title| title.text("Hello"))
-                    })
-                    .child::<Body, _>(|body| body.child::<H1, _>(|h1| h1.text("Hello, World!")))
-            })
-            .build();
+    fn test_class_if() {
+        let card = |active: bool, muted: bool| {
+            Element::<Div>::new()
+                .class_if(active, "active")
+                .classes(["card", "card"])
+                .class_if(muted, "muted")
+                .class_if(active, "card")
+                .render()
+        };
+        assert_eq!(card(false, false), r#"<div class="card"></div>"#);
+        assert_eq!(card(true, true), r#"<div class="active card muted"></div>"#);
+        assert_eq!(
+            Element::<Div>::new().class_if(false, "x").render(),
+            "<div></div>"
+        );
+    }
 
+    #[test]
+    fn test_data_and_aria_attributes() {
+        let div = Element::<Div>::new()
+            .data("row-2", "<x>")
+            .aria("hidden", true)
+            .aria("label", String::from("Close"));
         assert_eq!(
-            html,
-            r#"<!DOCTYPE html><html lang="en"><head><meta charset="UTF-8" /><title>Hello</title></head><body><h1>Hello, World!</h1></body></html>"#
+            div.render(),
+            r#"<div data-row-2="&lt;x&gt;" aria-hidden="true" aria-label="Close"></div>"#
         );
     }
 
     #[test]
-    fn test_class_chaining() {
-        let html = Element::<Div>::new()
-            .class("btn")
-            .class("btn-primary")
-            .class("active")
-            .render();
+    fn test_data_accepts_xml_name_characters() {
+        let div = Element::<Div>::new().data("_row.2", "x").data("café", "y");
+        assert_eq!(div.render(), r#"<div data-_row.2="x" data-café="y"></div>"#);
+    }
 
-        assert_eq!(html, r#"<div class="btn btn-primary active"></div>"#);
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "invalid data attribute name")]
+    fn test_data_rejects_uppercase() {
+        let _ = Element::<Div>::new().data("userId", "1");
     }
 
+    #[cfg(debug_assertions)]
     #[test]
-    fn test_data_attributes() {
-        let html = Element::<Div>::new()
-            .data("id", "123")
-            .data("action", "submit")
-            .render();
+    #[should_panic(expected = "invalid data attribute name")]
+    fn test_data_rejects_colon() {
+        let _ = Element::<Div>::new().data("x:y", "1");
+    }
 
-        assert_eq!(html, r#"<div data-id="123" data-action="submit"></div>"#);
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "invalid ARIA attribute name")]
+    fn test_aria_rejects_prefixed_name() {
+        let _ = Element::<Div>::new().aria("aria-label", "x");
     }
 
     #[test]
-    fn test_conditional() {
-        let show = true;
-        let html = Element::<Div>::new()
-            .when(show, |e| e.child::<Span, _>(|s| s.text("Visible")))
-            .render();
+    fn test_boolean_attribute_values() {
+        let input = Element::<Input>::new()
+            .attr_value("checked", &true)
+            .attr_value("disabled", &false)
+            .attr_value("readonly", &String::from("true"))
+            .attr_opt("required", Some(true))
+            .attr_value("spellcheck", &false)
+            .attr_value("value", &true);
+        assert_eq!(
+            input.render(),
+            r#"<input checked readonly="true" required spellcheck="false" value="true" />"#
+        );
+        let div =
+            Element::<Div>::new().attr_value("hidden", &ironhtml_attributes::Hidden::UntilFound);
+        assert_eq!(div.render(), r#"<div hidden="until-found"></div>"#);
+    }
 
-        assert_eq!(html, r"<div><span>Visible</span></div>");
+    #[test]
+    fn test_attr_if_and_attr_opt() {
+        let field = |required: bool, hint: Option<String>| {
+            Element::<Input>::new()
+                .attr_if(required, "required", true)
+                .attr_if(required, "class", "req")
+                .attr_opt("title", hint)
+                .attr_opt("dir", None::<ironhtml_attributes::Dir>)
+                .class("field")
+                .render()
+        };
+        assert_eq!(field(false, None), r#"<input class="field" />"#);
+        assert_eq!(
+            field(true, Some("<name>".into())),
+            r#"<input required class="req field" title="&lt;name&gt;" />"#
+        );
+    }
 
-        let hide = false;
-        let html = Element::<Div>::new()
-            .when(hide, |e| e.child::<Span, _>(|s| s.text("Hidden")))
-            .render();
+    #[test]
+    fn test_attrs_spread() {
+        let div = Element::<Div>::new()
+            .attr("title", "old")
+            .attrs([("class", "a b"), ("title", "new"), ("hidden", "")])
+            .attrs(vec![(String::from("class"), String::from("b c"))]);
+        assert_eq!(
+            div.render(),
+            r#"<div title="new" class="a b c" hidden></div>"#
+        );
+    }
 
-        assert_eq!(html, r"<div></div>");
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "invalid attribute name")]
+    fn test_attrs_rejects_unsafe_names() {
+        let _ = Element::<Div>::new().attrs([("onclick=\"x\" y", "z")]);
     }
 
+    #[cfg(not(debug_assertions))]
     #[test]
-    fn test_escape_text() {
-        let html = Element::<Div>::new()
-            .text("<script>alert('xss')</script>")
-            .render();
+    fn test_attrs_skips_unsafe_names() {
+        let div = Element::<Div>::new().attrs([("onclick=\"x\" y", "z"), ("title", "t")]);
+        assert_eq!(div.render(), r#"<div title="t"></div>"#);
+    }
 
-        assert_eq!(
-            html,
-            r"<div>&lt;script&gt;alert('xss')&lt;/script&gt;</div>"
-        );
+    #[test]
+    fn test_estimated_size() {
+        let list = Element::<Ul>::new()
+            .id("nav")
+            .children(["One", "Two", "Three"], |item, li: Element<Li>| {
+                li.text(item)
+            })
+            .child::<Li, _>(|li| li.child::<Br, _>(|br| br));
+        let html = list.render();
+        // Only the void `<br />` is overestimated.
+        assert!(list.estimated_size() >= html.len());
+        assert!(list.estimated_size() <= html.len() + 8);
     }
 
     #[test]
-    fn test_escape_attr() {
-        let html = Element::<Div>::new()
-            .attr("data-value", "say \"hello\"")
-            .render();
+    fn test_display() {
+        let div = Element::<Div>::new()
+            .attr("title", "\"quoted\"")
+            .child::<P, _>(|p| p.text("Tom & Jerry"));
+        assert_eq!(format!("{div}"), div.render());
+        assert_eq!(format!("{div:#}"), div.render_pretty());
+        let doc = Document::new().doctype().root::<Html, _>(|html| html);
+        assert_eq!(doc.to_string(), doc.render());
+        assert_eq!(format!("{doc:#}"), doc.render_pretty());
+        assert!(doc.render_pretty().starts_with("<!DOCTYPE html>\n<html>"));
+    }
 
-        assert_eq!(html, r#"<div data-value="say &quot;hello&quot;"></div>"#);
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_to_json() {
+        let doc = Document::new().doctype().root::<Html, _>(|html| {
+            html.child::<Body, _>(|body| {
+                body.child::<Div, _>(|div| {
+                    div.child::<Img, _>(|img| img.attr("src", "a.png").attr("alt", "A"))
+                        .raw("<hr>")
+                })
+            })
+        });
+        assert_eq!(
+            doc.to_json(),
+            concat!(
+                r#"[{"raw":"<!DOCTYPE html>"},{"tag":"html","attrs":{},"children":["#,
+                r#"{"tag":"body","attrs":{},"children":[{"tag":"div","attrs":{},"children":["#,
+                r#"{"tag":"img","attrs":{"src":"a.png","alt":"A"},"children":[]},"#,
+                r#"{"raw":"<hr>"}]}]}]}]"#
+            )
+        );
     }
 
     #[test]
-    fn test_type_safe_attribute_value() {
-        use ironhtml_attributes::{InputType, Loading};
+    fn test_collect_children() {
+        let users = ["Ada", "Grace"];
+        let items: Fragment<Ul> = users
+            .iter()
+            .map(|name| Element::<Li>::new().text(*name))
+            .collect();
+        let mut list = Element::<Ul>::new().append_child(items);
+        list.extend([Element::<Li>::new().text("Alan")]);
+        assert_eq!(
+            list.render(),
+            "<ul><li>Ada</li><li>Grace</li><li>Alan</li></ul>"
+        );
+    }
 
-        let html = Element::<Input>::new()
-            .attr_value(ironhtml_attributes::input::TYPE, &InputType::Email)
-            .attr("name", "email")
-            .render();
+    #[test]
+    fn test_srcset_on_picture() {
+        use ironhtml_attributes::{Descriptor, SrcSet};
 
-        assert_eq!(html, r#"<input type="email" name="email" />"#);
+        let wide = SrcSet::new()
+            .candidate("wide-800.jpg", Descriptor::Width(800))
+            .candidate("wide-1600.jpg", Descriptor::Width(1600));
+        let fallback = SrcSet::new().candidate("small.jpg", Descriptor::Density(1.0));
+        let picture = Element::<Picture>::new()
+            .child::<Source, _>(|s| s.attr("media", "(min-width: 800px)").srcset(&wide))
+            .child::<Img, _>(|i| {
+                i.attr("src", "small.jpg")
+                    .attr("alt", "")
+                    .attr("srcset", "old.jpg 1x")
+                    .srcset(&fallback)
+            });
+        assert_eq!(
+            picture.render(),
+            concat!(
+                r#"<picture><source media="(min-width: 800px)" "#,
+                r#"srcset="wide-800.jpg 800w, wide-1600.jpg 1600w" />"#,
+                r#"<img src="small.jpg" alt srcset="small.jpg 1x" /></picture>"#
+            )
+        );
+    }
 
-        let html = Element::<Img>::new()
-            .attr("src", "large.jpg")
-            .attr_value(ironhtml_attributes::img::LOADING, &Loading::Lazy)
-            .render();
+    #[test]
+    fn test_inline_style_cannot_break_out_of_attribute() {
+        let style = InlineStyle::new().color(r#"red" onclick="alert(1); x"#);
+        let div = Element::<Div>::new().style(&style);
+        assert_eq!(
+            div.render(),
+            r#"<div style="color: red\&quot; onclick=\&quot;alert(1)\; x"></div>"#
+        );
+    }
 
-        assert_eq!(html, r#"<img src="large.jpg" loading="lazy" />"#);
+    #[test]
+    fn test_cell_spans() {
+        let row = Element::<Tr>::new()
+            .child::<Th, _>(|th| th.rowspan(0).rowspan(2).text("Q1"))
+            .child::<Td, _>(|td| td.colspan(1000).text("x"));
+        assert_eq!(
+            row.render(),
+            r#"<tr><th rowspan="2">Q1</th><td colspan="1000">x</td></tr>"#
+        );
     }
 
     #[test]
-    fn test_form() {
-        use ironhtml_attributes::Method;
+    #[should_panic(expected = "colspan must be between 1 and 1000, got 0")]
+    fn test_colspan_rejects_zero() {
+        let _ = Element::<Td>::new().colspan(0);
+    }
 
-        let html = Element::<Form>::new()
-            .attr("action", "/submit")
-            .attr_value(ironhtml_attributes::form::METHOD, &Method::Post)
-            .child::<Input, _>(|i| {
-                i.attr("type", "text")
-                    .attr("name", "username")
-                    .attr("placeholder", "Username")
-            })
-            .child::<Input, _>(|i| i.attr("type", "password").attr("name", "password"))
-            .child::<Button, _>(|b| b.attr("type", "submit").text("Login"))
-            .render();
+    #[test]
+    fn test_custom_element() {
+        let widget = CustomElement::named("my-widget")
+            .id("w")
+            .bool_attr("hidden")
+            .child::<Li, _>(|li| li.text("a"))
+            .text("<b>")
+            .append_child(Element::<Strong>::new().text("x"));
+        assert_eq!(widget.tag(), "my-widget");
+        assert_eq!(
+            widget.render(),
+            r#"<my-widget id="w" hidden><li>a</li>&lt;b&gt;<strong>x</strong></my-widget>"#
+        );
 
-        assert!(html.contains(r#"<form action="/submit" method="post">"#));
-        assert!(html.contains(r#"<input type="text" name="username""#));
-        assert!(html.contains(r#"<button type="submit">Login</button>"#));
+        let p = Element::<P>::new().append_child(widget.clone());
+        assert_eq!(p.render(), format!("<p>{}</p>", widget.render()));
+        let nested = CustomElement::named("x-outer").append_child(CustomElement::named("x-inner"));
+        assert_eq!(nested.render(), "<x-outer><x-inner></x-inner></x-outer>");
     }
 
     #[test]
-    fn test_anchor_link() {
-        use ironhtml_attributes::Target;
+    #[should_panic(expected = "invalid custom element name")]
+    fn test_custom_element_rejects_invalid_name() {
+        let _ = CustomElement::named("div");
+    }
 
-        let html = Element::<A>::new()
-            .attr("href", "https://example.com")
-            .attr_value(ironhtml_attributes::anchor::TARGET, &Target::Blank)
-            .attr("rel", "noopener noreferrer")
-            .text("External Link")
-            .render();
+    #[test]
+    fn test_external_link() {
+        let link = Element::<A>::new()
+            .attr("rel", "nofollow noopener")
+            .attr("href", "/old")
+            .external("https://example.com/?a=1&b=2");
+        assert_eq!(
+            link.render(),
+            r#"<a rel="nofollow noopener noreferrer" href="https://example.com/?a=1&amp;b=2" target="_blank"></a>"#
+        );
+    }
 
+    #[test]
+    fn test_render_document() {
+        let html = Element::<Html>::new().child::<Body, _>(|b| b);
         assert_eq!(
-            html,
-            r#"<a href="https://example.com" target="_blank" rel="noopener noreferrer">External Link</a>"#
+            html.render_document(),
+            "<!DOCTYPE html><html><body></body></html>"
+        );
+        assert_eq!(
+            html.render_document(),
+            Document::new()
+                .doctype()
+                .root::<Html, _>(|h| h.child::<Body, _>(|b| b))
+                .render()
+        );
+        let xhtml = html.render_document_with(&Doctype::XhtmlStrict);
+        assert!(xhtml.starts_with("<!DOCTYPE html PUBLIC \"-//W3C//DTD XHTML 1.0 Strict//EN\""));
+        assert!(xhtml.ends_with("<html><body></body></html>"));
+        assert_eq!(
+            html.render_document_with(&Doctype::Custom("<!doctype html>".into())),
+            "<!doctype html><html><body></body></html>"
         );
     }
 
     #[test]
-    fn test_select_options() {
-        let html = Element::<Select>::new()
-            .attr("name", "country")
-            .child::<Option_, _>(|o| o.attr("value", "us").text("United States"))
-            .child::<Option_, _>(|o| o.attr("value", "uk").text("United Kingdom"))
-            .child::<Option_, _>(|o| o.attr("value", "ca").text("Canada"))
-            .render();
+    fn test_html_lang() {
+        let html = Element::<Html>::new().lang("en");
+        assert_eq!(html.render(), r#"<html lang="en"></html>"#);
 
-        assert!(html.contains(r#"<select name="country">"#));
-        assert!(html.contains(r#"<option value="us">United States</option>"#));
-        assert!(html.contains(r#"<option value="uk">United Kingdom</option>"#));
+        let html = Element::<Html>::new().lang_xhtml("pt-BR");
+        assert_eq!(
+            html.render(),
+            r#"<html lang="pt-BR" xml:lang="pt-BR"></html>"#
+        );
     }
 
+    #[cfg(debug_assertions)]
     #[test]
-    fn test_definition_list() {
-        let html = Element::<Dl>::new()
-            .child::<Dt, _>(|dt| dt.text("HTML"))
-            .child::<Dd, _>(|dd| dd.text("HyperText Markup Language"))
-            .child::<Dt, _>(|dt| dt.text("CSS"))
-            .child::<Dd, _>(|dd| dd.text("Cascading Style Sheets"))
-            .render();
+    #[should_panic(expected = "invalid language tag")]
+    fn test_html_lang_empty() {
+        let _ = Element::<Html>::new().lang("");
+    }
 
-        assert!(html.contains("<dl>"));
-        assert!(html.contains("<dt>HTML</dt>"));
-        assert!(html.contains("<dd>HyperText Markup Language</dd>"));
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "invalid language tag")]
+    fn test_html_lang_bad_chars() {
+        let _ = Element::<Html>::new().lang("en_US");
     }
 
     #[test]
-    fn test_complex_table() {
-        let html = Element::<Table>::new()
-            .class("table")
-            .child::<Thead, _>(|thead| {
-                thead.child::<Tr, _>(|tr| {
-                    tr.child::<Th, _>(|th| th.text("Name"))
-                        .child::<Th, _>(|th| th.text("Age"))
-                })
-            })
-            .child::<Tbody, _>(|tbody| {
-                tbody
-                    .child::<Tr, _>(|tr| {
-                        tr.child::<Td, _>(|td| td.text("Alice"))
-                            .child::<Td, _>(|td| td.text("30"))
-                    })
-                    .child::<Tr, _>(|tr| {
-                        tr.child::<Td, _>(|td| td.text("Bob"))
-                            .child::<Td, _>(|td| td.text("25"))
-                    })
-            })
-            .render();
+    fn test_meta_helpers() {
+        let csp = meta_http_equiv(HttpEquiv::ContentSecurityPolicy, "default-src 'self'");
+        assert_eq!(
+            csp.render(),
+            r#"<meta http-equiv="content-security-policy" content="default-src &#x27;self&#x27;" />"#
+        );
 
-        assert!(html.contains("<thead>"));
-        assert!(html.contains("<th>Name</th>"));
-        assert!(html.contains("<tbody>"));
-        assert!(html.contains("<td>Alice</td>"));
+        let refresh = meta_http_equiv(HttpEquiv::Refresh, "5; url=/next");
+        assert_eq!(
+            refresh.render(),
+            r#"<meta http-equiv="refresh" content="5; url=/next" />"#
+        );
+
+        let head =
+            Element::<Head>::new().child::<Meta, _>(|_| meta_name(MetaName::Description, "A page"));
+        assert_eq!(
+            head.render(),
+            r#"<head><meta name="description" content="A page" /></head>"#
+        );
     }
 
     #[cfg(feature = "std")]
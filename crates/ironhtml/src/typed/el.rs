@@ -0,0 +1,188 @@
+//! Lowercase constructors for every element, for building without the macro.
+//!
+//! Each function returns an empty [`Element`] of the matching type, so
+//! `div()` is shorthand for `Element::<Div>::new()`. Import them all with a
+//! glob and chain builder methods as usual; finished elements are added to a
+//! parent with [`append_child`](Element::append_child).
+//!
+//! ## Example
+//!
+//! ```rust
+//! use ironhtml::typed::el::*;
+//!
+//! let card = div()
+//!     .class("card")
+//!     .append_child(h2().text("Title"))
+//!     .append_child(p().text("Body ").append_child(a().attr("href", "/more").text("more")));
+//!
+//! assert_eq!(
+//!     card.render(),
+//!     r#"<div class="card"><h2>Title</h2><p>Body <a href="/more">more</a></p></div>"#
+//! );
+//! ```
+//!
+//! Nesting is still checked at compile time:
+//!
+//! ```rust,compile_fail
+//! use ironhtml::typed::el::*;
+//!
+//! // Ul cannot contain Div
+//! let invalid = ul().append_child(div());
+//! ```
+//!
+//! Some names also exist elsewhere in the crate, such as the typed
+//! [`super::table`] and [`super::details`] helpers and the untyped
+//! [`crate::div`], so glob-importing this module next to another glob makes
+//! them ambiguous. Import it on its own or refer to it by path, as in
+//! `el::table()`.
+
+use super::Element;
+use ironhtml_elements::{
+    Abbr, Address, Area, Article, Aside, Audio, Base, Bdi, Bdo, Blockquote, Body, Br, Button,
+    Canvas, Caption, Cite, Code, Col, Colgroup, Data, Datalist, Dd, Del, Details, Dfn, Dialog, Div,
+    Dl, Dt, Em, Embed, Fieldset, Figcaption, Figure, Footer, Form, Head, Header, Hgroup, Hr, Html,
+    Iframe, Img, Input, Ins, Kbd, Label, Legend, Li, Link, Main, Map, Mark, Math, Menu, Meta,
+    Meter, Nav, Noscript, Object, Ol, Optgroup, Option_, Output, Param, Picture, Pre, Progress, Rp,
+    Rt, Ruby, Samp, Script, Search, Section, Select, Slot, Small, Source, Span, Strong, Style, Sub,
+    Summary, Sup, Svg, Table, Tbody, Td, Template, Textarea, Tfoot, Th, Thead, Time, Title, Tr,
+    Track, Ul, Var, Video, Wbr, A, B, H1, H2, H3, H4, H5, H6, I, P, Q, S, U,
+};
+
+macro_rules! constructors {
+    ($($name:ident => $ty:ident,)*) => {
+        $(
+            #[doc = concat!("Create an empty `<", stringify!($name), ">` element.")]
+            #[must_use]
+            pub const fn $name() -> Element<$ty> {
+                Element::new()
+            }
+        )*
+    };
+}
+
+constructors! {
+    html => Html,
+    head => Head,
+    title => Title,
+    base => Base,
+    link => Link,
+    meta => Meta,
+    style => Style,
+    body => Body,
+    article => Article,
+    section => Section,
+    nav => Nav,
+    aside => Aside,
+    h1 => H1,
+    h2 => H2,
+    h3 => H3,
+    h4 => H4,
+    h5 => H5,
+    h6 => H6,
+    hgroup => Hgroup,
+    header => Header,
+    footer => Footer,
+    address => Address,
+    main => Main,
+    div => Div,
+    p => P,
+    hr => Hr,
+    pre => Pre,
+    blockquote => Blockquote,
+    ol => Ol,
+    ul => Ul,
+    menu => Menu,
+    li => Li,
+    dl => Dl,
+    dt => Dt,
+    dd => Dd,
+    figure => Figure,
+    figcaption => Figcaption,
+    search => Search,
+    a => A,
+    em => Em,
+    strong => Strong,
+    small => Small,
+    s => S,
+    cite => Cite,
+    q => Q,
+    dfn => Dfn,
+    abbr => Abbr,
+    ruby => Ruby,
+    rt => Rt,
+    rp => Rp,
+    data => Data,
+    time => Time,
+    code => Code,
+    var => Var,
+    samp => Samp,
+    kbd => Kbd,
+    sub => Sub,
+    sup => Sup,
+    i => I,
+    b => B,
+    u => U,
+    mark => Mark,
+    bdi => Bdi,
+    bdo => Bdo,
+    span => Span,
+    br => Br,
+    wbr => Wbr,
+    img => Img,
+    picture => Picture,
+    source => Source,
+    audio => Audio,
+    video => Video,
+    track => Track,
+    map => Map,
+    area => Area,
+    iframe => Iframe,
+    embed => Embed,
+    object => Object,
+    param => Param,
+    svg => Svg,
+    math => Math,
+    script => Script,
+    noscript => Noscript,
+    template => Template,
+    slot => Slot,
+    canvas => Canvas,
+    table => Table,
+    caption => Caption,
+    colgroup => Colgroup,
+    col => Col,
+    thead => Thead,
+    tbody => Tbody,
+    tfoot => Tfoot,
+    tr => Tr,
+    th => Th,
+    td => Td,
+    form => Form,
+    label => Label,
+    input => Input,
+    button => Button,
+    select => Select,
+    datalist => Datalist,
+    optgroup => Optgroup,
+    option => Option_,
+    textarea => Textarea,
+    output => Output,
+    progress => Progress,
+    meter => Meter,
+    fieldset => Fieldset,
+    legend => Legend,
+    details => Details,
+    summary => Summary,
+    dialog => Dialog,
+    del => Del,
+    ins => Ins,
+}
+
+#[cfg(feature = "experimental-elements")]
+use ironhtml_elements::{Fencedframe, Portal};
+
+#[cfg(feature = "experimental-elements")]
+constructors! {
+    fencedframe => Fencedframe,
+    portal => Portal,
+}
@@ -0,0 +1,91 @@
+//! Support for `#expr` children in [`html!`](crate::html).
+//!
+//! `#expr` adds a typed [`Element`] as a checked child, a [`Component`] as
+//! its HTML, and any other [`Display`] value as escaped text. The macro
+//! expands it to `(&&Interpolate(&expr)).child()`, and method lookup tries
+//! the traits in that order: [`AsElement`] is implemented on
+//! `&&Interpolate`, [`AsComponent`] on `&Interpolate`, and [`AsText`] on
+//! `Interpolate` itself. So an element or component that is also
+//! `Display` is never turned into text.
+//!
+//! Only the macro should name these items; they are not part of the
+//! public API.
+
+use alloc::vec::Vec;
+use core::fmt::Display;
+use ironhtml_elements::{CanContain, HtmlElement, Text};
+
+use super::{Component, Element, IntoChild, TypedNode};
+
+/// A borrowed `#expr` value, before its kind of child is known.
+pub struct Interpolate<'a, T: ?Sized>(pub &'a T);
+
+impl<T: ?Sized> Clone for Interpolate<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ?Sized> Copy for Interpolate<'_, T> {}
+
+/// Interpolates a typed [`Element`], checked against the parent's content
+/// model.
+pub trait AsElement {
+    /// The child to add.
+    type Child;
+
+    /// The child to add.
+    fn child(self) -> Self::Child;
+}
+
+impl<'a, C: HtmlElement> AsElement for &&Interpolate<'a, Element<C>> {
+    type Child = &'a Element<C>;
+
+    fn child(self) -> &'a Element<C> {
+        self.0
+    }
+}
+
+/// Interpolates a [`Component`] as its HTML.
+pub trait AsComponent {
+    /// The child to add.
+    type Child;
+
+    /// The child to add.
+    fn child(self) -> Self::Child;
+}
+
+impl<'a, T: Component + ?Sized> AsComponent for &Interpolate<'a, T> {
+    type Child = &'a T;
+
+    fn child(self) -> &'a T {
+        self.0
+    }
+}
+
+/// Interpolates a [`Display`] value as text.
+pub trait AsText {
+    /// The child to add.
+    type Child;
+
+    /// The child to add.
+    fn child(self) -> Self::Child;
+}
+
+impl<'a, T: Display + ?Sized> AsText for Interpolate<'a, T> {
+    type Child = DisplayText<'a, T>;
+
+    fn child(self) -> DisplayText<'a, T> {
+        DisplayText(self.0)
+    }
+}
+
+/// A [`Display`] value added as text, like
+/// [`Element::text_display`](super::Element::text_display).
+pub struct DisplayText<'a, T: ?Sized>(&'a T);
+
+impl<P: CanContain<Text>, T: Display + ?Sized> IntoChild<P> for DisplayText<'_, T> {
+    fn push_into(self, nodes: &mut Vec<TypedNode>) {
+        crate::push_text(nodes, self.0);
+    }
+}
@@ -0,0 +1,17 @@
+//! Compile-fail tests for the content model.
+//!
+//! Each file in `tests/ui/` nests an element where the HTML spec forbids it,
+//! misspells an element or event name, gives a literal `class` or `id` that
+//! selectors cannot match or an invalid custom element name, or makes a
+//! syntax mistake in `html!`, and must be rejected at compile time with a
+//! message pointing at the mistake. The expected compiler output lives next
+//! to it in a `.stderr` file; regenerate it with `TRYBUILD=overwrite` after
+//! an intentional diagnostic change.
+
+#![cfg(feature = "macros")]
+
+#[test]
+fn content_model() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
@@ -3,6 +3,8 @@
 #![cfg(feature = "macros")]
 
 use ironhtml::html;
+use ironhtml::typed::{Element, TypedNode};
+use ironhtml_elements::{Li, Span};
 
 #[test]
 fn test_simple_element() {
@@ -116,6 +118,17 @@ fn test_boolean_attribute() {
     );
 }
 
+#[test]
+fn test_boolean_attribute_with_empty_parens() {
+    let bare = html! { input.name("email").required.disabled };
+    let called = html! { input.name("email").required().disabled() };
+    assert_eq!(called.render(), bare.render());
+    assert_eq!(
+        called.render(),
+        r#"<input name="email" required disabled />"#
+    );
+}
+
 #[test]
 fn test_expression_in_text() {
     let name = "World";
@@ -123,6 +136,23 @@ fn test_expression_in_text() {
     assert_eq!(elem.render(), "<p>Hello, World!</p>");
 }
 
+#[test]
+fn test_display_expression_in_text() {
+    struct Tag(&'static str);
+    impl core::fmt::Display for Tag {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            write!(f, "<{}>", self.0)
+        }
+    }
+
+    let age: u32 = 36;
+    let tag = Tag("b");
+    let adult = age >= 18;
+    let elem = html! { p { "Age " #age ", tag " #tag ", " #adult } };
+    assert_eq!(elem.render(), "<p>Age 36, tag &lt;b&gt;, true</p>");
+    assert_eq!(elem.node_count(), 2);
+}
+
 #[test]
 fn test_expression_in_attribute() {
     let class_name = "container";
@@ -130,6 +160,167 @@ fn test_expression_in_attribute() {
     assert_eq!(elem.render(), r#"<div class="container"></div>"#);
 }
 
+#[test]
+fn test_computed_child_element() {
+    fn badge(label: &str, big: bool) -> Element<Span> {
+        if big {
+            html! { span.class("badge-lg") { #label } }
+        } else {
+            html! { span.class("badge") { #label } }
+        }
+    }
+
+    let big = false;
+    let elem = html! {
+        div {
+            "Status: "
+            { badge("new", big) }
+            { if big { badge("x", true) } else { badge("y", true) } }
+        }
+    };
+    assert_eq!(
+        elem.render(),
+        r#"<div>Status: <span class="badge">new</span><span class="badge-lg">y</span></div>"#
+    );
+}
+
+struct Avatar<'a> {
+    user: &'a str,
+}
+
+impl ironhtml::Render for Avatar<'_> {
+    fn render_into(&self, sink: &mut impl core::fmt::Write) -> core::fmt::Result {
+        write!(
+            sink,
+            r#"<img src="/avatars/{0}.png" alt="{0}" />"#,
+            ironhtml::escape_attr(self.user)
+        )
+    }
+}
+
+impl ironhtml::typed::Component for Avatar<'_> {}
+
+#[test]
+fn test_render_component_child() {
+    let avatar = Avatar { user: "ada" };
+    let card = html! {
+        div.class("card") {
+            { &avatar }
+            span { "Ada" }
+        }
+    };
+    assert_eq!(
+        card.render(),
+        r#"<div class="card"><img src="/avatars/ada.png" alt="ada" /><span>Ada</span></div>"#
+    );
+}
+
+#[test]
+fn test_interpolate_component() {
+    let avatar = Avatar { user: "ada" };
+    let name = "<Ada>";
+    let card = html! { div { #avatar span { #name } } };
+    assert_eq!(
+        card.render(),
+        r#"<div><img src="/avatars/ada.png" alt="ada" /><span>&lt;Ada&gt;</span></div>"#
+    );
+
+    // A typed element is added as a child, not as its escaped markup.
+    let icon = html! { i.class("icon") };
+    let button = html! { button { #icon " Save" } };
+    assert_eq!(
+        button.render(),
+        r#"<button><i class="icon"></i> Save</button>"#
+    );
+}
+
+#[test]
+fn test_render_borrowed_components_in_loop() {
+    let avatars = vec![Avatar { user: "ada" }, Avatar { user: "grace" }];
+    let expected = r#"<ul><li><img src="/avatars/ada.png" alt="ada" /></li><li><img src="/avatars/grace.png" alt="grace" /></li></ul>"#;
+
+    // Iterating a slice yields `&Avatar`.
+    let list = html! {
+        ul {
+            for avatar in #avatars.as_slice() {
+                li { { avatar } }
+            }
+        }
+    };
+    assert_eq!(list.render(), expected);
+
+    // A list of references yields `&&Avatar`, which renders the same.
+    let borrowed: Vec<&Avatar> = avatars.iter().collect();
+    let list = html! {
+        ul {
+            for avatar in #&borrowed {
+                li { { avatar } }
+            }
+        }
+    };
+    assert_eq!(list.render(), expected);
+
+    let mut html = String::new();
+    ironhtml::Render::render_into(&borrowed[0], &mut html).unwrap();
+    assert_eq!(html, r#"<img src="/avatars/ada.png" alt="ada" />"#);
+}
+
+#[test]
+fn test_mapped_iterator_children() {
+    fn item(name: &str) -> Element<Li> {
+        Element::<Li>::new().text(name)
+    }
+
+    let names = ["Ada", "Grace"];
+    let nicknames = [None, Some("Amazing Grace")];
+    let list = html! {
+        ul.class("people") {
+            li { "Header" }
+            { names.iter().map(|n| item(n)) }
+            { nicknames.iter().filter_map(|n| n.map(item)) }
+        }
+    };
+    assert_eq!(
+        list.render(),
+        r#"<ul class="people"><li>Header</li><li>Ada</li><li>Grace</li><li>Amazing Grace</li></ul>"#
+    );
+
+    // `names` is only borrowed while the list is built.
+    assert_eq!(names.len(), 2);
+}
+
+#[test]
+fn test_user_variables_named_like_internals() {
+    let e = "e-value";
+    let el = "el-value";
+    let node = "node-value";
+    let builder = "builder-value";
+    let items = ["x"];
+    let list = html! {
+        ul.class(#e) {
+            li { #e }
+            li.id(#el) { #node }
+            for e in #items {
+                li { #e " " #builder }
+            }
+            if #true {
+                li { #e }
+            }
+        }
+    };
+    assert_eq!(
+        list.render(),
+        concat!(
+            r#"<ul class="e-value">"#,
+            "<li>e-value</li>",
+            r#"<li id="el-value">node-value</li>"#,
+            "<li>x builder-value</li>",
+            "<li>e-value</li>",
+            "</ul>"
+        )
+    );
+}
+
 #[test]
 fn test_for_loop() {
     let items = ["Apple", "Banana", "Cherry"];
@@ -148,7 +339,7 @@ fn test_for_loop() {
 
 #[test]
 fn test_for_loop_with_index() {
-    let items: Vec<String> = vec!["A", "B", "C"]
+    let items: Vec<String> = ["A", "B", "C"]
         .iter()
         .enumerate()
         .map(|(i, item)| format!("{}: {}", i + 1, item))
@@ -166,6 +357,280 @@ fn test_for_loop_with_index() {
     );
 }
 
+#[test]
+fn test_separators_between_children_are_ignored() {
+    let name = "Ada";
+    let plain = html! {
+        ul {
+            li { "a" }
+            li { #name "!" }
+            if #true { li { "b" } }
+            { Element::<Li>::new().text("c") }
+        }
+    };
+    let separated = html! {
+        ul {
+            li { "a" },
+            li { #name; "!", };
+            if #true { li { "b" }; }
+            { Element::<Li>::new().text("c") },
+        }
+    };
+    assert_eq!(separated.render(), plain.render());
+    assert_eq!(
+        plain.render(),
+        "<ul><li>a</li><li>Ada!</li><li>b</li><li>c</li></ul>"
+    );
+}
+
+#[test]
+fn test_list_children() {
+    // Lists take `<li>` plus the script-supporting elements, and nothing
+    // else; see tests/ui/div_in_ul.rs and tests/ui/flow_in_ol_and_menu.rs.
+    let list = html! {
+        ol {
+            li { "One" }
+            template {}
+            script { "fill()" }
+        }
+    };
+    assert_eq!(
+        list.render(),
+        "<ol><li>One</li><template></template><script>fill()</script></ol>"
+    );
+    let menu = html! { menu { li { "Copy" } } };
+    assert_eq!(menu.render(), "<menu><li>Copy</li></menu>");
+}
+
+#[test]
+fn test_custom_element() {
+    // A custom element takes any children, checked or not, and its own
+    // children still go through the content model.
+    let widget = html! {
+        p {
+            element("my-widget").attr("theme", "dark").key(1) {
+                li { "item" }
+                "text"
+                span.class("label") { "x" }
+            }
+        }
+    };
+    assert_eq!(
+        widget.render(),
+        r#"<p><my-widget theme="dark"><li>item</li>text<span class="label">x</span></my-widget></p>"#
+    );
+
+    let root = html! { element("app-root").id("app") };
+    assert_eq!(root.render(), r#"<app-root id="app"></app-root>"#);
+}
+
+#[test]
+fn test_custom_element_in_for_loop() {
+    let names = ["a", "b"];
+    let list = html! {
+        div {
+            for name in #names use key = name {
+                element("x-item").data_name(#name) { #name }
+            } else {
+                "none"
+            }
+        }
+    };
+    assert_eq!(
+        list.render(),
+        concat!(
+            r#"<div><x-item data-name="a" data-ih-key="a">a</x-item>"#,
+            r#"<x-item data-name="b" data-ih-key="b">b</x-item></div>"#
+        )
+    );
+
+    let empty: [&str; 0] = [];
+    let list = html! {
+        div {
+            for name in #empty {
+                element("x-item") { #name }
+            } else {
+                "none"
+            }
+        }
+    };
+    assert_eq!(list.render(), "<div>none</div>");
+}
+
+#[test]
+fn test_keyed_for_loop() {
+    struct Row {
+        id: u32,
+        name: &'static str,
+    }
+    let rows = [
+        Row { id: 7, name: "Ada" },
+        Row {
+            id: 9,
+            name: "Grace",
+        },
+    ];
+    let list = html! {
+        ul {
+            for row in #&rows use key = row.id {
+                li.class("row") { #row.name }
+            }
+        }
+    };
+    assert_eq!(
+        list.render(),
+        r#"<ul><li class="row" data-ih-key="7">Ada</li><li class="row" data-ih-key="9">Grace</li></ul>"#
+    );
+}
+
+#[test]
+fn test_key_attribute_in_loop() {
+    let rows = [("a1", "Ada"), ("g2", "Grace")];
+    let list = html! {
+        ul {
+            for (key, name) in #rows {
+                li.key(key) { #name }
+            }
+        }
+    };
+    assert_eq!(list.render(), "<ul><li>Ada</li><li>Grace</li></ul>");
+    assert_eq!(
+        list.to_json(),
+        concat!(
+            r#"{"tag":"ul","attrs":{},"children":["#,
+            r#"{"tag":"li","key":"a1","attrs":{},"children":["Ada"]},"#,
+            r#"{"tag":"li","key":"g2","attrs":{},"children":["Grace"]}]}"#
+        )
+    );
+}
+
+#[test]
+fn test_for_loop_else() {
+    fn results(items: &[&str]) -> String {
+        html! {
+            ul {
+                for item in #items.iter().copied().filter(|i| !i.is_empty()) {
+                    li { #item }
+                } else {
+                    li.class("empty") { "No results" }
+                    li { "Try another search" }
+                }
+            }
+        }
+        .render()
+    }
+
+    assert_eq!(
+        results(&[]),
+        r#"<ul><li class="empty">No results</li><li>Try another search</li></ul>"#
+    );
+    assert_eq!(results(&[""]), results(&[]));
+    assert_eq!(results(&["a", "b"]), "<ul><li>a</li><li>b</li></ul>");
+}
+
+#[test]
+fn test_while_loops() {
+    struct Row {
+        name: &'static str,
+    }
+
+    let mut rows = vec![Row { name: "b" }, Row { name: "<a>" }];
+    let elem = html! {
+        table {
+            while let Some(row) = #rows.pop() {
+                tr { td { #row.name } }
+            }
+        }
+    };
+    assert_eq!(
+        elem.render(),
+        "<table><tr><td>&lt;a&gt;</td></tr><tr><td>b</td></tr></table>"
+    );
+    assert!(rows.is_empty());
+
+    let mut n = 0;
+    let elem = html! {
+        ol {
+            while #n < 3 {
+                li { #{ n += 1; n.to_string() } }
+            }
+        }
+    };
+    assert_eq!(elem.render(), "<ol><li>1</li><li>2</li><li>3</li></ol>");
+}
+
+#[test]
+fn test_fragments() {
+    let intro = html! {
+        frag {
+            h1 { "Title" }
+            p { "Lead" }
+            "trailing text"
+        }
+    };
+    assert_eq!(intro.render(), "<h1>Title</h1><p>Lead</p>trailing text");
+    assert_eq!(format!("{intro}"), intro.render());
+
+    let main = html! { div { { intro.clone() } } };
+    assert_eq!(
+        main.render(),
+        "<div><h1>Title</h1><p>Lead</p>trailing text</div>"
+    );
+
+    // A borrowed fragment adds copies of its children.
+    let page = html! { div { { &intro } footer {} } };
+    assert_eq!(
+        page.render(),
+        "<div><h1>Title</h1><p>Lead</p>trailing text<footer></footer></div>"
+    );
+
+    // Nested in an element, a fragment's children join the element.
+    let row = html! { tr { frag { td { "a" } td { "b" } } } };
+    assert_eq!(row.render(), "<tr><td>a</td><td>b</td></tr>");
+
+    let cells = html! { frag<tr> { td { "1" } td { "2" } } };
+    let row = html! { tr { { cells } } };
+    assert_eq!(row.render(), "<tr><td>1</td><td>2</td></tr>");
+}
+
+#[test]
+fn test_let_bindings() {
+    let users = [("Ada", 36), ("Alan", 41)];
+    let elem = html! {
+        ul {
+            let total = users.len();
+            li { #format!("{total} users") }
+            for (name, age) in #users {
+                li {
+                    let label = format!("{name} ({age})");
+                    let label = label.to_uppercase();
+                    #label
+                }
+            }
+            let (first, _) = users[0];
+            li.class("first") { #first }
+        }
+    };
+    assert_eq!(
+        elem.render(),
+        "<ul><li>2 users</li><li>ADA (36)</li><li>ALAN (41)</li>\
+         <li class=\"first\">Ada</li></ul>"
+    );
+
+    let top = html! {
+        let title = "Top";
+        h1 { #title }
+    };
+    assert_eq!(top.render(), "<h1>Top</h1>");
+
+    let nested = html! {
+        let title = "Top";
+        let title = title.to_uppercase();
+        header { h1 { #title } }
+    };
+    assert_eq!(nested.render(), "<header><h1>TOP</h1></header>");
+}
+
 #[test]
 fn test_conditional() {
     let show = true;
@@ -192,6 +657,47 @@ fn test_conditional_false() {
     assert_eq!(elem.render(), "<div></div>");
 }
 
+#[test]
+fn test_match() {
+    enum Status {
+        Ok,
+        Warn(u32),
+        Err(&'static str),
+    }
+
+    let render = |status: Status| {
+        html! {
+            ul {
+                li { "status: " }
+                match #status {
+                    Status::Ok => { li.class("ok") { "ok" } },
+                    Status::Warn(n) if n > 1 => { li { #n.to_string() " warnings" } }
+                    Status::Warn(_) => {}
+                    Status::Err(e) => {
+                        li.class("err") { #e }
+                        li { "retry" }
+                    }
+                }
+            }
+        }
+        .render()
+    };
+
+    assert_eq!(
+        render(Status::Ok),
+        r#"<ul><li>status: </li><li class="ok">ok</li></ul>"#
+    );
+    assert_eq!(
+        render(Status::Warn(3)),
+        "<ul><li>status: </li><li>3 warnings</li></ul>"
+    );
+    assert_eq!(render(Status::Warn(1)), "<ul><li>status: </li></ul>");
+    assert_eq!(
+        render(Status::Err("<down>")),
+        r#"<ul><li>status: </li><li class="err">&lt;down&gt;</li><li>retry</li></ul>"#
+    );
+}
+
 #[test]
 fn test_table() {
     let elem = html! {
@@ -279,6 +785,141 @@ fn test_aria_attribute() {
     );
 }
 
+#[test]
+fn test_event_handler_attribute() {
+    let name = "Ada";
+    let button = html! {
+        button.on(click, #format!("greet('{name}')")).on(keydown, "key(event)") { "Hi" }
+    };
+    assert_eq!(
+        button.render(),
+        r#"<button onclick="greet(&#x27;Ada&#x27;)" onkeydown="key(event)">Hi</button>"#
+    );
+
+    let link = html! { a.href("#").on(click, r#"alert("<&>")"#) };
+    assert_eq!(
+        link.render(),
+        r##"<a href="#" onclick="alert(&quot;&lt;&amp;&gt;&quot;)"></a>"##
+    );
+}
+
+#[test]
+fn test_window_event_handler_on_body() {
+    let body = html! {
+        body.on(hashchange, "route()").on(popstate, "route()") { main {} }
+    };
+    assert_eq!(
+        body.render(),
+        r#"<body onhashchange="route()" onpopstate="route()"><main></main></body>"#
+    );
+}
+
+#[test]
+fn test_string_literal_attribute_name() {
+    let count = 3;
+    let elem = html! {
+        button.attr("@click", "open = !open").attr(":class", #count.to_string()).attr("x-on:keydown.escape", "open = false") {
+            "Toggle"
+        }
+    };
+    assert_eq!(
+        elem.render(),
+        r#"<button @click="open = !open" :class="3" x-on:keydown.escape="open = false">Toggle</button>"#
+    );
+}
+
+#[test]
+fn test_string_literal_attribute_value_escaped() {
+    let elem = html! { div.attr("x-data", "{ label: \"<b>\" }") };
+    assert_eq!(
+        elem.render(),
+        r#"<div x-data="{ label: &quot;&lt;b&gt;&quot; }"></div>"#
+    );
+}
+
+#[test]
+fn test_attribute_spread() {
+    let extra: Vec<(String, String)> = vec![
+        ("id".into(), "first".into()),
+        ("data-x".into(), "<1>".into()),
+    ];
+    let more = [("id", "last")];
+    let elem = html! {
+        div {
+            span ..(extra.clone()).class("a") ..more { "x" }
+            br ..extra
+        }
+    };
+    assert_eq!(
+        elem.render(),
+        r#"<div><span id="last" data-x="&lt;1&gt;" class="a">x</span><br id="first" data-x="&lt;1&gt;" /></div>"#
+    );
+}
+
+#[test]
+fn test_script_and_style_text_is_not_escaped() {
+    let limit = "10";
+    let head = html! {
+        head {
+            script { "if (a < b && b > 0) { n = " #limit "; }" }
+            style { "ul > li::after { content: \"&\"; }" }
+        }
+    };
+    assert_eq!(
+        head.render(),
+        "<head><script>if (a < b && b > 0) { n = 10; }</script>\
+         <style>ul > li::after { content: \"&\"; }</style></head>"
+    );
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "<script> text must not contain `</script`")]
+fn test_script_text_rejects_end_tag() {
+    let hostile = "</script><script>alert(1)</script>";
+    let _ = html! { script { "let s = '" #hostile "';" } }.render();
+}
+
+#[cfg(not(debug_assertions))]
+#[test]
+fn test_script_text_cannot_close_element() {
+    let hostile = "</script><script>alert(1)</script>";
+    let script = html! { script { "let s = '" #hostile "';" } };
+    assert_eq!(
+        script.render(),
+        r"<script>let s = '<\/script><script>alert(1)<\/script>';</script>"
+    );
+}
+
+#[test]
+fn test_raw_html() {
+    let markdown = String::from("<p>Hi <b>there</b></p>");
+    let elem = html! {
+        div {
+            @raw markdown.as_str()
+            p { @raw "&copy; 2024" }
+        }
+    };
+    assert_eq!(
+        elem.render(),
+        "<div><p>Hi <b>there</b></p><p>&copy; 2024</p></div>"
+    );
+}
+
+#[test]
+fn test_adjacent_text_is_one_node() {
+    let name = "<Ada>";
+    let elem = html! { p { "a" "b" } };
+    assert_eq!(elem.render(), "<p>ab</p>");
+    let TypedNode::Element { children, .. } = elem.into_node() else {
+        unreachable!()
+    };
+    assert_eq!(children, [TypedNode::Text("ab".into())]);
+
+    let greeting = html! { p { "Hello, " #name "!" } };
+    assert_eq!(greeting.render(), "<p>Hello, &lt;Ada&gt;!</p>");
+}
+
 #[test]
 fn test_complex_page() {
     let title = "My Page";
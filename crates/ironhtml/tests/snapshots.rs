@@ -0,0 +1,60 @@
+//! Snapshot tests of pretty-printed output, using `insta`.
+//!
+//! Run `cargo insta review` (or set `INSTA_UPDATE=always`) after an
+//! intended change to the rendered markup to update the stored snapshots.
+
+#![cfg(feature = "typed")]
+
+use ironhtml::typed::{Document, Element};
+use ironhtml_attributes::{AriaLive, Role};
+use ironhtml_elements::{
+    Body, Div, Footer, Head, Header, Html, Li, Main, Meta, Nav, Title, Ul, A, H1, P,
+};
+
+fn page() -> Document {
+    Document::new().doctype().root::<Html, _>(|html| {
+        html.attr("lang", "en")
+            .child::<Head, _>(|h| {
+                h.child::<Meta, _>(|m| m.attr("charset", "utf-8"))
+                    .child::<Title, _>(|t| t.text("Snapshot"))
+            })
+            .child::<Body, _>(|body| {
+                body.child::<Header, _>(|h| {
+                    h.child::<Nav, _>(|nav| {
+                        nav.child::<Ul, _>(|ul| {
+                            ul.child::<Li, _>(|li| {
+                                li.child::<A, _>(|a| a.attr("href", "/").text("Home"))
+                            })
+                            .child::<Li, _>(|li| {
+                                li.child::<A, _>(|a| a.attr("href", "/about").text("About"))
+                            })
+                        })
+                    })
+                })
+                .child::<Main, _>(|main| {
+                    main.child::<H1, _>(|h| h.text("Hello & welcome"))
+                        .child::<P, _>(|p| {
+                            p.text("Read the ")
+                                .child::<A, _>(|a| a.attr("href", "/docs").text("docs"))
+                                .text(".")
+                        })
+                        .child::<Div, _>(|d| d.role(Role::Status).aria_live(AriaLive::Polite))
+                })
+                .child::<Footer, _>(|f| f.child::<P, _>(|p| p.text("Footer")))
+            })
+    })
+}
+
+#[test]
+fn snapshot_page() {
+    insta::assert_snapshot!(page().render_pretty());
+}
+
+#[test]
+fn snapshot_element() {
+    let list = Element::<Ul>::new()
+        .class("items")
+        .child::<Li, _>(|li| li.text("One"))
+        .child::<Li, _>(|li| li.text("Two"));
+    insta::assert_snapshot!(list.render_pretty());
+}
@@ -0,0 +1,7 @@
+use ironhtml::html;
+
+fn main() {
+    let _ = html! { div { } .class("x") };
+    let _ = html! { ul { li { "a" } .id("first") } };
+    let _ = html! { div { } ..extra };
+}
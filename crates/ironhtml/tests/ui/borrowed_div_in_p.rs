@@ -0,0 +1,6 @@
+use ironhtml::html;
+
+fn main() {
+    let card = html! { div { "card" } };
+    let _ = html! { p { { &card } } };
+}
@@ -0,0 +1,6 @@
+use ironhtml::typed::Element;
+use ironhtml_elements::{Img, Span};
+
+fn main() {
+    let _ = Element::<Img>::new().child::<Span, _>(|s| s);
+}
@@ -0,0 +1,18 @@
+use core::fmt::{self, Write};
+use ironhtml::typed::{Component, Element};
+use ironhtml::Render;
+use ironhtml_elements::Input;
+
+struct Icon;
+
+impl Render for Icon {
+    fn render_into(&self, sink: &mut impl Write) -> fmt::Result {
+        sink.write_str("<svg></svg>")
+    }
+}
+
+impl Component for Icon {}
+
+fn main() {
+    let _ = Element::<Input>::new().append_child(&Icon);
+}
@@ -0,0 +1,6 @@
+use ironhtml::html;
+
+fn main() {
+    let _ = html! { ol { p {} } };
+    let _ = html! { menu { span {} } };
+}
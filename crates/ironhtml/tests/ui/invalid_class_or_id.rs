@@ -0,0 +1,9 @@
+use ironhtml::html;
+
+fn main() {
+    let _ = html! { div.id("has space") };
+    let _ = html! { div.id("") };
+    let _ = html! { div.class("btn btn") };
+    let _ = html! { div.class("card\u{a0}body") };
+    let _ = html! { div.class(" ") };
+}
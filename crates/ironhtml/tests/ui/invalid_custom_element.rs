@@ -0,0 +1,7 @@
+use ironhtml::html;
+
+fn main() {
+    let _ = html! { div { element("widget") } };
+    let _ = html! { element("My-Widget") };
+    let _ = html! { element("font-face") };
+}
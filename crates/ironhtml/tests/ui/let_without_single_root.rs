@@ -0,0 +1,7 @@
+use ironhtml::html;
+
+fn main() {
+    let _ = html! { let x = 1; div { #x } span {} };
+    let _ = html! { let x = 1; #x };
+    let _ = html! { let x = 1; };
+}
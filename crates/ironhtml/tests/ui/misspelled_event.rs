@@ -0,0 +1,7 @@
+use ironhtml::html;
+
+fn main() {
+    let _ = html! { button.on(Click, "go()") };
+    let _ = html! { button.on(onclick, "go()") };
+    let _ = html! { div.on(frobnicate, "go()") };
+}
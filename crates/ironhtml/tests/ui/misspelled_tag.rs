@@ -0,0 +1,6 @@
+use ironhtml::html;
+
+fn main() {
+    let _ = html! { section { diiv { "Hello" } } };
+    let _ = html! { my_widget };
+}
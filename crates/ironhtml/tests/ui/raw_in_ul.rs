@@ -0,0 +1,5 @@
+use ironhtml::html;
+
+fn main() {
+    let _ = html! { ul { @raw "<li>x</li>" } };
+}
@@ -0,0 +1,6 @@
+use ironhtml::typed::Element;
+use ironhtml_elements::Br;
+
+fn main() {
+    let _ = Element::<Br>::new().text("line");
+}
@@ -0,0 +1,8 @@
+use ironhtml::html;
+
+fn main() {
+    let _ = html! { div { <p>"hi"</p> } };
+    let _ = html! { div { 42 } };
+    let _ = html! { div { ; } };
+    let _ = html! { p { "a" } p { "b" } };
+}